@@ -1,7 +1,7 @@
 set -euxo pipefail
 
 main() {
-    :
+    rustup component add clippy
 }
 
 main
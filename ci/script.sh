@@ -2,7 +2,28 @@ set -euxo pipefail
 
 main() {
     cargo check
+    cargo check --no-default-features
+    cargo check --features cmsis-dsp
+    cargo check --features fusion
+    cargo check --features critical-section
+    cargo check --features embedded-graphics
+    cargo check --features heapless
+    cargo check --features rand_core
+    cargo check --features approx,bytemuck,cgmath,fixed,glam,half,heapless,nalgebra,proptest,quickcheck,rkyv,serde,std,ufmt,zerocopy
+    cargo check --features fast-math
+    cargo check --features fixed,half,num-traits
+    cargo clippy --all-targets -- -D warnings
+    cargo clippy --all-targets --features approx,bytemuck,cgmath,fixed,glam,half,heapless,nalgebra,proptest,quickcheck,rkyv,serde,std,ufmt,zerocopy -- -D warnings
     cargo test
+    cargo test --features fusion
+    cargo test --features critical-section
+    cargo test --features embedded-graphics
+    cargo test --features heapless
+    cargo test --features rand_core
+    cargo test --features approx,bytemuck,cgmath,fixed,glam,half,heapless,nalgebra,proptest,quickcheck,rkyv,serde,std,ufmt,zerocopy
+    cargo test --features fast-math
+    cargo test --features fixed,half,num-traits
+    RUSTDOCFLAGS="-C opt-level=3" cargo test --release --features no-panic
 }
 
 main
@@ -1,56 +1,98 @@
 #![deny(warnings)]
-#![feature(proc_macro)]
 
 extern crate proc_macro;
+extern crate proc_macro2;
 #[macro_use]
 extern crate quote;
-#[macro_use]
 extern crate syn;
 
 use proc_macro::TokenStream;
+use proc_macro2::Span;
+use syn::parse::{Parse, ParseStream, Result};
 use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
-use syn::synom::Synom;
-use syn::{Expr, ExprArray, Ident};
+use syn::{Error, Expr, ExprArray, Ident, Token};
 
 struct Mat {
     rows: Punctuated<ExprArray, Token![,]>,
 }
 
-impl Synom for Mat {
-    named!(parse -> Self, do_parse!(
-        rows: call!(Punctuated::parse_terminated_nonempty) >> (Mat { rows })
-    ));
+impl Parse for Mat {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Mat {
+            rows: Punctuated::parse_terminated(input)?,
+        })
+    }
 }
 
 /// A macro to construct matrices
 #[proc_macro]
 pub fn mat(input: TokenStream) -> TokenStream {
-    let mat: Mat = syn::parse(input).unwrap();
+    match syn::parse(input).and_then(|mat| expand(&mat)) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => compile_errors(e).into(),
+    }
+}
 
+// NOTE `Error::to_compile_error` expands to `::core::compile_error!`, which doesn't resolve in 2015
+// edition crates
+fn compile_errors(e: Error) -> proc_macro2::TokenStream {
+    let errors = e.into_iter().map(|e| {
+        let msg = e.to_string();
+        quote_spanned!(e.span()=> compile_error!(#msg);)
+    });
+
+    quote!({ #(#errors)* })
+}
+
+fn expand(mat: &Mat) -> Result<proc_macro2::TokenStream> {
     // check consistent number of columns
     let nrows = mat.rows.len();
-    let ncols = mat.rows.iter().next().expect("BUG: zero rows").elems.len();
-
-    for row in mat.rows.iter() {
-        for (i, expr) in row.elems.iter().enumerate() {
-            if i >= ncols {
-                expr.span()
-                    .unstable()
-                    .error(format!("expected {} elements", ncols,))
-                    .emit();
+    let ncols = match mat.rows.iter().next() {
+        Some(row) => row.elems.len(),
+        None => {
+            return Err(Error::new(
+                Span::call_site(),
+                "expected at least one row, e.g. `mat![[1, 2]]`",
+            ))
+        }
+    };
+
+    let mut errors: Option<Error> = None;
+    {
+        let mut error = |span: Span| {
+            let e = Error::new(span, format!("expected {} elements", ncols));
+            match errors {
+                Some(ref mut errors) => errors.combine(e),
+                None => errors = Some(e),
+            }
+        };
+
+        for row in mat.rows.iter() {
+            if row.elems.len() < ncols {
+                error(row.span());
+            }
+
+            for (i, expr) in row.elems.iter().enumerate() {
+                if i >= ncols {
+                    error(expr.span());
+                }
             }
         }
     }
 
+    if let Some(errors) = errors {
+        return Err(errors);
+    }
+
     let size = nrows * ncols;
     let elems: Vec<&Expr> = mat.rows.iter().flat_map(|row| row.elems.iter()).collect();
 
-    let nrows_ty = Ident::from(format!("U{}", nrows));
-    let ncols_ty = Ident::from(format!("U{}", ncols));
+    let nrows_ty = Ident::new(&format!("U{}", nrows), Span::call_site());
+    let ncols_ty = Ident::new(&format!("U{}", ncols), Span::call_site());
 
-    quote!(unsafe {
+    Ok(quote!(unsafe {
         extern crate mat;
         mat::Mat::<_, [_; #size], mat::typenum::#nrows_ty, mat::typenum::#ncols_ty>::new([#(#elems,)*])
-    }).into()
+    }))
 }
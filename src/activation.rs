@@ -0,0 +1,238 @@
+//! Activation functions
+//!
+//! Element-wise activations and a row-wise softmax, the usual steps that follow the matrix
+//! multiplication of a neural network layer. `sigmoid`, `tanh` and `softmax_rows` evaluate the
+//! exponential function through the `Exp` trait; on targets where that's too slow a `Lut` can
+//! approximate any activation by interpolating a table.
+//!
+//! # Example
+//!
+//! ```
+//! extern crate mat;
+//! extern crate typenum;
+//!
+//! use mat::activation;
+//! use mat::MatGen;
+//! use mat::traits::Matrix;
+//! use typenum::{U2, U3};
+//!
+//! # fn main() {
+//! // logits of two samples, one per row
+//! let logits = MatGen::<f32, U2, U3>::from_fn(|r, c| [[1., 2., 3.], [-1., 0., 1000.]][r][c]);
+//!
+//! let relu = activation::relu(&logits);
+//! assert_eq!(relu.get(1, 0), 0.);
+//!
+//! // each row of probabilities sums to one, even when an exponential would overflow
+//! let p = activation::softmax_rows(&logits);
+//! assert!((p.get(0, 0) + p.get(0, 1) + p.get(0, 2) - 1.).abs() < 1e-6);
+//! assert_eq!(p.get(1, 2), 1.);
+//! # }
+//! ```
+
+use core::ops;
+
+use generic_array::{ArrayLength, GenericArray};
+use typenum::Prod;
+
+use traits::{Exp, Matrix, One, Zero};
+use {array, MatGen};
+
+/// Returns `max(x, 0)` for every element `x` of `m`
+pub fn relu<M, T>(m: M) -> MatGen<T, M::NROWS, M::NCOLS>
+where
+    M: Matrix<Elem = T>,
+    M::NROWS: ops::Mul<M::NCOLS>,
+    Prod<M::NROWS, M::NCOLS>: ArrayLength<T>,
+    T: Clone + PartialOrd + Zero,
+{
+    map(m, |x| if x > T::zero() { x } else { T::zero() })
+}
+
+/// Returns `x` if `x` is positive, and `alpha x` otherwise, for every element `x` of `m`
+pub fn leaky_relu<M, T>(m: M, alpha: T) -> MatGen<T, M::NROWS, M::NCOLS>
+where
+    M: Matrix<Elem = T>,
+    M::NROWS: ops::Mul<M::NCOLS>,
+    Prod<M::NROWS, M::NCOLS>: ArrayLength<T>,
+    T: ops::Mul<T, Output = T> + Clone + PartialOrd + Zero,
+{
+    map(m, |x| if x > T::zero() { x } else { alpha.clone() * x })
+}
+
+/// Returns the logistic function, `1 / (1 + e^-x)`, of every element `x` of `m`
+pub fn sigmoid<M, T>(m: M) -> MatGen<T, M::NROWS, M::NCOLS>
+where
+    M: Matrix<Elem = T>,
+    M::NROWS: ops::Mul<M::NCOLS>,
+    Prod<M::NROWS, M::NCOLS>: ArrayLength<T>,
+    T: ops::Add<T, Output = T>
+        + ops::Div<T, Output = T>
+        + ops::Neg<Output = T>
+        + Clone
+        + Exp
+        + One,
+{
+    // NOTE `e^-x` overflows to infinity for large negative `x`, which yields the right limit, 0
+    map(m, |x| T::one() / (T::one() + (-x).exp()))
+}
+
+/// Returns the hyperbolic tangent of every element `x` of `m`
+pub fn tanh<M, T>(m: M) -> MatGen<T, M::NROWS, M::NCOLS>
+where
+    M: Matrix<Elem = T>,
+    M::NROWS: ops::Mul<M::NCOLS>,
+    Prod<M::NROWS, M::NCOLS>: ArrayLength<T>,
+    T: ops::Add<T, Output = T>
+        + ops::Div<T, Output = T>
+        + ops::Mul<T, Output = T>
+        + ops::Sub<T, Output = T>
+        + Clone
+        + Exp
+        + One,
+{
+    // `tanh(x) = 1 - 2 / (e^2x + 1)`, which has the right limits when `e^2x` overflows
+    map(m, |x| {
+        let two = T::one() + T::one();
+        T::one() - two.clone() / ((two * x).exp() + T::one())
+    })
+}
+
+/// Applies the softmax function to each row of `m`
+///
+/// The elements of each row of the result are positive and sum to one. The maximum of each row is
+/// subtracted before exponentiating so large inputs don't overflow.
+pub fn softmax_rows<M, T>(m: M) -> MatGen<T, M::NROWS, M::NCOLS>
+where
+    M: Matrix<Elem = T>,
+    M::NROWS: ops::Mul<M::NCOLS>,
+    Prod<M::NROWS, M::NCOLS>: ArrayLength<T>,
+    T: ops::Add<T, Output = T>
+        + ops::Div<T, Output = T>
+        + ops::Sub<T, Output = T>
+        + Clone
+        + Exp
+        + PartialOrd
+        + Zero,
+{
+    let ncols = m.ncols();
+    let mut out = map(m, |x| x);
+
+    // NOTE `max` because `chunks_mut` panics when `ncols` is zero, in which case there are no rows
+    // to iterate over anyway
+    for row in out.as_mut_slice().chunks_mut(ncols.max(1)) {
+        let mut max = row[0].clone();
+        for x in row.iter().skip(1) {
+            if *x > max {
+                max = x.clone();
+            }
+        }
+
+        let mut sum = T::zero();
+        for x in row.iter_mut() {
+            *x = (x.clone() - max.clone()).exp();
+            sum = sum + x.clone();
+        }
+
+        for x in row.iter_mut() {
+            *x = x.clone() / sum.clone();
+        }
+    }
+
+    out
+}
+
+/// A function tabulated at `N` evenly spaced points, evaluated by linear interpolation
+///
+/// # Example
+///
+/// ```
+/// extern crate mat;
+/// extern crate typenum;
+///
+/// use mat::activation::Lut;
+/// use typenum::U64;
+///
+/// # fn main() {
+/// // NOTE the table can be built once, at startup, with a slow but accurate `exp`
+/// let sigmoid = Lut::<U64>::new(|x| 1. / (1. + (-x).exp()), -8., 8.);
+///
+/// assert!((sigmoid.eval(0.) - 0.5).abs() < 1e-3);
+/// assert!((sigmoid.eval(1.) - 0.7311).abs() < 1e-3);
+///
+/// // inputs outside the table saturate
+/// assert_eq!(sigmoid.eval(100.), sigmoid.eval(8.));
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct Lut<N>
+where
+    N: ArrayLength<f32>,
+{
+    table: GenericArray<f32, N>,
+    lo: f32,
+    hi: f32,
+}
+
+impl<N> Lut<N>
+where
+    N: ArrayLength<f32>,
+{
+    /// Tabulates `f` at `N` evenly spaced points from `lo` to `hi`
+    ///
+    /// # Panics
+    ///
+    /// This operation panics if `N` is less than two or if `lo` is not less than `hi`
+    pub fn new<F>(f: F, lo: f32, hi: f32) -> Self
+    where
+        F: Fn(f32) -> f32,
+    {
+        let n = N::to_usize();
+        assert!(n >= 2 && lo < hi);
+
+        let step = (hi - lo) / (n - 1) as f32;
+        Lut {
+            table: array::generate(|i| f(lo + i as f32 * step)),
+            lo,
+            hi,
+        }
+    }
+
+    /// Evaluates the tabulated function at `x`
+    ///
+    /// Inputs below `lo` (above `hi`) return the value at `lo` (`hi`).
+    pub fn eval(&self, x: f32) -> f32 {
+        let n = N::to_usize();
+        if x <= self.lo {
+            return self.table[0];
+        } else if x >= self.hi {
+            return self.table[n - 1];
+        }
+
+        let t = (x - self.lo) / (self.hi - self.lo) * (n - 1) as f32;
+        let i = (t as usize).min(n - 2);
+        let frac = t - i as f32;
+        self.table[i] + (self.table[i + 1] - self.table[i]) * frac
+    }
+
+    /// Evaluates the tabulated function at every element of `m`
+    pub fn apply<M>(&self, m: M) -> MatGen<f32, M::NROWS, M::NCOLS>
+    where
+        M: Matrix<Elem = f32>,
+        M::NROWS: ops::Mul<M::NCOLS>,
+        Prod<M::NROWS, M::NCOLS>: ArrayLength<f32>,
+    {
+        map(m, |x| self.eval(x))
+    }
+}
+
+fn map<M, T, F>(m: M, f: F) -> MatGen<T, M::NROWS, M::NCOLS>
+where
+    M: Matrix<Elem = T>,
+    M::NROWS: ops::Mul<M::NCOLS>,
+    Prod<M::NROWS, M::NCOLS>: ArrayLength<T>,
+    T: Clone,
+    F: Fn(T) -> T,
+{
+    MatGen::from_fn(|r, c| f(unsafe { m.unsafe_get(r, c) }))
+}
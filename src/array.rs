@@ -0,0 +1,125 @@
+//! `GenericArray` construction and slice views
+//!
+//! Arrays are built by `generic-array`'s `generate`, which writes the elements into
+//! `MaybeUninit` storage one at a time, so no array is ever observed uninitialized. Zero length
+//! arrays are `[T; 0]`, which have the alignment of `T`, so they are viewed as slices like any
+//! other array. Arrays whose elements can fail to be produced are filled by a `Builder`, which
+//! only ever drops the elements it has initialized.
+
+use core::mem::{ManuallyDrop, MaybeUninit};
+use core::ptr;
+
+use generic_array::sequence::GenericSequence;
+use generic_array::{ArrayLength, GenericArray};
+
+/// Creates an array where the element at index `i` is `f(i)`
+///
+/// `f` is called once per element, in order of increasing index.
+pub fn generate<T, N, F>(f: F) -> GenericArray<T, N>
+where
+    N: ArrayLength<T>,
+    F: FnMut(usize) -> T,
+{
+    GenericArray::generate(f)
+}
+
+/// Creates an array where the element at index `i` is `f(i)`, stopping at the first error
+///
+/// `f` is called in order of increasing index; the elements produced before an error are dropped.
+#[cfg(feature = "rkyv")]
+pub fn try_generate<T, N, E, F>(mut f: F) -> Result<GenericArray<T, N>, E>
+where
+    N: ArrayLength<T>,
+    F: FnMut(usize) -> Result<T, E>,
+{
+    let mut builder = Builder::new();
+    while builder.len != N::to_usize() {
+        let elem = f(builder.len)?;
+        unsafe { builder.write(elem) }
+    }
+
+    Ok(unsafe { builder.assume_init() })
+}
+
+/// An array that is filled one element at a time, in order of increasing index
+pub struct Builder<T, N>
+where
+    N: ArrayLength<T>,
+{
+    array: MaybeUninit<GenericArray<T, N>>,
+    // NOTE the elements at `..len` are initialized
+    len: usize,
+}
+
+impl<T, N> Builder<T, N>
+where
+    N: ArrayLength<T>,
+{
+    /// Creates a builder with no initialized elements
+    pub fn new() -> Self {
+        Builder {
+            array: MaybeUninit::uninit(),
+            len: 0,
+        }
+    }
+
+    /// Writes `elem` after the last initialized element; gives `elem` back if the array is full
+    pub fn push(&mut self, elem: T) -> Result<(), T> {
+        if self.len == N::to_usize() {
+            return Err(elem);
+        }
+
+        unsafe { self.write(elem) }
+        Ok(())
+    }
+
+    /// Returns the array if all its elements have been initialized
+    pub fn finish(self) -> Option<GenericArray<T, N>> {
+        if self.len != N::to_usize() {
+            return None;
+        }
+
+        Some(unsafe { self.assume_init() })
+    }
+
+    // NOTE the array must not be full
+    unsafe fn write(&mut self, elem: T) {
+        ptr::write((self.array.as_mut_ptr() as *mut T).add(self.len), elem);
+        self.len += 1;
+    }
+
+    // NOTE the array must be full
+    unsafe fn assume_init(self) -> GenericArray<T, N> {
+        // the elements are now owned by the returned array so `Drop` must not run
+        let this = ManuallyDrop::new(self);
+        ptr::read(this.array.as_ptr())
+    }
+}
+
+impl<T, N> Drop for Builder<T, N>
+where
+    N: ArrayLength<T>,
+{
+    fn drop(&mut self) {
+        unsafe {
+            let elems = self.array.as_mut_ptr() as *mut T;
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(elems, self.len));
+        }
+    }
+}
+
+/// Views the array as a slice
+pub fn as_slice<T, N>(array: &GenericArray<T, N>) -> &[T]
+where
+    N: ArrayLength<T>,
+{
+    array
+}
+
+/// Views the array as a mutable slice
+pub fn as_mut_slice<T, N>(array: &mut GenericArray<T, N>) -> &mut [T]
+where
+    N: ArrayLength<T>,
+{
+    array
+}
@@ -0,0 +1,295 @@
+//! Block matrices
+//!
+//! `hcat` and `vcat` concatenate two matrices side by side and one on top of the other. The
+//! dimensions of the result are the `typenum` sums of the dimensions of the operands, and operands
+//! whose other dimension doesn't match are rejected at compile time. `block_mat!` builds a matrix
+//! from a grid of blocks with these two operations.
+//!
+//! `block_diag` places two matrices along the diagonal of a larger one, their direct sum, e.g. to
+//! combine the covariances of independent subsystems; `block_diag!` does the same for any number of
+//! matrices.
+//!
+//! # Example
+//!
+//! ```
+//! extern crate mat;
+//! extern crate typenum;
+//!
+//! use mat::{block_mat, MatGen};
+//! use typenum::{U1, U2, U3};
+//!
+//! # fn main() {
+//! let a = MatGen::<i32, U2, U2>::from_fn(|r, c| [[1, 2], [3, 4]][r][c]);
+//! let b = MatGen::<i32, U2, U1>::from_fn(|r, _| [5, 6][r]);
+//! let i = MatGen::<i32, U1, U3>::from_fn(|_, c| if c == 2 { 1 } else { 0 });
+//!
+//! // augmented matrix `[A b]` with an extra row
+//! let m = block_mat![
+//!     [&a, &b],
+//!     [&i],
+//! ];
+//!
+//! assert_eq!(m, MatGen::<i32, U3, U3>::from_fn(|r, c| [[1, 2, 5], [3, 4, 6], [0, 0, 1]][r][c]));
+//! # }
+//! ```
+
+use core::{fmt, ops};
+
+use generic_array::ArrayLength;
+use typenum::{Prod, Sum, Unsigned};
+
+use traits::{Matrix, UnsafeGet, Zero};
+use {text, MatGen};
+
+/// Returns the matrix `[l r]`, with `l` on the left and `r` on the right
+pub fn hcat<L, R, T>(l: L, r: R) -> MatGen<T, L::NROWS, Sum<L::NCOLS, R::NCOLS>>
+where
+    L: Matrix<Elem = T>,
+    R: Matrix<Elem = T, NROWS = L::NROWS>,
+    L::NCOLS: ops::Add<R::NCOLS>,
+    Sum<L::NCOLS, R::NCOLS>: Unsigned,
+    L::NROWS: ops::Mul<Sum<L::NCOLS, R::NCOLS>>,
+    Prod<L::NROWS, Sum<L::NCOLS, R::NCOLS>>: ArrayLength<T>,
+    T: Clone,
+{
+    HCat { l, r }.eval()
+}
+
+/// Returns the matrix `[t; b]`, with `t` on top and `b` at the bottom
+pub fn vcat<U, B, T>(t: U, b: B) -> MatGen<T, Sum<U::NROWS, B::NROWS>, U::NCOLS>
+where
+    U: Matrix<Elem = T>,
+    B: Matrix<Elem = T, NCOLS = U::NCOLS>,
+    U::NROWS: ops::Add<B::NROWS>,
+    Sum<U::NROWS, B::NROWS>: Unsigned,
+    Sum<U::NROWS, B::NROWS>: ops::Mul<U::NCOLS>,
+    Prod<Sum<U::NROWS, B::NROWS>, U::NCOLS>: ArrayLength<T>,
+    T: Clone,
+{
+    VCat { t, b }.eval()
+}
+
+/// The lazy `[l r]`, which `block_mat!` evaluates once
+#[doc(hidden)]
+#[derive(Clone, Copy)]
+pub struct HCat<L, R> {
+    l: L,
+    r: R,
+}
+
+#[doc(hidden)]
+pub fn lazy_hcat<L, R>(l: L, r: R) -> HCat<L, R> {
+    HCat { l, r }
+}
+
+impl<L, R, T> Matrix for HCat<L, R>
+where
+    L: Matrix<Elem = T>,
+    R: Matrix<Elem = T, NROWS = L::NROWS>,
+    L::NCOLS: ops::Add<R::NCOLS>,
+    Sum<L::NCOLS, R::NCOLS>: Unsigned,
+    T: Clone,
+{
+    type NROWS = L::NROWS;
+    type NCOLS = Sum<L::NCOLS, R::NCOLS>;
+
+    fn fmt_tree(self, f: &mut fmt::Formatter) -> fmt::Result {
+        text::fmt_node(f, "HCat", self.l, self.r)
+    }
+}
+
+impl<L, R, T> UnsafeGet for HCat<L, R>
+where
+    L: Matrix<Elem = T>,
+    R: Matrix<Elem = T, NROWS = L::NROWS>,
+    L::NCOLS: ops::Add<R::NCOLS>,
+    Sum<L::NCOLS, R::NCOLS>: Unsigned,
+    T: Clone,
+{
+    type Elem = T;
+
+    unsafe fn unsafe_get(self, r: usize, c: usize) -> T {
+        let lcols = self.l.ncols();
+        if c < lcols {
+            self.l.unsafe_get(r, c)
+        } else {
+            self.r.unsafe_get(r, c - lcols)
+        }
+    }
+}
+
+/// The lazy `[t; b]`, which `block_mat!` evaluates once
+#[doc(hidden)]
+#[derive(Clone, Copy)]
+pub struct VCat<U, B> {
+    t: U,
+    b: B,
+}
+
+#[doc(hidden)]
+pub fn lazy_vcat<U, B>(t: U, b: B) -> VCat<U, B> {
+    VCat { t, b }
+}
+
+impl<U, B, T> Matrix for VCat<U, B>
+where
+    U: Matrix<Elem = T>,
+    B: Matrix<Elem = T, NCOLS = U::NCOLS>,
+    U::NROWS: ops::Add<B::NROWS>,
+    Sum<U::NROWS, B::NROWS>: Unsigned,
+    T: Clone,
+{
+    type NROWS = Sum<U::NROWS, B::NROWS>;
+    type NCOLS = U::NCOLS;
+
+    fn fmt_tree(self, f: &mut fmt::Formatter) -> fmt::Result {
+        text::fmt_node(f, "VCat", self.t, self.b)
+    }
+}
+
+impl<U, B, T> UnsafeGet for VCat<U, B>
+where
+    U: Matrix<Elem = T>,
+    B: Matrix<Elem = T, NCOLS = U::NCOLS>,
+    U::NROWS: ops::Add<B::NROWS>,
+    Sum<U::NROWS, B::NROWS>: Unsigned,
+    T: Clone,
+{
+    type Elem = T;
+
+    unsafe fn unsafe_get(self, r: usize, c: usize) -> T {
+        let trows = self.t.nrows();
+        if r < trows {
+            self.t.unsafe_get(r, c)
+        } else {
+            self.b.unsafe_get(r - trows, c)
+        }
+    }
+}
+
+/// The block diagonal matrix with an `A` block and a `B` block along its diagonal
+pub type BlockDiag<T, A, B> = MatGen<
+    T,
+    Sum<<A as Matrix>::NROWS, <B as Matrix>::NROWS>,
+    Sum<<A as Matrix>::NCOLS, <B as Matrix>::NCOLS>,
+>;
+
+/// Returns the block diagonal matrix with `a` at the top left, `b` at the bottom right and zeros
+/// elsewhere
+///
+/// # Example
+///
+/// ```
+/// extern crate mat;
+/// extern crate typenum;
+///
+/// use mat::{block, block_diag, MatGen};
+/// use typenum::{U1, U2, U4};
+///
+/// # fn main() {
+/// // covariances of a position / velocity state and of a bias
+/// let p = MatGen::<f32, U2, U2>::from_fn(|r, c| [[4., 1.], [1., 2.]][r][c]);
+/// let q = MatGen::<f32, U1, U1>::from_fn(|_, _| 0.5);
+///
+/// let cov = block::block_diag(&p, &q);
+/// assert_eq!(cov, MatGen::from_fn(|r, c| [[4., 1., 0.], [1., 2., 0.], [0., 0., 0.5]][r][c]));
+///
+/// // any number of blocks
+/// let cov = block_diag!(&p, &q, &q);
+/// assert_eq!(cov, MatGen::<f32, U4, U4>::from_fn(|r, c| {
+///     [[4., 1., 0., 0.], [1., 2., 0., 0.], [0., 0., 0.5, 0.], [0., 0., 0., 0.5]][r][c]
+/// }));
+/// # }
+/// ```
+pub fn block_diag<A, B, T>(a: A, b: B) -> BlockDiag<T, A, B>
+where
+    A: Matrix<Elem = T>,
+    B: Matrix<Elem = T>,
+    A::NROWS: ops::Add<B::NROWS>,
+    A::NCOLS: ops::Add<B::NCOLS>,
+    Sum<A::NROWS, B::NROWS>: Unsigned + ops::Mul<Sum<A::NCOLS, B::NCOLS>>,
+    Sum<A::NCOLS, B::NCOLS>: Unsigned,
+    Prod<Sum<A::NROWS, B::NROWS>, Sum<A::NCOLS, B::NCOLS>>: ArrayLength<T>,
+    T: Clone + Zero,
+{
+    let (arows, acols) = a.size();
+    MatGen::from_fn(|i, j| unsafe {
+        match (i < arows, j < acols) {
+            (true, true) => a.unsafe_get(i, j),
+            (false, false) => b.unsafe_get(i - arows, j - acols),
+            _ => T::zero(),
+        }
+    })
+}
+
+/// Assembles a matrix from a grid of blocks
+///
+/// `block_mat![[a, b], [c, d]]` is the matrix with the blocks `a` and `b` side by side on top of
+/// the blocks `c` and `d`. The blocks are `Matrix` expressions, e.g. `&m` where `m` is a `MatGen`.
+/// All the blocks of a row must have the same number of rows, and all the rows must have the same
+/// number of columns; this is checked at compile time. The rows can have a different number of
+/// blocks. The blocks are read in place and the result is evaluated once, without intermediate
+/// matrices.
+///
+/// Blocks that don't line up don't compile
+///
+/// ``` compile_fail
+/// #[macro_use]
+/// extern crate mat;
+/// extern crate typenum;
+///
+/// use mat::MatGen;
+/// use typenum::{U1, U2};
+///
+/// # fn main() {
+/// let a = MatGen::<i32, U2, U2>::zeros();
+/// let b = MatGen::<i32, U1, U1>::zeros();
+///
+/// let m = block_mat![[&a, &b]];
+/// # }
+/// ```
+#[macro_export(local_inner_macros)]
+macro_rules! block_mat {
+    ($([$($block:expr),+ $(,)*]),+ $(,)*) => {
+        $crate::traits::Matrix::eval(__block_mat_rows!($([$($block),+]),+))
+    };
+}
+
+#[doc(hidden)]
+#[macro_export(local_inner_macros)]
+macro_rules! __block_mat_rows {
+    ([$($block:expr),+]) => {
+        __block_mat_row!($($block),+)
+    };
+    ([$($block:expr),+], $([$($rest:expr),+]),+) => {
+        $crate::block::lazy_vcat(
+            __block_mat_row!($($block),+),
+            __block_mat_rows!($([$($rest),+]),+),
+        )
+    };
+}
+
+#[doc(hidden)]
+#[macro_export(local_inner_macros)]
+macro_rules! __block_mat_row {
+    ($block:expr) => {
+        $block
+    };
+    ($block:expr, $($rest:expr),+) => {
+        $crate::block::lazy_hcat($block, __block_mat_row!($($rest),+))
+    };
+}
+
+/// Returns the block diagonal matrix with the given matrices along its diagonal
+///
+/// See `block::block_diag`; the operands are `Matrix` expressions, e.g. `&m` where `m` is a
+/// `MatGen`, and need not be square.
+#[macro_export(local_inner_macros)]
+macro_rules! block_diag {
+    ($block:expr $(,)*) => {
+        $crate::traits::Matrix::eval($block)
+    };
+    ($block:expr, $($rest:expr),+ $(,)*) => {
+        $crate::block::block_diag($block, &block_diag!($($rest),+))
+    };
+}
@@ -0,0 +1,215 @@
+//! Approximate comparison of matrices
+//!
+//! `assert_mat_eq!` asserts that two matrices, or matrix expressions, are equal within an absolute
+//! and / or relative tolerance. On failure it reports the first element, in row major order, that
+//! is out of tolerance.
+//!
+//! # Example
+//!
+//! ```
+//! #[macro_use]
+//! extern crate mat;
+//! extern crate typenum;
+//!
+//! use mat::MatGen;
+//! use mat::traits::Transpose;
+//! use typenum::{U2, U3};
+//!
+//! # fn main() {
+//! let a = MatGen::<f32, U2, U3>::from_fn(|r, c| (r + c) as f32 / 10.);
+//! let expected = MatGen::<f32, U2, U2>::from_fn(|r, c| [[0.05, 0.08], [0.08, 0.14]][r][c]);
+//!
+//! assert_mat_eq!(&a * a.t(), &expected, abs = 1e-6);
+//! assert_mat_eq!(&a * a.t(), &expected, rel = 1e-5);
+//!
+//! // integer matrices can be compared exactly
+//! let i = MatGen::<i32, U2, U2>::identity();
+//! assert_mat_eq!(&i * &i, &i);
+//! # }
+//! ```
+//!
+//! A failed assertion panics with a message like this one:
+//!
+//! ``` text
+//! assertion failed: matrices differ at (1, 0)
+//!    actual: 0.1
+//!  expected: 0.08
+//! ```
+//!
+//! `compare` measures how far apart two matrices are instead, which is useful to log the accuracy
+//! of a computation over time.
+//!
+//! ```
+//! extern crate mat;
+//! extern crate typenum;
+//!
+//! use mat::MatGen;
+//! use mat::compare;
+//! use typenum::U2;
+//!
+//! # fn main() {
+//! let actual = MatGen::<f32, U2, U2>::from_fn(|r, c| [[1., 2.], [3., 4.]][r][c]);
+//! let expected = MatGen::<f32, U2, U2>::from_fn(|r, c| [[1., 2.5], [3., 3.5]][r][c]);
+//!
+//! let report = compare::compare(&actual, &expected, 0.1);
+//! assert_eq!(report.max_abs_error, 0.5);
+//! assert_eq!(report.max_rel_error, 0.2);
+//! assert_eq!(report.worst, (0, 1));
+//! assert_eq!(report.mismatches, 2);
+//! assert!(!report.passed());
+//! # }
+//! ```
+
+use core::cmp::Ordering;
+use core::ops;
+
+use linalg;
+use traits::{Matrix, Zero};
+
+/// The result of comparing two matrices element-wise
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MatCompare<T> {
+    /// Largest absolute error
+    pub max_abs_error: T,
+    /// Largest error relative to the magnitude of the expected element
+    ///
+    /// Elements whose expected value is zero are left out
+    pub max_rel_error: T,
+    /// Row and column of the element with the largest absolute error
+    pub worst: (usize, usize),
+    /// Number of elements whose absolute error exceeds the tolerance
+    pub mismatches: usize,
+}
+
+impl<T> MatCompare<T> {
+    /// Returns `true` if all the elements are within the tolerance
+    pub fn passed(&self) -> bool {
+        self.mismatches == 0
+    }
+}
+
+/// Compares `actual` against `expected` element-wise
+///
+/// Elements whose absolute error exceeds `tol`, or is NaN, count as mismatches. A NaN error is also
+/// the largest error.
+pub fn compare<L, R, T>(actual: L, expected: R, tol: T) -> MatCompare<T>
+where
+    L: Matrix<Elem = T>,
+    R: Matrix<NROWS = L::NROWS, NCOLS = L::NCOLS, Elem = T>,
+    T: ops::Div<T, Output = T> + ops::Sub<T, Output = T> + Clone + PartialOrd + Zero,
+{
+    let mut report = MatCompare {
+        max_abs_error: T::zero(),
+        max_rel_error: T::zero(),
+        worst: (0, 0),
+        mismatches: 0,
+    };
+
+    let (nrows, ncols) = actual.size();
+    for r in 0..nrows {
+        for c in 0..ncols {
+            let (a, e) = unsafe { (actual.unsafe_get(r, c), expected.unsafe_get(r, c)) };
+            let abs_error = linalg::abs(a - e.clone());
+
+            if exceeds(&abs_error, &tol) {
+                report.mismatches += 1;
+            }
+
+            if e != T::zero() {
+                let rel_error = abs_error.clone() / linalg::abs(e);
+                if exceeds(&rel_error, &report.max_rel_error) {
+                    report.max_rel_error = rel_error;
+                }
+            }
+
+            if exceeds(&abs_error, &report.max_abs_error) {
+                report.max_abs_error = abs_error;
+                report.worst = (r, c);
+            }
+        }
+    }
+
+    report
+}
+
+/// Asserts that two matrices are equal within a tolerance
+///
+/// `$actual` and `$expected` must implement `Matrix` so pass references to `Mat` and `MatGen`, or
+/// expression trees. The optional `abs` and `rel` arguments are the absolute tolerance and the
+/// tolerance relative to the magnitude of the expected element; an element is within tolerance if
+/// it satisfies either of them. Both tolerances default to zero.
+///
+/// # Panics
+///
+/// This macro panics if an element is out of tolerance. The panic message contains the row and
+/// column of the first such element, and its actual and expected values.
+#[macro_export(local_inner_macros)]
+macro_rules! assert_mat_eq {
+    ($actual:expr, $expected:expr) => {
+        assert_mat_eq!(
+            $actual,
+            $expected,
+            abs = $crate::traits::Zero::zero(),
+            rel = $crate::traits::Zero::zero()
+        )
+    };
+    ($actual:expr, $expected:expr, abs = $abs:expr) => {
+        assert_mat_eq!($actual, $expected, abs = $abs, rel = $crate::traits::Zero::zero())
+    };
+    ($actual:expr, $expected:expr, rel = $rel:expr) => {
+        assert_mat_eq!($actual, $expected, abs = $crate::traits::Zero::zero(), rel = $rel)
+    };
+    ($actual:expr, $expected:expr, abs = $abs:expr, rel = $rel:expr) => {{
+        let (actual, expected) = ($actual, $expected);
+        if let Some((r, c)) = $crate::compare::first_mismatch(actual, expected, $abs, $rel) {
+            __assert_mat_eq_panic!(
+                "assertion failed: matrices differ at ({}, {})\n   actual: {:?}\n expected: {:?}",
+                r,
+                c,
+                $crate::traits::Matrix::get(actual, r, c),
+                $crate::traits::Matrix::get(expected, r, c),
+            )
+        }
+    }};
+}
+
+// NOTE `local_inner_macros` resolves `panic!` to `$crate::panic!`
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __assert_mat_eq_panic {
+    ($($arg:tt)*) => {
+        panic!($($arg)*)
+    };
+}
+
+#[doc(hidden)]
+pub fn first_mismatch<L, R, T>(actual: L, expected: R, abs: T, rel: T) -> Option<(usize, usize)>
+where
+    L: Matrix<Elem = T>,
+    R: Matrix<NROWS = L::NROWS, NCOLS = L::NCOLS, Elem = T>,
+    T: ops::Mul<T, Output = T> + ops::Sub<T, Output = T> + Clone + PartialOrd + Zero,
+{
+    let (nrows, ncols) = actual.size();
+    for r in 0..nrows {
+        for c in 0..ncols {
+            let (a, e) = unsafe { (actual.unsafe_get(r, c), expected.unsafe_get(r, c)) };
+            let diff = linalg::abs(a - e.clone());
+            if exceeds(&diff, &abs) && exceeds(&diff, &(rel.clone() * linalg::abs(e))) {
+                return Some((r, c));
+            }
+        }
+    }
+
+    None
+}
+
+/// Returns `true` if `x` is greater than `bound` or if they can't be compared (e.g. NaN)
+fn exceeds<T>(x: &T, bound: &T) -> bool
+where
+    T: PartialOrd,
+{
+    match x.partial_cmp(bound) {
+        Some(Ordering::Less) | Some(Ordering::Equal) => false,
+        Some(Ordering::Greater) | None => true,
+    }
+}
@@ -0,0 +1,161 @@
+//! Complex numbers
+//!
+//! # Example
+//!
+//! The product of a matrix and its adjoint is Hermitian
+//!
+//! ```
+//! extern crate mat;
+//! extern crate typenum;
+//!
+//! use mat::MatGen;
+//! use mat::complex::Complex;
+//! use mat::traits::Matrix;
+//! use typenum::U2;
+//!
+//! # fn main() {
+//! let z = [
+//!     [Complex::new(1, 2), Complex::new(0, -1)],
+//!     [Complex::new(3, 0), Complex::new(2, 1)],
+//! ];
+//! let a = MatGen::<_, U2, U2>::from_fn(|r, c| z[r][c]);
+//!
+//! assert_eq!((&a).adjoint().get(0, 1), Complex::new(3, 0));
+//! assert_eq!((&a).adjoint().get(1, 0), Complex::new(0, 1));
+//!
+//! let h = ((&a).adjoint() * &a).eval();
+//! assert_eq!(h.get(0, 1), h.get(1, 0).conj());
+//! assert_eq!(h.get(0, 0).im, 0);
+//! # }
+//! ```
+
+use core::ops;
+
+use traits::{Conj, One, Zero};
+
+/// A complex number, `re + im i`
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Complex<T> {
+    /// Real part
+    pub re: T,
+    /// Imaginary part
+    pub im: T,
+}
+
+impl<T> Complex<T> {
+    /// Creates the complex number `re + im i`
+    pub fn new(re: T, im: T) -> Self {
+        Complex { re, im }
+    }
+}
+
+impl<T> Complex<T>
+where
+    T: ops::Add<T, Output = T> + ops::Mul<T, Output = T> + Copy,
+{
+    /// Returns the squared magnitude, `re² + im²`
+    pub fn norm_squared(self) -> T {
+        self.re * self.re + self.im * self.im
+    }
+}
+
+impl<T> Complex<T>
+where
+    T: ops::Sub<T, Output = T> + Copy + Zero,
+{
+    /// Returns the complex conjugate, `re - im i`
+    pub fn conj(self) -> Self {
+        Complex::new(self.re, T::zero() - self.im)
+    }
+}
+
+impl<T> Conj for Complex<T>
+where
+    T: ops::Sub<T, Output = T> + Copy + Zero,
+{
+    fn conj(self) -> Self {
+        Complex::conj(self)
+    }
+}
+
+impl<T> Zero for Complex<T>
+where
+    T: Zero,
+{
+    fn zero() -> Self {
+        Complex::new(T::zero(), T::zero())
+    }
+}
+
+impl<T> One for Complex<T>
+where
+    T: One + Zero,
+{
+    fn one() -> Self {
+        Complex::new(T::one(), T::zero())
+    }
+}
+
+impl<T> From<u8> for Complex<T>
+where
+    T: From<u8> + Zero,
+{
+    fn from(x: u8) -> Self {
+        Complex::new(T::from(x), T::zero())
+    }
+}
+
+impl<T> ops::Add for Complex<T>
+where
+    T: ops::Add<T, Output = T>,
+{
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Complex::new(self.re + rhs.re, self.im + rhs.im)
+    }
+}
+
+impl<T> ops::Sub for Complex<T>
+where
+    T: ops::Sub<T, Output = T>,
+{
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Complex::new(self.re - rhs.re, self.im - rhs.im)
+    }
+}
+
+impl<T> ops::Mul for Complex<T>
+where
+    T: ops::Add<T, Output = T> + ops::Mul<T, Output = T> + ops::Sub<T, Output = T> + Copy,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Complex::new(
+            self.re * rhs.re - self.im * rhs.im,
+            self.re * rhs.im + self.im * rhs.re,
+        )
+    }
+}
+
+impl<T> ops::Div for Complex<T>
+where
+    T: ops::Add<T, Output = T>
+        + ops::Div<T, Output = T>
+        + ops::Mul<T, Output = T>
+        + ops::Sub<T, Output = T>
+        + Copy,
+{
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self {
+        let d = rhs.norm_squared();
+        Complex::new(
+            (self.re * rhs.re + self.im * rhs.im) / d,
+            (self.im * rhs.re - self.re * rhs.im) / d,
+        )
+    }
+}
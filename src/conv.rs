@@ -0,0 +1,258 @@
+//! 2D convolution
+//!
+//! `conv2d` slides a kernel over an image and returns the sum of the element-wise products at every
+//! position. Like in most image processing and neural network libraries the kernel is not flipped,
+//! i.e. this is actually a cross-correlation; the two are the same for the symmetric kernels of
+//! most smoothing filters.
+//!
+//! The dimensions of the result are computed at compile time from the dimensions of the operands
+//! and the padding `Mode`: `Valid` only keeps the positions where the kernel fits in the image and
+//! `Same` pads the image with zeros so the result has the dimensions of the image.
+//!
+//! `MatGen::im2col` rearranges the patches of an image into the columns of a matrix, which turns a
+//! `Valid` convolution into a matrix multiplication that can be computed by a `gemm` backend.
+//!
+//! # Example
+//!
+//! ```
+//! extern crate mat;
+//! extern crate typenum;
+//!
+//! use mat::conv::{self, Same, Valid};
+//! use mat::MatGen;
+//! use mat::traits::Matrix;
+//! use typenum::{U3, U4};
+//!
+//! # fn main() {
+//! let image = MatGen::<f32, U4, U4>::from_fn(|r, c| (r * 4 + c) as f32);
+//! // box blur
+//! let kernel = MatGen::<f32, U3, U3>::from_fn(|_, _| 1. / 9.);
+//!
+//! // 2x2 result
+//! let valid = conv::conv2d(&image, &kernel, Valid);
+//! assert!((valid.get(0, 0) - 5.).abs() < 1e-6);
+//!
+//! // 4x4 result, its interior agrees with `valid`
+//! let same = conv::conv2d(&image, &kernel, Same);
+//! assert_eq!(same.get(1, 1), valid.get(0, 0));
+//! assert!((same.get(0, 0) - (0. + 1. + 4. + 5.) / 9.).abs() < 1e-6);
+//! # }
+//! ```
+
+use core::ops;
+
+use generic_array::ArrayLength;
+use typenum::{Add1, B1, Diff, Prod, Unsigned};
+
+use traits::{Matrix, Zero};
+use MatGen;
+
+/// How the image is padded, which determines the dimensions of the result of a convolution
+///
+/// `Len` is the length of the result along an axis where the image has length `N` and the kernel
+/// has length `K`.
+pub trait Mode<N, K> {
+    /// Length of the result
+    type Len: Unsigned;
+
+    /// Number of zeros that pad the start of the axis, given the length of the kernel
+    #[doc(hidden)]
+    fn padding(k: usize) -> usize;
+}
+
+/// No padding: the result has length `N - K + 1`
+///
+/// Kernels larger than the image are rejected at compile time.
+#[derive(Clone, Copy, Debug)]
+pub struct Valid;
+
+/// Zero padding: the result has the length of the image, `N`
+///
+/// The kernel is centered on each element of the image; even length kernels have one more element
+/// after the center than before it.
+#[derive(Clone, Copy, Debug)]
+pub struct Same;
+
+impl<N, K> Mode<N, K> for Valid
+where
+    N: ops::Sub<K>,
+    Diff<N, K>: ops::Add<B1>,
+    Add1<Diff<N, K>>: Unsigned,
+{
+    type Len = Add1<Diff<N, K>>;
+
+    fn padding(_: usize) -> usize {
+        0
+    }
+}
+
+impl<N, K> Mode<N, K> for Same
+where
+    N: Unsigned,
+{
+    type Len = N;
+
+    fn padding(k: usize) -> usize {
+        k.saturating_sub(1) / 2
+    }
+}
+
+/// The result of convolving an `I` image with a `K` kernel in `MODE`
+pub type Conv<T, I, K, MODE> = MatGen<
+    T,
+    <MODE as Mode<<I as Matrix>::NROWS, <K as Matrix>::NROWS>>::Len,
+    <MODE as Mode<<I as Matrix>::NCOLS, <K as Matrix>::NCOLS>>::Len,
+>;
+
+/// Length of a `Valid` convolution along an axis, `N - K + 1`
+pub type ValidLen<N, K> = <Valid as Mode<N, K>>::Len;
+
+/// The patch matrix of the `KH` by `KW` patches of an `NROWS` by `NCOLS` image
+pub type Patches<T, NROWS, NCOLS, KH, KW> =
+    MatGen<T, Prod<KH, KW>, Prod<ValidLen<NROWS, KH>, ValidLen<NCOLS, KW>>>;
+
+/// Convolves `image` with `kernel`
+pub fn conv2d<I, K, MODE, T>(image: I, kernel: K, mode: MODE) -> Conv<T, I, K, MODE>
+where
+    I: Matrix<Elem = T>,
+    K: Matrix<Elem = T>,
+    MODE: Mode<I::NROWS, K::NROWS> + Mode<I::NCOLS, K::NCOLS>,
+    <MODE as Mode<I::NROWS, K::NROWS>>::Len: ops::Mul<<MODE as Mode<I::NCOLS, K::NCOLS>>::Len>,
+    Prod<<MODE as Mode<I::NROWS, K::NROWS>>::Len, <MODE as Mode<I::NCOLS, K::NCOLS>>::Len>:
+        ArrayLength<T>,
+    T: ops::Add<T, Output = T> + ops::Mul<T, Output = T> + Clone + Zero,
+{
+    conv2d_widened(image, kernel, mode)
+}
+
+/// Convolves `image` with `kernel`, widening the elements to `A` before multiplying them
+///
+/// This is meant for integer images and kernels, e.g. `u8` pixels and `i8` weights accumulated in
+/// `i32`, whose products and sums would overflow the element type.
+///
+/// # Example
+///
+/// ```
+/// extern crate mat;
+/// extern crate typenum;
+///
+/// use mat::conv::{self, Same};
+/// use mat::MatGen;
+/// use mat::traits::Matrix;
+/// use typenum::{U3, U8};
+///
+/// # fn main() {
+/// let image = MatGen::<u8, U8, U8>::from_fn(|r, c| if c < 4 { 0 } else { 255 - r as u8 });
+/// // horizontal Sobel operator
+/// let sobel = MatGen::<i8, U3, U3>::from_fn(|r, c| [[-1, 0, 1], [-2, 0, 2], [-1, 0, 1]][r][c]);
+///
+/// let edges = conv::conv2d_widened::<i32, _, _, _>(&image, &sobel, Same);
+/// assert_eq!(edges.get(3, 3), 4 * 255 - 2 - 3 * 2 - 4);
+/// assert_eq!(edges.get(3, 1), 0);
+/// # }
+/// ```
+pub fn conv2d_widened<A, I, K, MODE>(image: I, kernel: K, _mode: MODE) -> Conv<A, I, K, MODE>
+where
+    I: Matrix,
+    K: Matrix,
+    MODE: Mode<I::NROWS, K::NROWS> + Mode<I::NCOLS, K::NCOLS>,
+    <MODE as Mode<I::NROWS, K::NROWS>>::Len: ops::Mul<<MODE as Mode<I::NCOLS, K::NCOLS>>::Len>,
+    Prod<<MODE as Mode<I::NROWS, K::NROWS>>::Len, <MODE as Mode<I::NCOLS, K::NCOLS>>::Len>:
+        ArrayLength<A>,
+    A: ops::Add<A, Output = A>
+        + ops::Mul<A, Output = A>
+        + Clone
+        + From<I::Elem>
+        + From<K::Elem>
+        + Zero,
+{
+    let (nrows, ncols) = image.size();
+    let (krows, kcols) = kernel.size();
+    let top = <MODE as Mode<I::NROWS, K::NROWS>>::padding(krows);
+    let left = <MODE as Mode<I::NCOLS, K::NCOLS>>::padding(kcols);
+
+    MatGen::from_fn(|r, c| {
+        let mut sum = A::zero();
+        for i in 0..krows {
+            // row of the image under the kernel row `i`; the padding rows contribute nothing
+            let ir = match (r + i).checked_sub(top) {
+                Some(ir) if ir < nrows => ir,
+                _ => continue,
+            };
+
+            for j in 0..kcols {
+                let ic = match (c + j).checked_sub(left) {
+                    Some(ic) if ic < ncols => ic,
+                    _ => continue,
+                };
+
+                unsafe {
+                    sum = sum
+                        + A::from(image.unsafe_get(ir, ic)) * A::from(kernel.unsafe_get(i, j));
+                }
+            }
+        }
+        sum
+    })
+}
+
+impl<T, NROWS, NCOLS> MatGen<T, NROWS, NCOLS>
+where
+    NROWS: Unsigned + ops::Mul<NCOLS>,
+    NCOLS: Unsigned,
+    Prod<NROWS, NCOLS>: ArrayLength<T>,
+    T: Clone,
+{
+    /// Returns the patch matrix of the `KH` by `KW` patches of this image
+    ///
+    /// Each column holds a patch in row major order and the columns are ordered like the elements
+    /// of a `Valid` convolution, also in row major order. So multiplying a `KH` by `KW` kernel,
+    /// flattened into a row vector, by the patch matrix yields the `Valid` convolution of the
+    /// image with the kernel, flattened into a row vector.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate mat;
+    /// extern crate typenum;
+    ///
+    /// use mat::conv::{self, Valid};
+    /// use mat::MatGen;
+    /// use mat::traits::Matrix;
+    /// use typenum::{U1, U2, U3, U4, U5, U6, U8};
+    ///
+    /// # fn main() {
+    /// let image = MatGen::<f32, U4, U5>::from_fn(|r, c| (r * 5 + c) as f32);
+    /// let kernel = MatGen::<f32, U3, U2>::from_fn(|r, c| (r + 2 * c) as f32);
+    ///
+    /// let patches: MatGen<f32, U6, U8> = image.im2col::<U3, U2>();
+    /// let row = MatGen::<f32, U1, U6>::from_fn(|_, i| kernel.get(i / 2, i % 2));
+    /// let flat = row.matmul(&patches);
+    ///
+    /// let out = conv::conv2d(&image, &kernel, Valid);
+    /// for i in 0..8 {
+    ///     assert_eq!(flat.get(i), out.get(i / 4, i % 4));
+    /// }
+    /// # }
+    /// ```
+    pub fn im2col<KH, KW>(&self) -> Patches<T, NROWS, NCOLS, KH, KW>
+    where
+        KH: Unsigned + ops::Mul<KW>,
+        KW: Unsigned,
+        Valid: Mode<NROWS, KH> + Mode<NCOLS, KW>,
+        ValidLen<NROWS, KH>: ops::Mul<ValidLen<NCOLS, KW>>,
+        Prod<KH, KW>: Unsigned + ops::Mul<Prod<ValidLen<NROWS, KH>, ValidLen<NCOLS, KW>>>,
+        Prod<ValidLen<NROWS, KH>, ValidLen<NCOLS, KW>>: Unsigned,
+        Prod<Prod<KH, KW>, Prod<ValidLen<NROWS, KH>, ValidLen<NCOLS, KW>>>: ArrayLength<T>,
+    {
+        let (ncols, kw) = (NCOLS::to_usize(), KW::to_usize());
+        let out_ncols = ValidLen::<NCOLS, KW>::to_usize();
+        let buffer = self.as_slice();
+
+        MatGen::from_fn(|patch, pos| {
+            let (i, j) = (patch / kw, patch % kw);
+            let (r, c) = (pos / out_ncols, pos % out_ncols);
+            buffer[(r + i) * ncols + c + j].clone()
+        })
+    }
+}
@@ -0,0 +1,249 @@
+use core::{fmt, ops};
+
+use generic_array::{ArrayLength, GenericArray};
+use typenum::{Prod, Unsigned};
+
+use traits::{Matrix, UnsafeGet, Zero};
+use {array, MatGen, Product, Sum};
+
+/// Diagonal matrix that only stores its diagonal
+///
+/// Multiplying a diagonal matrix by a matrix `m` scales the rows of `m` so the lazy product `&d *
+/// m` costs one multiplication per element, instead of `N`.
+///
+/// # Example
+///
+/// ```
+/// extern crate mat;
+/// extern crate typenum;
+///
+/// use mat::{DiagMat, MatGen};
+/// use mat::traits::{Matrix, Transpose};
+/// use typenum::{U1, U3};
+///
+/// # fn main() {
+/// // weighting matrix
+/// let q = DiagMat::<f32, U3>::from_fn(|i| (i + 1) as f32);
+/// assert_eq!((&q).get(1, 1), 2.);
+/// assert_eq!((&q).get(1, 2), 0.);
+///
+/// let x = MatGen::<f32, U3, U1>::from_fn(|r, _| r as f32);
+///
+/// // quadratic cost `xᵀ Q x`
+/// let cost = (x.t() * (&q * &x)).get(0, 0);
+/// assert_eq!(cost, 1. * 2. + 2. * 3. * 2.);
+///
+/// // `Q x` without building the lazy product
+/// assert_eq!(q.scale_rows(&x), &q * &x);
+/// # }
+/// ```
+pub struct DiagMat<T, N>
+where
+    N: ArrayLength<T>,
+    T: Clone,
+{
+    diag: GenericArray<T, N>,
+}
+
+impl<T, N> Clone for DiagMat<T, N>
+where
+    N: ArrayLength<T>,
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        let diag = self.diag();
+        DiagMat::from_fn(|i| diag[i].clone())
+    }
+}
+
+/// The product of a diagonal matrix and a matrix
+#[derive(Clone, Copy)]
+pub struct DiagProduct<D, M> {
+    d: D,
+    m: M,
+}
+
+impl<T, N> DiagMat<T, N>
+where
+    N: ArrayLength<T>,
+    T: Clone,
+{
+    /// Creates a diagonal matrix from its diagonal
+    pub fn new(diag: GenericArray<T, N>) -> Self {
+        DiagMat { diag }
+    }
+
+    /// Creates a diagonal matrix whose `i`-th diagonal element is `f(i)`
+    pub fn from_fn<F>(f: F) -> Self
+    where
+        F: Fn(usize) -> T,
+    {
+        DiagMat::new(array::generate(f))
+    }
+
+    /// Returns the diagonal
+    pub fn diag(&self) -> &[T] {
+        array::as_slice(&self.diag)
+    }
+
+    /// Returns a mutable reference to the diagonal
+    pub fn diag_mut(&mut self) -> &mut [T] {
+        array::as_mut_slice(&mut self.diag)
+    }
+
+    /// Sets the element at row `r` and column `c` to `value`
+    ///
+    /// Returns `value` back if `r` or `c` exceed the matrix dimensions, or if the element is
+    /// outside the diagonal.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate mat;
+    /// extern crate typenum;
+    ///
+    /// use mat::DiagMat;
+    /// use typenum::U2;
+    ///
+    /// # fn main() {
+    /// let mut d = DiagMat::<i32, U2>::from_fn(|_| 1);
+    ///
+    /// assert_eq!(d.set_checked(1, 1, 3), Ok(()));
+    /// assert_eq!(d.set_checked(0, 1, 4), Err(4));
+    /// assert_eq!(d.set_checked(2, 2, 5), Err(5));
+    /// assert_eq!(d.diag(), &[1, 3]);
+    /// # }
+    /// ```
+    pub fn set_checked(&mut self, r: usize, c: usize, value: T) -> Result<(), T> {
+        no_panic!(if r == c {
+            match array::as_mut_slice(&mut self.diag).get_mut(r) {
+                Some(elem) => {
+                    *elem = value;
+                    Ok(())
+                }
+                None => Err(value),
+            }
+        } else {
+            Err(value)
+        })
+    }
+
+    /// Scales the rows of `m`, i.e. returns `D m`
+    pub fn scale_rows<M, C>(&self, m: M) -> MatGen<T, N, C>
+    where
+        M: Matrix<Elem = T, NROWS = N, NCOLS = C>,
+        N: Unsigned + ops::Mul<C>,
+        C: Unsigned,
+        Prod<N, C>: ArrayLength<T>,
+        T: ops::Mul<T, Output = T>,
+    {
+        MatGen::from_fn(|r, c| self.diag()[r].clone() * unsafe { m.unsafe_get(r, c) })
+    }
+
+    /// Scales the columns of `m`, i.e. returns `m D`
+    pub fn scale_cols<M, R>(&self, m: M) -> MatGen<T, R, N>
+    where
+        M: Matrix<Elem = T, NROWS = R, NCOLS = N>,
+        R: Unsigned + ops::Mul<N>,
+        N: Unsigned,
+        Prod<R, N>: ArrayLength<T>,
+        T: ops::Mul<T, Output = T>,
+    {
+        MatGen::from_fn(|r, c| unsafe { m.unsafe_get(r, c) } * self.diag()[c].clone())
+    }
+}
+
+impl<T, N> Matrix for &DiagMat<T, N>
+where
+    N: ArrayLength<T> + Unsigned,
+    T: Clone + Zero,
+{
+    type NROWS = N;
+    type NCOLS = N;
+}
+
+impl<T, N> UnsafeGet for &DiagMat<T, N>
+where
+    N: ArrayLength<T> + Unsigned,
+    T: Clone + Zero,
+{
+    type Elem = T;
+
+    unsafe fn unsafe_get(self, r: usize, c: usize) -> T {
+        if r == c {
+            self.diag().get_unchecked(r).clone()
+        } else {
+            T::zero()
+        }
+    }
+}
+
+impl<'a, T, N, R> ops::Mul<R> for &'a DiagMat<T, N>
+where
+    N: ArrayLength<T> + Unsigned,
+    T: Clone + Zero,
+    R: Matrix<NROWS = N>,
+{
+    type Output = DiagProduct<&'a DiagMat<T, N>, R>;
+
+    fn mul(self, rhs: R) -> Self::Output {
+        DiagProduct { d: self, m: rhs }
+    }
+}
+
+impl<T, N, M> Matrix for DiagProduct<&DiagMat<T, N>, M>
+where
+    N: ArrayLength<T> + Unsigned,
+    M: Matrix<Elem = T, NROWS = N>,
+    T: ops::Mul<T, Output = T> + Clone,
+{
+    type NROWS = N;
+    type NCOLS = M::NCOLS;
+
+    fn fmt_tree(self, f: &mut fmt::Formatter) -> fmt::Result {
+        // NOTE `&DiagMat` is only a `Matrix` if `T: Zero`
+        write!(f, "Mul({0}x{0}, ", N::to_usize())?;
+        self.m.fmt_tree(f)?;
+        f.write_str(")")
+    }
+}
+
+impl<T, N, M> UnsafeGet for DiagProduct<&DiagMat<T, N>, M>
+where
+    N: ArrayLength<T> + Unsigned,
+    M: Matrix<Elem = T, NROWS = N>,
+    T: ops::Mul<T, Output = T> + Clone,
+{
+    type Elem = T;
+
+    unsafe fn unsafe_get(self, r: usize, c: usize) -> T {
+        self.d.diag.get_unchecked(r).clone() * self.m.unsafe_get(r, c)
+    }
+}
+
+impl<D, M, RHS> ops::Mul<RHS> for DiagProduct<D, M>
+where
+    DiagProduct<D, M>: Matrix,
+    RHS: Matrix<NROWS = <DiagProduct<D, M> as Matrix>::NCOLS>,
+{
+    type Output = Product<DiagProduct<D, M>, RHS>;
+
+    fn mul(self, rhs: RHS) -> Self::Output {
+        Product { l: self, r: rhs }
+    }
+}
+
+impl<D, M, RHS> ops::Add<RHS> for DiagProduct<D, M>
+where
+    DiagProduct<D, M>: Matrix,
+    RHS: Matrix<
+        NROWS = <DiagProduct<D, M> as Matrix>::NROWS,
+        NCOLS = <DiagProduct<D, M> as Matrix>::NCOLS,
+    >,
+{
+    type Output = Sum<DiagProduct<D, M>, RHS>;
+
+    fn add(self, rhs: RHS) -> Self::Output {
+        Sum { l: self, r: rhs }
+    }
+}
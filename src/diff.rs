@@ -0,0 +1,212 @@
+//! Numerical differentiation
+//!
+//! # Examples
+//!
+//! Check a hand-derived Jacobian
+//!
+//! ```
+//! extern crate mat;
+//! extern crate typenum;
+//!
+//! use mat::{diff, mat, Vector};
+//! use mat::traits::Matrix;
+//! use typenum::U2;
+//!
+//! # fn main() {
+//! let f = |x: &Vector<f64, U2>| {
+//!     let (x0, x1) = (x[0], x[1]);
+//!     Vector::<_, U2>::from_fn(|i, _| if i == 0 { x0 * x1 } else { x0.sin() })
+//! };
+//!
+//! let j = diff::numerical_jacobian(f, &mat![[1.], [2.]], 1e-6);
+//!
+//! let expected = mat![
+//!     [2., 1.],
+//!     [1_f64.cos(), 0.],
+//! ];
+//!
+//! for r in 0..2 {
+//!     for c in 0..2 {
+//!         assert!((j.get(r, c) - expected.get(r, c)).abs() < 1e-6);
+//!     }
+//! }
+//! # }
+//! ```
+//!
+//! The same Jacobian computed exactly with dual numbers
+//!
+//! ```
+//! extern crate mat;
+//! extern crate typenum;
+//!
+//! use mat::{diff, mat, Vector};
+//! use mat::dual::Dual;
+//! use mat::traits::{Matrix, Trig};
+//! use typenum::U2;
+//!
+//! # fn main() {
+//! let f = |x: &Vector<Dual<f64>, U2>| {
+//!     let (x0, x1) = (x[0], x[1]);
+//!     Vector::<_, U2>::from_fn(|i, _| if i == 0 { x0 * x1 } else { x0.sin() })
+//! };
+//!
+//! let j = diff::jacobian(f, &mat![[1.], [2.]]);
+//!
+//! assert_eq!(j.get(0, 0), 2.);
+//! assert_eq!(j.get(0, 1), 1.);
+//! assert!((j.get(1, 0) - 1_f64.cos()).abs() < 1e-12);
+//! assert_eq!(j.get(1, 1), 0.);
+//! # }
+//! ```
+//!
+//! Hessian of `f(x) = x0² x1`
+//!
+//! ```
+//! extern crate mat;
+//! extern crate typenum;
+//!
+//! use mat::{diff, mat, Vector};
+//! use mat::traits::Matrix;
+//! use typenum::U2;
+//!
+//! # fn main() {
+//! let f = |x: &Vector<f64, U2>| x[0] * x[0] * x[1];
+//!
+//! let h = diff::numerical_hessian(f, &mat![[1.], [3.]], 1e-4);
+//!
+//! assert!((h.get(0, 0) - 6.).abs() < 1e-6);
+//! assert!((h.get(0, 1) - 2.).abs() < 1e-6);
+//! assert_eq!(h.get(0, 1), h.get(1, 0));
+//! assert!(h.get(1, 1).abs() < 1e-6);
+//! # }
+//! ```
+
+use core::ops;
+
+use generic_array::ArrayLength;
+use typenum::{Prod, U1, Unsigned};
+
+use {MatGen, Vector};
+use dual::Dual;
+use traits::{Matrix, Zero};
+
+/// Computes the `M` by `N` Jacobian of `f` at `x` using central differences
+///
+/// `f` maps `N` inputs to `M` outputs and `eps` is the perturbation applied to each input.
+pub fn numerical_jacobian<F, X, T, M, N>(mut f: F, x: X, eps: T) -> MatGen<T, M, N>
+where
+    F: FnMut(&Vector<T, N>) -> Vector<T, M>,
+    X: Matrix<Elem = T, NROWS = N, NCOLS = U1>,
+    M: Unsigned + ops::Mul<N> + ops::Mul<U1>,
+    N: Unsigned + ops::Mul<U1>,
+    Prod<M, N>: ArrayLength<T>,
+    Prod<M, U1>: ArrayLength<T>,
+    Prod<N, U1>: ArrayLength<T>,
+    T: ops::Add<T, Output = T>
+        + ops::Div<T, Output = T>
+        + ops::Sub<T, Output = T>
+        + Copy
+        + Zero,
+{
+    let mut j = MatGen::zeros();
+    let mut xp = x.eval();
+    let mut xm = x.eval();
+
+    for c in 0..N::to_usize() {
+        let xc = x.get(c, 0);
+        xp.set(c, 0, xc + eps);
+        xm.set(c, 0, xc - eps);
+
+        let fp = f(&xp);
+        let fm = f(&xm);
+        for r in 0..M::to_usize() {
+            j.set(r, c, (fp.get(r, 0) - fm.get(r, 0)) / (eps + eps));
+        }
+
+        xp.set(c, 0, xc);
+        xm.set(c, 0, xc);
+    }
+
+    j
+}
+
+/// Computes the `M` by `N` Jacobian of `f` at `x` using forward-mode automatic differentiation
+///
+/// `f` maps `N` inputs to `M` outputs and must be written in terms of `Dual` numbers. It's
+/// evaluated once per input, each time with a different input as the variable of differentiation.
+pub fn jacobian<F, X, T, M, N>(mut f: F, x: X) -> MatGen<T, M, N>
+where
+    F: FnMut(&Vector<Dual<T>, N>) -> Vector<Dual<T>, M>,
+    X: Matrix<Elem = T, NROWS = N, NCOLS = U1>,
+    M: Unsigned + ops::Mul<N> + ops::Mul<U1>,
+    N: Unsigned + ops::Mul<U1>,
+    Prod<M, N>: ArrayLength<T>,
+    Prod<M, U1>: ArrayLength<Dual<T>>,
+    Prod<N, U1>: ArrayLength<Dual<T>>,
+    T: Copy + From<u8> + Zero,
+{
+    let mut j = MatGen::zeros();
+    let mut xd = Vector::from_fn(|r, _| Dual::constant(x.get(r, 0)));
+
+    for c in 0..N::to_usize() {
+        xd.set(c, 0, Dual::variable(x.get(c, 0)));
+
+        let y = f(&xd);
+        for r in 0..M::to_usize() {
+            j.set(r, c, y.get(r, 0).deriv);
+        }
+
+        xd.set(c, 0, Dual::constant(x.get(c, 0)));
+    }
+
+    j
+}
+
+/// Computes the `N` by `N` Hessian of the scalar function `f` at `x` using central differences
+///
+/// `eps` is the perturbation applied to each input. Only the upper triangle is estimated; the lower
+/// triangle mirrors it so the result is exactly symmetric.
+pub fn numerical_hessian<F, X, T, N>(mut f: F, x: X, eps: T) -> MatGen<T, N, N>
+where
+    F: FnMut(&Vector<T, N>) -> T,
+    X: Matrix<Elem = T, NROWS = N, NCOLS = U1>,
+    N: Unsigned + ops::Mul<N> + ops::Mul<U1>,
+    Prod<N, N>: ArrayLength<T>,
+    Prod<N, U1>: ArrayLength<T>,
+    T: ops::Add<T, Output = T>
+        + ops::Div<T, Output = T>
+        + ops::Mul<T, Output = T>
+        + ops::Sub<T, Output = T>
+        + Copy
+        + Zero,
+{
+    let n = N::to_usize();
+    let mut h = MatGen::zeros();
+    let mut xe = x.eval();
+    let denom = (eps + eps) * (eps + eps);
+
+    // evaluates `f` at `x + di * e_i + dj * e_j`
+    let mut eval = |i: usize, di: T, j: usize, dj: T| {
+        xe.set(i, 0, xe.get(i, 0) + di);
+        xe.set(j, 0, xe.get(j, 0) + dj);
+        let y = f(&xe);
+        xe.set(i, 0, x.get(i, 0));
+        xe.set(j, 0, x.get(j, 0));
+        y
+    };
+
+    for i in 0..n {
+        for j in i..n {
+            let pp = eval(i, eps, j, eps);
+            let pm = eval(i, eps, j, T::zero() - eps);
+            let mp = eval(i, T::zero() - eps, j, eps);
+            let mm = eval(i, T::zero() - eps, j, T::zero() - eps);
+
+            let hij = (pp - pm - mp + mm) / denom;
+            h.set(i, j, hij);
+            h.set(j, i, hij);
+        }
+    }
+
+    h
+}
@@ -0,0 +1,115 @@
+//! Type level dimensions
+//!
+//! This module re-exports the `typenum` integers from `U0` to `U64` so matrix types can be spelled
+//! without depending on `typenum`. `def_mat_alias!` goes one step further and defines a `MatGen`
+//! alias from integer literals.
+//!
+//! # Example
+//!
+//! ```
+//! #[macro_use]
+//! extern crate mat;
+//!
+//! use mat::MatGen;
+//! use mat::dims::{U1, U9};
+//!
+//! def_mat_alias!(StateCov = f32, 9, 9);
+//! def_mat_alias!(pub State = f32, 9, 1);
+//!
+//! # fn main() {
+//! let p = StateCov::identity();
+//! let x: MatGen<f32, U9, U1> = State::zeros();
+//! assert_eq!((&p * &x).eval(), x);
+//! # }
+//! ```
+
+pub use typenum::{
+    U0, U1, U2, U3, U4, U5, U6, U7, U8, U9, U10, U11, U12, U13, U14, U15, U16, U17, U18, U19,
+    U20, U21, U22, U23, U24, U25, U26, U27, U28, U29, U30, U31, U32, U33, U34, U35, U36, U37,
+    U38, U39, U40, U41, U42, U43, U44, U45, U46, U47, U48, U49, U50, U51, U52, U53, U54, U55,
+    U56, U57, U58, U59, U60, U61, U62, U63, U64,
+};
+
+/// Defines an alias of a `MatGen` with dimensions given as integer literals from 0 to 64
+///
+/// For example `def_mat_alias!(Cov = f32, 9, 9);` expands to
+/// `type Cov = MatGen<f32, dims::U9, dims::U9>;`. The alias can be preceded by attributes and a
+/// visibility.
+#[macro_export]
+macro_rules! def_mat_alias {
+    ($(#[$attr:meta])* $vis:vis $name:ident = $T:ty, $nrows:tt, $ncols:tt) => {
+        $(#[$attr])*
+        $vis type $name =
+            $crate::MatGen<$T, $crate::__mat_dim!($nrows), $crate::__mat_dim!($ncols)>;
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __mat_dim {
+    (0) => { $crate::dims::U0 };
+    (1) => { $crate::dims::U1 };
+    (2) => { $crate::dims::U2 };
+    (3) => { $crate::dims::U3 };
+    (4) => { $crate::dims::U4 };
+    (5) => { $crate::dims::U5 };
+    (6) => { $crate::dims::U6 };
+    (7) => { $crate::dims::U7 };
+    (8) => { $crate::dims::U8 };
+    (9) => { $crate::dims::U9 };
+    (10) => { $crate::dims::U10 };
+    (11) => { $crate::dims::U11 };
+    (12) => { $crate::dims::U12 };
+    (13) => { $crate::dims::U13 };
+    (14) => { $crate::dims::U14 };
+    (15) => { $crate::dims::U15 };
+    (16) => { $crate::dims::U16 };
+    (17) => { $crate::dims::U17 };
+    (18) => { $crate::dims::U18 };
+    (19) => { $crate::dims::U19 };
+    (20) => { $crate::dims::U20 };
+    (21) => { $crate::dims::U21 };
+    (22) => { $crate::dims::U22 };
+    (23) => { $crate::dims::U23 };
+    (24) => { $crate::dims::U24 };
+    (25) => { $crate::dims::U25 };
+    (26) => { $crate::dims::U26 };
+    (27) => { $crate::dims::U27 };
+    (28) => { $crate::dims::U28 };
+    (29) => { $crate::dims::U29 };
+    (30) => { $crate::dims::U30 };
+    (31) => { $crate::dims::U31 };
+    (32) => { $crate::dims::U32 };
+    (33) => { $crate::dims::U33 };
+    (34) => { $crate::dims::U34 };
+    (35) => { $crate::dims::U35 };
+    (36) => { $crate::dims::U36 };
+    (37) => { $crate::dims::U37 };
+    (38) => { $crate::dims::U38 };
+    (39) => { $crate::dims::U39 };
+    (40) => { $crate::dims::U40 };
+    (41) => { $crate::dims::U41 };
+    (42) => { $crate::dims::U42 };
+    (43) => { $crate::dims::U43 };
+    (44) => { $crate::dims::U44 };
+    (45) => { $crate::dims::U45 };
+    (46) => { $crate::dims::U46 };
+    (47) => { $crate::dims::U47 };
+    (48) => { $crate::dims::U48 };
+    (49) => { $crate::dims::U49 };
+    (50) => { $crate::dims::U50 };
+    (51) => { $crate::dims::U51 };
+    (52) => { $crate::dims::U52 };
+    (53) => { $crate::dims::U53 };
+    (54) => { $crate::dims::U54 };
+    (55) => { $crate::dims::U55 };
+    (56) => { $crate::dims::U56 };
+    (57) => { $crate::dims::U57 };
+    (58) => { $crate::dims::U58 };
+    (59) => { $crate::dims::U59 };
+    (60) => { $crate::dims::U60 };
+    (61) => { $crate::dims::U61 };
+    (62) => { $crate::dims::U62 };
+    (63) => { $crate::dims::U63 };
+    (64) => { $crate::dims::U64 };
+}
@@ -0,0 +1,165 @@
+//! Double buffering
+//!
+//! `DoubleBuffered` holds two copies of a value, usually a matrix: the *front* buffer, which is
+//! read, and the *back* buffer, which is written. `swap` publishes the back buffer by exchanging
+//! the roles of the two buffers. No copy is made and no memory is allocated.
+//!
+//! With the `critical-section` feature `split` hands out a `Writer` and a `Reader` that can be used
+//! from different execution contexts, e.g. an interrupt handler that publishes a freshly computed
+//! matrix and the main loop that consumes it. `Reader::read` copies the front buffer and
+//! `Writer::swap` exchanges the buffers, both in a critical section, so the reader never observes a
+//! partially written matrix.
+//!
+//! # Example
+//!
+//! ```
+//! extern crate mat;
+//! extern crate typenum;
+//!
+//! use mat::MatGen;
+//! use mat::double::DoubleBuffered;
+//! use mat::traits::Matrix;
+//! use typenum::U2;
+//!
+//! # fn main() {
+//! let mut db = DoubleBuffered::new(MatGen::<f32, U2, U2>::zeros());
+//!
+//! // e.g. in an interrupt handler
+//! {
+//!     let m = db.write();
+//!     m.set(0, 0, 1.);
+//!     m.set(1, 1, 2.);
+//! }
+//!
+//! // the reader keeps seeing the old matrix until the new one is published
+//! assert_eq!(*db.read(), MatGen::zeros());
+//!
+//! db.swap();
+//! assert_eq!(db.read().get(1, 1), 2.);
+//! # }
+//! ```
+
+use core::cell::{Cell, UnsafeCell};
+
+/// A value with a front buffer, which is read, and a back buffer, which is written
+pub struct DoubleBuffered<T> {
+    buffers: [UnsafeCell<T>; 2],
+    // index of the front buffer
+    front: Cell<usize>,
+}
+
+impl<T> DoubleBuffered<T>
+where
+    T: Clone,
+{
+    /// Creates a double buffer whose front and back buffers are both `init`
+    pub fn new(init: T) -> Self {
+        DoubleBuffered {
+            buffers: [UnsafeCell::new(init.clone()), UnsafeCell::new(init)],
+            front: Cell::new(0),
+        }
+    }
+}
+
+impl<T> DoubleBuffered<T> {
+    /// Returns the back buffer
+    pub fn write(&mut self) -> &mut T {
+        let back = 1 - self.front.get();
+        unsafe { &mut *self.buffers[back].get() }
+    }
+
+    /// Publishes the back buffer; the previous front buffer becomes the back buffer
+    pub fn swap(&mut self) {
+        self.front.set(1 - self.front.get());
+    }
+
+    /// Returns the front buffer
+    pub fn read(&self) -> &T {
+        unsafe { &*self.buffers[self.front.get()].get() }
+    }
+
+    /// Splits the double buffer into a writer and a reader
+    ///
+    /// The writer and the reader can be sent to different execution contexts, like an interrupt
+    /// handler and the main loop.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate mat;
+    /// extern crate typenum;
+    ///
+    /// use mat::MatGen;
+    /// use mat::double::DoubleBuffered;
+    /// use typenum::U3;
+    ///
+    /// # fn main() {
+    /// let mut db = DoubleBuffered::new(MatGen::<f32, U3, U3>::zeros());
+    /// let (mut writer, mut reader) = db.split();
+    ///
+    /// *writer.write() = MatGen::identity();
+    /// assert_eq!(reader.read(), MatGen::zeros());
+    ///
+    /// writer.swap();
+    /// assert_eq!(reader.read(), MatGen::identity());
+    /// # }
+    /// ```
+    #[cfg(feature = "critical-section")]
+    pub fn split<'a>(&'a mut self) -> (Writer<'a, T>, Reader<'a, T>) {
+        (Writer { db: &*self }, Reader { db: &*self })
+    }
+}
+
+/// The writing half of a `DoubleBuffered` value
+#[cfg(feature = "critical-section")]
+pub struct Writer<'a, T>
+where
+    T: 'a,
+{
+    db: &'a DoubleBuffered<T>,
+}
+
+#[cfg(feature = "critical-section")]
+unsafe impl<'a, T> Send for Writer<'a, T> where T: Send {}
+
+#[cfg(feature = "critical-section")]
+impl<'a, T> Writer<'a, T> {
+    /// Returns the back buffer
+    pub fn write(&mut self) -> &mut T {
+        // NOTE(unsafe) only `swap`, which needs `&mut self`, changes which buffer is the back
+        // buffer, and the reader never accesses the back buffer
+        let back = 1 - self.db.front.get();
+        unsafe { &mut *self.db.buffers[back].get() }
+    }
+
+    /// Publishes the back buffer; the previous front buffer becomes the back buffer
+    pub fn swap(&mut self) {
+        ::critical_section::with(|_| self.db.front.set(1 - self.db.front.get()))
+    }
+}
+
+/// The reading half of a `DoubleBuffered` value
+#[cfg(feature = "critical-section")]
+pub struct Reader<'a, T>
+where
+    T: 'a,
+{
+    db: &'a DoubleBuffered<T>,
+}
+
+#[cfg(feature = "critical-section")]
+unsafe impl<'a, T> Send for Reader<'a, T> where T: Send {}
+
+#[cfg(feature = "critical-section")]
+impl<'a, T> Reader<'a, T>
+where
+    T: Clone,
+{
+    /// Returns a copy of the front buffer, the value most recently published by the writer
+    pub fn read(&mut self) -> T {
+        ::critical_section::with(|_| {
+            let front = self.db.front.get();
+            unsafe { (*self.db.buffers[front].get()).clone() }
+        })
+    }
+}
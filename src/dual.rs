@@ -0,0 +1,203 @@
+//! Dual numbers for forward-mode automatic differentiation
+//!
+//! A dual number `value + deriv ε`, where `ε² = 0`, carries the derivative of a computation along
+//! with its value. Evaluating a function with `Dual::variable(x)` as its input yields `f(x)` and
+//! `f'(x)`, exact up to rounding. `diff::jacobian` uses this to compute Jacobians of vector
+//! functions.
+//!
+//! # Example
+//!
+//! ```
+//! use mat::dual::Dual;
+//! use mat::traits::Trig;
+//!
+//! // f(x) = x sin(x) + 1 / x
+//! let f = |x: Dual<f64>| x * x.sin() + Dual::constant(1.) / x;
+//!
+//! let y = f(Dual::variable(2.));
+//! // NOTE `Trig` may be implemented by a different math library than `std`'s
+//! assert!((y.value - (2. * 2_f64.sin() + 0.5)).abs() < 1e-12);
+//! assert!((y.deriv - (2_f64.sin() + 2. * 2_f64.cos() - 0.25)).abs() < 1e-12);
+//! ```
+
+use core::ops;
+
+use traits::{Conj, One, Sqrt, Trig, Zero};
+
+/// A dual number, `value + deriv ε`
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Dual<T> {
+    /// Value
+    pub value: T,
+    /// Derivative
+    pub deriv: T,
+}
+
+impl<T> Dual<T> {
+    /// Creates the dual number `value + deriv ε`
+    pub fn new(value: T, deriv: T) -> Self {
+        Dual { value, deriv }
+    }
+}
+
+impl<T> Dual<T>
+where
+    T: Zero,
+{
+    /// Creates a constant, i.e. a dual number whose derivative is zero
+    pub fn constant(value: T) -> Self {
+        Dual::new(value, T::zero())
+    }
+}
+
+impl<T> Dual<T>
+where
+    T: From<u8>,
+{
+    /// Creates the variable of differentiation, i.e. a dual number whose derivative is one
+    pub fn variable(value: T) -> Self {
+        Dual::new(value, T::from(1))
+    }
+}
+
+/// Dual numbers over the reals are their own conjugate
+impl<T> Conj for Dual<T>
+where
+    T: Copy,
+{
+    fn conj(self) -> Self {
+        self
+    }
+}
+
+impl<T> Zero for Dual<T>
+where
+    T: Zero,
+{
+    fn zero() -> Self {
+        Dual::new(T::zero(), T::zero())
+    }
+}
+
+impl<T> One for Dual<T>
+where
+    T: One + Zero,
+{
+    fn one() -> Self {
+        Dual::constant(T::one())
+    }
+}
+
+impl<T> From<u8> for Dual<T>
+where
+    T: From<u8> + Zero,
+{
+    fn from(x: u8) -> Self {
+        Dual::constant(T::from(x))
+    }
+}
+
+impl<T> ops::Add for Dual<T>
+where
+    T: ops::Add<T, Output = T>,
+{
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Dual::new(self.value + rhs.value, self.deriv + rhs.deriv)
+    }
+}
+
+impl<T> ops::Sub for Dual<T>
+where
+    T: ops::Sub<T, Output = T>,
+{
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Dual::new(self.value - rhs.value, self.deriv - rhs.deriv)
+    }
+}
+
+impl<T> ops::Mul for Dual<T>
+where
+    T: ops::Add<T, Output = T> + ops::Mul<T, Output = T> + Copy,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Dual::new(
+            self.value * rhs.value,
+            self.value * rhs.deriv + self.deriv * rhs.value,
+        )
+    }
+}
+
+impl<T> ops::Div for Dual<T>
+where
+    T: ops::Div<T, Output = T> + ops::Mul<T, Output = T> + ops::Sub<T, Output = T> + Copy,
+{
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self {
+        Dual::new(
+            self.value / rhs.value,
+            (self.deriv * rhs.value - self.value * rhs.deriv) / (rhs.value * rhs.value),
+        )
+    }
+}
+
+impl<T> Sqrt for Dual<T>
+where
+    T: ops::Add<T, Output = T> + ops::Div<T, Output = T> + Copy + Sqrt,
+{
+    fn sqrt(self) -> Self {
+        let s = self.value.sqrt();
+        Dual::new(s, self.deriv / (s + s))
+    }
+}
+
+impl<T> Trig for Dual<T>
+where
+    T: ops::Add<T, Output = T>
+        + ops::Div<T, Output = T>
+        + ops::Mul<T, Output = T>
+        + ops::Sub<T, Output = T>
+        + Copy
+        + From<u8>
+        + Sqrt
+        + Trig
+        + Zero,
+{
+    fn sin(self) -> Self {
+        Dual::new(self.value.sin(), self.deriv * self.value.cos())
+    }
+
+    fn cos(self) -> Self {
+        Dual::new(self.value.cos(), T::zero() - self.deriv * self.value.sin())
+    }
+
+    fn tan(self) -> Self {
+        let t = self.value.tan();
+        Dual::new(t, self.deriv * (T::from(1) + t * t))
+    }
+
+    fn asin(self) -> Self {
+        let d = (T::from(1) - self.value * self.value).sqrt();
+        Dual::new(self.value.asin(), self.deriv / d)
+    }
+
+    fn acos(self) -> Self {
+        let d = (T::from(1) - self.value * self.value).sqrt();
+        Dual::new(self.value.acos(), T::zero() - self.deriv / d)
+    }
+
+    fn atan2(self, x: Self) -> Self {
+        let y = self;
+        let d = x.value * x.value + y.value * y.value;
+        Dual::new(
+            y.value.atan2(x.value),
+            (x.value * y.deriv - y.value * x.deriv) / d,
+        )
+    }
+}
@@ -0,0 +1,34 @@
+use core::fmt;
+
+/// The error type of the fallible linear algebra operations
+///
+/// # Example
+///
+/// ```
+/// use mat::{linalg, Error};
+///
+/// let a = mat::mat2([[1., 2.], [2., 4.]]);
+/// let b = mat::vec2(1., 2.);
+///
+/// assert_eq!(linalg::solve(&a, &b), Err(Error::Singular));
+/// assert_eq!(a.inverse(), Err(Error::Singular));
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Error {
+    /// The matrix is singular
+    Singular,
+    /// The columns of the matrix are linearly dependent
+    RankDeficient,
+    /// An iterative algorithm ran out of iterations before reaching its tolerance
+    DidNotConverge,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Singular => f.write_str("the matrix is singular"),
+            Error::RankDeficient => f.write_str("the columns of the matrix are linearly dependent"),
+            Error::DidNotConverge => f.write_str("the algorithm did not converge"),
+        }
+    }
+}
@@ -0,0 +1,180 @@
+//! Recursive and finite impulse response (FIR) filters
+//!
+//! # Example
+//!
+//! Identify the parameters of the model `y = 2 * x0 - 3 * x1` from a stream of observations
+//!
+//! ```
+//! extern crate mat;
+//! extern crate typenum;
+//!
+//! use mat::filter::Rls;
+//! use mat::mat;
+//! use typenum::U2;
+//!
+//! # fn main() {
+//! let mut rls = Rls::<f64, U2>::new(1e3, 1.);
+//!
+//! for &(x0, x1) in &[(1., 0.), (0., 1.), (1., 1.), (2., -1.)] {
+//!     rls.update(&mat![[x0], [x1]], 2. * x0 - 3. * x1);
+//! }
+//!
+//! assert!((rls.theta()[0] - 2.).abs() < 1e-2);
+//! assert!((rls.theta()[1] + 3.).abs() < 1e-2);
+//! # }
+//! ```
+
+use core::ops;
+
+use generic_array::ArrayLength;
+use typenum::{Prod, U1, Unsigned};
+
+use conv::{Mode, Valid, ValidLen};
+use {MatGen, Vector};
+use traits::{Matrix, Zero};
+
+/// Recursive least squares (RLS) estimator
+///
+/// Estimates the parameters `θ` of the linear model `y = xᵀ θ` one observation at a time. Both the
+/// parameter vector and the `N` by `N` inverse correlation matrix `P` are statically allocated.
+pub struct Rls<T, N>
+where
+    N: Unsigned + ops::Mul<N> + ops::Mul<U1>,
+    Prod<N, N>: ArrayLength<T>,
+    Prod<N, U1>: ArrayLength<T>,
+    T: Copy,
+{
+    theta: Vector<T, N>,
+    p: MatGen<T, N, N>,
+    lambda: T,
+}
+
+impl<T, N> Rls<T, N>
+where
+    N: Unsigned + ops::Mul<N> + ops::Mul<U1>,
+    Prod<N, N>: ArrayLength<T>,
+    Prod<N, U1>: ArrayLength<T>,
+    T: ops::Add<T, Output = T>
+        + ops::Div<T, Output = T>
+        + ops::Mul<T, Output = T>
+        + ops::Sub<T, Output = T>
+        + Copy
+        + Zero,
+{
+    /// Creates a new estimator
+    ///
+    /// The parameter vector starts at zero and `P` starts at `delta` times the identity matrix; a
+    /// large `delta` expresses low confidence in the initial estimate. `lambda` is the forgetting
+    /// factor, usually in the range `0.95..=1`; `1` weights all the observations equally.
+    pub fn new(delta: T, lambda: T) -> Self {
+        Rls {
+            theta: MatGen::zeros(),
+            p: MatGen::from_fn(|r, c| if r == c { delta } else { T::zero() }),
+            lambda,
+        }
+    }
+
+    /// Updates the estimate with the observation `y` of the regressor vector `x`
+    ///
+    /// Returns the *a priori* prediction error, `y - xᵀ θ`, computed before the update
+    pub fn update<X>(&mut self, x: X, y: T) -> T
+    where
+        X: Matrix<Elem = T, NROWS = N, NCOLS = U1>,
+    {
+        let n = N::to_usize();
+
+        // P x
+        let px = (&self.p * x).eval();
+
+        // gain vector
+        let mut denom = self.lambda;
+        let mut prediction = T::zero();
+        for i in 0..n {
+            denom = denom + x.get(i, 0) * px.get(i, 0);
+            prediction = prediction + x.get(i, 0) * self.theta.get(i, 0);
+        }
+        let k = Vector::<T, N>::from_fn(|r, _| px.get(r, 0) / denom);
+
+        let error = y - prediction;
+
+        for i in 0..n {
+            let theta = self.theta.get(i, 0) + k.get(i, 0) * error;
+            self.theta.set(i, 0, theta);
+        }
+
+        // NOTE `P` is symmetric so `xᵀ P` is the transpose of `P x`
+        for r in 0..n {
+            for c in 0..n {
+                let p = (self.p.get(r, c) - k.get(r, 0) * px.get(c, 0)) / self.lambda;
+                self.p.set(r, c, p);
+            }
+        }
+
+        error
+    }
+
+    /// Returns the current parameter estimate
+    pub fn theta(&self) -> &Vector<T, N> {
+        &self.theta
+    }
+
+    /// Returns the current inverse correlation matrix
+    pub fn p(&self) -> &MatGen<T, N, N> {
+        &self.p
+    }
+}
+
+/// Filters a window of `L` samples with the FIR filter whose `N` taps are `coeffs`
+///
+/// The output `y[i] = Σ coeffs[k] window[i + N - 1 - k]` is computed for the `L - N + 1`
+/// positions where all the taps overlap the window, i.e. the first `N - 1` samples of the window
+/// are the history of the first output. This is the product of an `L - N + 1` by `L` Toeplitz
+/// matrix and the window, but the Toeplitz matrix is never materialized.
+///
+/// To filter a stream block by block keep the last `N - 1` samples of each window as the start of
+/// the next one.
+///
+/// # Example
+///
+/// ```
+/// extern crate mat;
+/// extern crate typenum;
+///
+/// use mat::filter;
+/// use mat::Vector;
+/// use typenum::{U3, U6};
+///
+/// # fn main() {
+/// // 3-tap filter with an asymmetric impulse response
+/// let coeffs = Vector::<i32, U3>::from_fn(|r, _| [1, 10, 100][r]);
+/// let window = Vector::<i32, U6>::from_fn(|r, _| [0, 0, 1, 0, 0, 2][r]);
+///
+/// // the impulse at sample 2 shows up as the taps in order
+/// let y = filter::fir_apply(&coeffs, &window);
+/// assert_eq!(y.iter().cloned().collect::<Vec<_>>(), [1, 10, 100, 2]);
+/// # }
+/// ```
+pub fn fir_apply<T, N, L>(
+    coeffs: &Vector<T, N>,
+    window: &Vector<T, L>,
+) -> Vector<T, ValidLen<L, N>>
+where
+    N: Unsigned + ops::Mul<U1>,
+    L: Unsigned + ops::Mul<U1>,
+    Valid: Mode<L, N>,
+    ValidLen<L, N>: ops::Mul<U1>,
+    Prod<N, U1>: ArrayLength<T>,
+    Prod<L, U1>: ArrayLength<T>,
+    Prod<ValidLen<L, N>, U1>: ArrayLength<T>,
+    T: ops::Add<T, Output = T> + ops::Mul<T, Output = T> + Clone + Zero,
+{
+    let (coeffs, window) = (coeffs.as_slice(), window.as_slice());
+
+    MatGen::from_fn(|i, _| {
+        let mut sum = T::zero();
+        for (h, x) in coeffs.iter().zip(window[i..i + coeffs.len()].iter().rev()) {
+            sum = sum + h.clone() * x.clone();
+        }
+        sum
+    })
+}
@@ -0,0 +1,253 @@
+//! Attitude and heading reference system (AHRS) filters
+//!
+//! These filters fuse gyroscope and accelerometer readings into an orientation estimate. The
+//! orientation is a unit quaternion that rotates vectors from the sensor frame to the earth frame,
+//! whose Z axis points up. Gyroscope readings are in radians per second; accelerometer readings can
+//! be in any unit as only their direction is used.
+//!
+//! # Example
+//!
+//! A sensor lying still, but tilted 0.3 radians about its X axis
+//!
+//! ```
+//! use mat::fusion::Mahony;
+//!
+//! let mut ahrs = Mahony::new(2., 0.);
+//!
+//! let gyro = mat::vec3(0., 0., 0.);
+//! let accel = mat::vec3(0., 0.3_f64.sin(), 0.3_f64.cos());
+//! for _ in 0..1_000 {
+//!     ahrs.update(&gyro, &accel, 0.01);
+//! }
+//!
+//! let roll = ahrs.orientation().to_euler(mat::geometry::EulerSequence::Zyx)[2];
+//! assert!((roll - 0.3).abs() < 1e-6);
+//! ```
+
+use core::ops;
+
+use typenum::{U1, U3, U4};
+
+use {MatGen, Vec3};
+use geometry::{self, Quaternion};
+use traits::{Matrix, Sqrt, Transpose, Zero};
+
+/// Madgwick's gradient descent orientation filter
+pub struct Madgwick<T> {
+    q: Quaternion<T>,
+    beta: T,
+}
+
+impl<T> Madgwick<T>
+where
+    T: ops::Add<T, Output = T>
+        + ops::Div<T, Output = T>
+        + ops::Mul<T, Output = T>
+        + ops::Sub<T, Output = T>
+        + Copy
+        + From<u8>
+        + PartialEq
+        + Sqrt
+        + Zero,
+{
+    /// Creates a new filter that starts at the identity orientation
+    ///
+    /// `beta` is the gain of the accelerometer correction; larger values converge faster but let
+    /// more accelerometer noise through.
+    pub fn new(beta: T) -> Self {
+        Madgwick {
+            q: Quaternion::identity(),
+            beta,
+        }
+    }
+
+    /// Returns the current orientation estimate
+    pub fn orientation(&self) -> Quaternion<T> {
+        self.q
+    }
+
+    /// Overrides the orientation estimate
+    pub fn set_orientation(&mut self, q: Quaternion<T>) {
+        self.q = q;
+    }
+
+    /// Updates the estimate with the angular rate `gyro` and the acceleration `accel` measured over
+    /// the last `dt` seconds
+    ///
+    /// The accelerometer correction is skipped if `accel` is zero.
+    pub fn update<G, A>(&mut self, gyro: G, accel: A, dt: T)
+    where
+        G: Matrix<Elem = T, NROWS = U3, NCOLS = U1>,
+        A: Matrix<Elem = T, NROWS = U3, NCOLS = U1>,
+    {
+        let q = self.q;
+        let mut q_dot = rate(q, gyro.get(0, 0), gyro.get(1, 0), gyro.get(2, 0));
+
+        if accel.norm_squared() != T::zero() {
+            let a = accel.normalize();
+            let (two, four) = (T::from(2), T::from(4));
+            let Quaternion { w, x, y, z } = q;
+
+            // objective function: predicted minus measured direction of gravity
+            let g = gravity(q);
+            let f = Vec3::from_fn(|r, _| g[r] - a[r]);
+            let z0 = T::zero();
+            let rows = [
+                [z0 - two * y, two * z, z0 - two * w, two * x],
+                [two * x, two * w, two * z, two * y],
+                [z0, z0 - four * x, z0 - four * y, z0],
+            ];
+            let j = MatGen::<T, U3, U4>::from_fn(|r, c| rows[r][c]);
+
+            // gradient step: Jᵀ f
+            let s = ((&j).t() * &f).eval();
+            if s.norm_squared() != T::zero() {
+                let s = s.normalize();
+                q_dot = Quaternion::new(
+                    q_dot.w - self.beta * s[0],
+                    q_dot.x - self.beta * s[1],
+                    q_dot.y - self.beta * s[2],
+                    q_dot.z - self.beta * s[3],
+                );
+            }
+        }
+
+        self.q = integrate(q, q_dot, dt);
+    }
+}
+
+/// Mahony's nonlinear complementary filter
+pub struct Mahony<T>
+where
+    T: Copy,
+{
+    q: Quaternion<T>,
+    kp: T,
+    ki: T,
+    integral: Vec3<T>,
+}
+
+impl<T> Mahony<T>
+where
+    T: ops::Add<T, Output = T>
+        + ops::Div<T, Output = T>
+        + ops::Mul<T, Output = T>
+        + ops::Sub<T, Output = T>
+        + Copy
+        + From<u8>
+        + PartialEq
+        + Sqrt
+        + Zero,
+{
+    /// Creates a new filter that starts at the identity orientation
+    ///
+    /// `kp` is the proportional gain of the accelerometer correction and `ki` the integral gain
+    /// used to estimate the gyroscope bias; a `ki` of zero disables the bias estimation.
+    pub fn new(kp: T, ki: T) -> Self {
+        Mahony {
+            q: Quaternion::identity(),
+            kp,
+            ki,
+            integral: Vec3::zeros(),
+        }
+    }
+
+    /// Returns the current orientation estimate
+    pub fn orientation(&self) -> Quaternion<T> {
+        self.q
+    }
+
+    /// Overrides the orientation estimate
+    pub fn set_orientation(&mut self, q: Quaternion<T>) {
+        self.q = q;
+    }
+
+    /// Returns the current estimate of the gyroscope bias correction
+    ///
+    /// This is the integral term that is added to the gyroscope readings.
+    pub fn bias_correction(&self) -> &Vec3<T> {
+        &self.integral
+    }
+
+    /// Updates the estimate with the angular rate `gyro` and the acceleration `accel` measured over
+    /// the last `dt` seconds
+    ///
+    /// The accelerometer correction is skipped if `accel` is zero.
+    pub fn update<G, A>(&mut self, gyro: G, accel: A, dt: T)
+    where
+        G: Matrix<Elem = T, NROWS = U3, NCOLS = U1>,
+        A: Matrix<Elem = T, NROWS = U3, NCOLS = U1>,
+    {
+        let mut omega = gyro.eval();
+
+        if accel.norm_squared() != T::zero() {
+            let a = accel.normalize();
+
+            // rotation that takes the predicted direction of gravity onto the measured one
+            let e = geometry::cross(&a, &gravity(self.q));
+
+            if self.ki != T::zero() {
+                for i in 0..3 {
+                    self.integral[i] = self.integral[i] + self.ki * e[i] * dt;
+                }
+            }
+
+            for i in 0..3 {
+                omega[i] = omega[i] + self.integral[i] + self.kp * e[i];
+            }
+        }
+
+        let q_dot = rate(self.q, omega[0], omega[1], omega[2]);
+        self.q = integrate(self.q, q_dot, dt);
+    }
+}
+
+// direction of gravity in the sensor frame predicted by the orientation `q`
+fn gravity<T>(q: Quaternion<T>) -> Vec3<T>
+where
+    T: ops::Add<T, Output = T>
+        + ops::Mul<T, Output = T>
+        + ops::Sub<T, Output = T>
+        + Copy
+        + From<u8>
+        + Zero,
+{
+    // third row of the rotation matrix, i.e. `Rᵀ e_z`
+    let r = q.to_matrix();
+    Vec3::from_fn(|i, _| r.get(2, i))
+}
+
+// time derivative of `q` when rotating at the angular rate `(x, y, z)`: `q ⊗ (0, ω) / 2`
+fn rate<T>(q: Quaternion<T>, x: T, y: T, z: T) -> Quaternion<T>
+where
+    T: ops::Add<T, Output = T>
+        + ops::Div<T, Output = T>
+        + ops::Mul<T, Output = T>
+        + ops::Sub<T, Output = T>
+        + Copy
+        + From<u8>
+        + Zero,
+{
+    let two = T::from(2);
+    let p = q * Quaternion::new(T::zero(), x, y, z);
+    Quaternion::new(p.w / two, p.x / two, p.y / two, p.z / two)
+}
+
+fn integrate<T>(q: Quaternion<T>, q_dot: Quaternion<T>, dt: T) -> Quaternion<T>
+where
+    T: ops::Add<T, Output = T>
+        + ops::Div<T, Output = T>
+        + ops::Mul<T, Output = T>
+        + ops::Sub<T, Output = T>
+        + Copy
+        + From<u8>
+        + Sqrt
+        + Zero,
+{
+    Quaternion::new(
+        q.w + q_dot.w * dt,
+        q.x + q_dot.x * dt,
+        q.y + q_dot.y * dt,
+        q.z + q_dot.z * dt,
+    ).normalize()
+}
@@ -0,0 +1,205 @@
+//! Pluggable backends for eager matrix multiplication
+//!
+//! `MatGen::matmul` multiplies two matrices into a new one through the `GemmBackend` that the
+//! element type selects with `DefaultBackend`, and so does evaluating the lazy product of two
+//! `MatGen` (or `Mat`) matrices, `(&a * &b).eval()`. The portable backend is used for every element
+//! type unless the `cmsis-dsp` feature is enabled, in which case `f32` products are computed by
+//! CMSIS-DSP's `arm_mat_mult_f32`. The application must then link to the CMSIS-DSP library (e.g.
+//! `libarm_cortexM4lf_math.a`). `MatGen::mul_with` picks the backend explicitly.
+//!
+//! NOTE with the `num-traits` feature `Zero` is implemented by a blanket implementation, which
+//! can't select a backend, so `(&a * &b).eval()` always uses the tiled product; `matmul` still
+//! uses the default backend.
+//!
+//! # Example
+//!
+//! ```
+//! extern crate mat;
+//! extern crate typenum;
+//!
+//! use mat::MatGen;
+//! use mat::gemm::Portable;
+//! use typenum::{U2, U3};
+//!
+//! # fn main() {
+//! let a = MatGen::<f32, U2, U3>::from_fn(|r, c| (r + c) as f32);
+//! let b = MatGen::<f32, U3, U2>::from_fn(|r, c| (r * c) as f32);
+//!
+//! let c = a.matmul(&b);
+//! assert_eq!(c, &a * &b);
+//! assert_eq!(c, a.mul_with::<Portable, _>(&b));
+//! # }
+//! ```
+
+use core::ops;
+
+use generic_array::ArrayLength;
+use typenum::{Prod, Unsigned};
+
+use {kernel, MatGen};
+use traits::Zero;
+
+/// A matrix multiplication kernel
+///
+/// The operands and the result are stored in row major order
+pub trait GemmBackend<T> {
+    /// Stores the product of the `m` by `k` matrix `a` and the `k` by `n` matrix `b` in the `m` by
+    /// `n` matrix `c`
+    fn gemm(a: &[T], b: &[T], c: &mut [T], m: usize, k: usize, n: usize);
+}
+
+/// The backend `MatGen::matmul` uses for elements of this type
+pub trait DefaultBackend: Sized {
+    /// The backend
+    type Backend: GemmBackend<Self>;
+}
+
+/// Pure Rust backend that works with any element type
+///
+/// Every element is summed in the order of the lazy product so both produce the same result.
+pub struct Portable;
+
+impl<T> GemmBackend<T> for Portable
+where
+    T: ops::Add<T, Output = T> + ops::Mul<T, Output = T> + Clone + Zero,
+{
+    fn gemm(a: &[T], b: &[T], c: &mut [T], m: usize, k: usize, n: usize) {
+        kernel::gemm(a, b, c, m, k, n)
+    }
+}
+
+/// CMSIS-DSP backend for `f32` matrices
+///
+/// This calls `arm_mat_mult_f32` which uses the FPU and DSP instructions of Cortex-M4F and M7
+/// processors.
+///
+/// # Panics
+///
+/// `gemm` panics if a dimension exceeds `u16::MAX`
+#[cfg(feature = "cmsis-dsp")]
+pub struct CmsisDsp;
+
+#[cfg(feature = "cmsis-dsp")]
+#[allow(non_camel_case_types)]
+#[repr(C)]
+struct arm_matrix_instance_f32 {
+    num_rows: u16,
+    num_cols: u16,
+    p_data: *mut f32,
+}
+
+#[cfg(feature = "cmsis-dsp")]
+extern "C" {
+    // NOTE the returned `arm_status` is a C enum, which has the size of an `int`
+    fn arm_mat_mult_f32(
+        src_a: *const arm_matrix_instance_f32,
+        src_b: *const arm_matrix_instance_f32,
+        dst: *mut arm_matrix_instance_f32,
+    ) -> i32;
+}
+
+#[cfg(feature = "cmsis-dsp")]
+impl GemmBackend<f32> for CmsisDsp {
+    fn gemm(a: &[f32], b: &[f32], c: &mut [f32], m: usize, k: usize, n: usize) {
+        let max = usize::from(u16::MAX);
+        assert!(m <= max && k <= max && n <= max);
+        assert!(a.len() == m * k && b.len() == k * n && c.len() == m * n);
+
+        // NOTE CMSIS-DSP never writes to the sources
+        let a = arm_matrix_instance_f32 {
+            num_rows: m as u16,
+            num_cols: k as u16,
+            p_data: a.as_ptr() as *mut f32,
+        };
+        let b = arm_matrix_instance_f32 {
+            num_rows: k as u16,
+            num_cols: n as u16,
+            p_data: b.as_ptr() as *mut f32,
+        };
+        let mut c = arm_matrix_instance_f32 {
+            num_rows: m as u16,
+            num_cols: n as u16,
+            p_data: c.as_mut_ptr(),
+        };
+
+        // NOTE the dimensions have been checked so this can't fail with `ARM_MATH_SIZE_MISMATCH`
+        let status = unsafe { arm_mat_mult_f32(&a, &b, &mut c) };
+        debug_assert_eq!(status, 0);
+    }
+}
+
+#[cfg(feature = "cmsis-dsp")]
+impl DefaultBackend for f32 {
+    type Backend = CmsisDsp;
+}
+
+#[cfg(not(feature = "cmsis-dsp"))]
+impl DefaultBackend for f32 {
+    type Backend = Portable;
+}
+
+macro_rules! portable {
+    ($($T:ty),+) => {
+        $(
+            impl DefaultBackend for $T {
+                type Backend = Portable;
+            }
+        )+
+    };
+}
+
+portable!(f64, i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+/// Multiplies with the default backend of `T`; this backs `Zero::gemm`
+#[cfg(not(feature = "num-traits"))]
+pub(crate) fn default_gemm<T>(a: &[T], b: &[T], c: &mut [T], m: usize, k: usize, n: usize) -> bool
+where
+    T: DefaultBackend,
+{
+    T::Backend::gemm(a, b, c, m, k, n);
+    true
+}
+
+impl<T, NROWS, NCOLS> MatGen<T, NROWS, NCOLS>
+where
+    NROWS: Unsigned + ops::Mul<NCOLS>,
+    NCOLS: Unsigned,
+    Prod<NROWS, NCOLS>: ArrayLength<T>,
+    T: Clone,
+{
+    /// Returns the matrix product `self * rhs`, computed by the default backend of the element type
+    pub fn matmul<K>(&self, rhs: &MatGen<T, NCOLS, K>) -> MatGen<T, NROWS, K>
+    where
+        NCOLS: ops::Mul<K>,
+        NROWS: ops::Mul<K>,
+        K: Unsigned,
+        Prod<NCOLS, K>: ArrayLength<T>,
+        Prod<NROWS, K>: ArrayLength<T>,
+        T: DefaultBackend + Zero,
+    {
+        self.mul_with::<T::Backend, K>(rhs)
+    }
+
+    /// Returns the matrix product `self * rhs`, computed by the backend `B`
+    pub fn mul_with<B, K>(&self, rhs: &MatGen<T, NCOLS, K>) -> MatGen<T, NROWS, K>
+    where
+        B: GemmBackend<T>,
+        NCOLS: ops::Mul<K>,
+        NROWS: ops::Mul<K>,
+        K: Unsigned,
+        Prod<NCOLS, K>: ArrayLength<T>,
+        Prod<NROWS, K>: ArrayLength<T>,
+        T: Zero,
+    {
+        let mut out: MatGen<T, NROWS, K> = MatGen::zeros();
+        B::gemm(
+            self.as_slice(),
+            rhs.as_slice(),
+            out.as_mut_slice(),
+            NROWS::to_usize(),
+            NCOLS::to_usize(),
+            K::to_usize(),
+        );
+        out
+    }
+}
@@ -0,0 +1,1190 @@
+//! Geometry
+//!
+//! # Examples
+//!
+//! Cross product
+//!
+//! ```
+//! use mat::{geometry, mat};
+//! use mat::traits::Matrix;
+//!
+//! let x = mat::vec3(1, 0, 0);
+//! let y = mat::vec3(0, 1, 0);
+//!
+//! let z = geometry::cross(&x, &y);
+//! assert_eq!(z.dot(&mat::vec3(0, 0, 1)), 1);
+//!
+//! // also works with row vectors
+//! let z = geometry::cross(&mat![[0, 1, 0]], &mat![[1, 0, 0]]);
+//! assert_eq!(z[2], -1);
+//! ```
+//!
+//! Rotations
+//!
+//! ```
+//! use std::f64::consts::FRAC_PI_2;
+//!
+//! use mat::geometry;
+//! use mat::traits::Matrix;
+//!
+//! let r = geometry::rotation3_z(FRAC_PI_2);
+//! let v = (&r * &mat::vec3(1., 0., 0.)).eval();
+//! assert!(v[0].abs() < 1e-12 && (v[1] - 1.).abs() < 1e-12);
+//!
+//! let axis = mat::vec3(0., 0., 1.);
+//! let q = geometry::rotation3_axis_angle(&axis, FRAC_PI_2);
+//! for i in 0..3 {
+//!     for j in 0..3 {
+//!         assert!((q.get(i, j) - r.get(i, j)).abs() < 1e-12);
+//!     }
+//! }
+//! ```
+//!
+//! Homogeneous transforms of a two link kinematic chain
+//!
+//! ```
+//! use std::f64::consts::FRAC_PI_2;
+//!
+//! use mat::geometry;
+//! use mat::traits::Matrix;
+//!
+//! // each link rotates about its Z axis and then extends 1 unit along its X axis
+//! let link = |angle| {
+//!     let r = geometry::rotation3_z(angle);
+//!     geometry::compose(
+//!         &geometry::rigid_transform(&r, &mat::vec3(0., 0., 0.)),
+//!         &geometry::translation(&mat::vec3(1., 0., 0.)),
+//!     )
+//! };
+//!
+//! let tip = geometry::compose_all(&[link(FRAC_PI_2), link(-FRAC_PI_2)]);
+//!
+//! // the tip of the chain is at (1, 1, 0)
+//! assert!((tip.get(0, 3) - 1.).abs() < 1e-12);
+//! assert!((tip.get(1, 3) - 1.).abs() < 1e-12);
+//! assert!(tip.get(2, 3).abs() < 1e-12);
+//! ```
+
+use core::ops;
+
+use generic_array::ArrayLength;
+use typenum::{Min, NonZero, Prod, U1, U2, U3, Unsigned};
+
+use {Mat2, Mat3, Mat4, MatGen, Product, Vec2, Vec3, Vector};
+use linalg::abs;
+use traits::{Matrix, Sqrt, Trig, Zero};
+
+/// Intrinsic Euler angle sequences
+///
+/// The angles `[a, b, c]` of a sequence are applied in order about the named axes, each rotation
+/// being about the axes produced by the previous ones.
+///
+/// At gimbal lock, when the middle angle is `±π/2`, the first and third rotations are about the
+/// same axis and only their sum (or difference) can be recovered. Conversions *to* Euler angles
+/// report the third angle as zero in that case.
+///
+/// Near gimbal lock the first and third angles are computed from elements of the rotation matrix
+/// that are dominated by rounding errors, so conversions *to* Euler angles treat any rotation whose
+/// middle angle `b` has `|cos(b)| <= 1e-6`, i.e. is within about `1e-6` radians of `±π/2`, as
+/// being at gimbal lock.
+///
+/// # Example
+///
+/// ```
+/// use mat::geometry::{self, EulerSequence, Quaternion};
+///
+/// let (yaw, pitch, roll): (f64, f64, f64) = (0.3, -0.2, 0.1);
+/// let r = geometry::from_euler(EulerSequence::Zyx, [yaw, pitch, roll]);
+///
+/// let angles = geometry::to_euler(EulerSequence::Zyx, &r);
+/// assert!((angles[0] - yaw).abs() < 1e-12);
+/// assert!((angles[1] - pitch).abs() < 1e-12);
+/// assert!((angles[2] - roll).abs() < 1e-12);
+///
+/// let q = Quaternion::from_euler(EulerSequence::Xyz, [roll, pitch, yaw]);
+/// let angles = q.to_euler(EulerSequence::Xyz);
+/// assert!((angles[0] - roll).abs() < 1e-12);
+///
+/// // (almost) at gimbal lock only the difference of the first and third angles is observable
+/// let b = core::f64::consts::FRAC_PI_2 - 1e-9;
+/// let r = geometry::from_euler(EulerSequence::Zyx, [0.5, b, 0.2]);
+///
+/// let angles = geometry::to_euler(EulerSequence::Zyx, &r);
+/// assert!((angles[0] - 0.3).abs() < 1e-6);
+/// assert_eq!(angles[2], 0.);
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum EulerSequence {
+    /// Yaw about Z, then pitch about Y, then roll about X: `R = Rz(a) Ry(b) Rx(c)`
+    Zyx,
+    /// Rotation about X, then Y, then Z: `R = Rx(a) Ry(b) Rz(c)`
+    Xyz,
+}
+
+/// A quaternion, `w + x i + y j + z k`
+///
+/// Quaternions of unit norm represent rotations in 3D space.
+///
+/// # Example
+///
+/// ```
+/// use std::f64::consts::FRAC_PI_2;
+///
+/// use mat::geometry::{self, Quaternion};
+/// use mat::traits::Matrix;
+///
+/// let q = Quaternion::from_axis_angle(&mat::vec3(0., 0., 1.), FRAC_PI_2);
+///
+/// let v = q.rotate(&mat::vec3(1., 0., 0.));
+/// assert!(v[0].abs() < 1e-12 && (v[1] - 1.).abs() < 1e-12);
+///
+/// // conversions to and from rotation matrices
+/// let r = q.to_matrix();
+/// let expected = geometry::rotation3_z(FRAC_PI_2);
+/// for i in 0..3 {
+///     for j in 0..3 {
+///         assert!((r.get(i, j) - expected.get(i, j)).abs() < 1e-12);
+///     }
+/// }
+///
+/// let p = Quaternion::from_matrix(&r);
+/// assert!((p.w - q.w).abs() < 1e-12 && (p.z - q.z).abs() < 1e-12);
+///
+/// // composition: two quarter turns make a half turn
+/// let v = (q * q).rotate(&mat::vec3(1., 0., 0.));
+/// assert!((v[0] + 1.).abs() < 1e-12);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Quaternion<T> {
+    /// Scalar part
+    pub w: T,
+    /// `i` component of the vector part
+    pub x: T,
+    /// `j` component of the vector part
+    pub y: T,
+    /// `k` component of the vector part
+    pub z: T,
+}
+
+impl<T> Quaternion<T>
+where
+    T: ops::Add<T, Output = T>
+        + ops::Mul<T, Output = T>
+        + ops::Sub<T, Output = T>
+        + Copy
+        + From<u8>
+        + Zero,
+{
+    /// Creates the quaternion `w + x i + y j + z k`
+    pub fn new(w: T, x: T, y: T, z: T) -> Self {
+        Quaternion { w, x, y, z }
+    }
+
+    /// Returns the quaternion that represents no rotation
+    pub fn identity() -> Self {
+        Quaternion::new(T::from(1), T::zero(), T::zero(), T::zero())
+    }
+
+    /// Returns the unit quaternion that rotates by `angle` radians around `axis`
+    ///
+    /// `axis` must be a unit vector.
+    pub fn from_axis_angle<A>(axis: A, angle: T) -> Self
+    where
+        A: Matrix<Elem = T, NROWS = U3, NCOLS = U1>,
+        T: ops::Div<T, Output = T> + Trig,
+    {
+        let half = angle / T::from(2);
+        let (s, c) = (half.sin(), half.cos());
+        Quaternion::new(c, s * axis.get(0, 0), s * axis.get(1, 0), s * axis.get(2, 0))
+    }
+
+    /// Returns the unit quaternion that represents the same rotation as the matrix `m`
+    ///
+    /// `m` must be a rotation matrix, i.e. orthogonal with determinant `+1`. The scalar part of the
+    /// returned quaternion is non-negative.
+    pub fn from_matrix<M>(m: M) -> Self
+    where
+        M: Matrix<Elem = T, NROWS = U3, NCOLS = U3>,
+        T: ops::Div<T, Output = T> + PartialOrd + Sqrt,
+    {
+        let one = T::from(1);
+        let four = T::from(4);
+        let (m00, m11, m22) = (m.get(0, 0), m.get(1, 1), m.get(2, 2));
+        let trace = m00 + m11 + m22;
+
+        // Shepperd's method: pick the largest component to avoid dividing by a small number
+        let q = if trace > T::zero() {
+            let s = (trace + one).sqrt() * T::from(2);
+            Quaternion::new(
+                s / four,
+                (m.get(2, 1) - m.get(1, 2)) / s,
+                (m.get(0, 2) - m.get(2, 0)) / s,
+                (m.get(1, 0) - m.get(0, 1)) / s,
+            )
+        } else if m00 > m11 && m00 > m22 {
+            let s = (one + m00 - m11 - m22).sqrt() * T::from(2);
+            Quaternion::new(
+                (m.get(2, 1) - m.get(1, 2)) / s,
+                s / four,
+                (m.get(0, 1) + m.get(1, 0)) / s,
+                (m.get(0, 2) + m.get(2, 0)) / s,
+            )
+        } else if m11 > m22 {
+            let s = (one + m11 - m00 - m22).sqrt() * T::from(2);
+            Quaternion::new(
+                (m.get(0, 2) - m.get(2, 0)) / s,
+                (m.get(0, 1) + m.get(1, 0)) / s,
+                s / four,
+                (m.get(1, 2) + m.get(2, 1)) / s,
+            )
+        } else {
+            let s = (one + m22 - m00 - m11).sqrt() * T::from(2);
+            Quaternion::new(
+                (m.get(1, 0) - m.get(0, 1)) / s,
+                (m.get(0, 2) + m.get(2, 0)) / s,
+                (m.get(1, 2) + m.get(2, 1)) / s,
+                s / four,
+            )
+        };
+
+        if q.w < T::zero() {
+            Quaternion::new(T::zero() - q.w, T::zero() - q.x, T::zero() - q.y, T::zero() - q.z)
+        } else {
+            q
+        }
+    }
+
+    /// Returns the unit quaternion that represents the rotation of the Euler `angles`
+    pub fn from_euler(seq: EulerSequence, angles: [T; 3]) -> Self
+    where
+        T: ops::Div<T, Output = T> + Trig,
+    {
+        let (o, z) = (T::from(1), T::zero());
+        let (x, y, z) = (::vec3(o, z, z), ::vec3(z, o, z), ::vec3(z, z, o));
+
+        match seq {
+            EulerSequence::Zyx => {
+                Quaternion::from_axis_angle(&z, angles[0])
+                    * Quaternion::from_axis_angle(&y, angles[1])
+                    * Quaternion::from_axis_angle(&x, angles[2])
+            }
+            EulerSequence::Xyz => {
+                Quaternion::from_axis_angle(&x, angles[0])
+                    * Quaternion::from_axis_angle(&y, angles[1])
+                    * Quaternion::from_axis_angle(&z, angles[2])
+            }
+        }
+    }
+
+    /// Returns the Euler angles of the rotation represented by this unit quaternion
+    ///
+    /// See `EulerSequence` for the handling of gimbal lock.
+    pub fn to_euler(self, seq: EulerSequence) -> [T; 3]
+    where
+        T: ops::Div<T, Output = T> + PartialOrd + Trig,
+    {
+        to_euler(seq, &self.to_matrix())
+    }
+
+    /// Returns the conjugate quaternion, `w - x i - y j - z k`
+    ///
+    /// For unit quaternions this is the inverse rotation.
+    pub fn conjugate(self) -> Self {
+        Quaternion::new(self.w, T::zero() - self.x, T::zero() - self.y, T::zero() - self.z)
+    }
+
+    /// Returns the squared norm of the quaternion
+    pub fn norm_squared(self) -> T {
+        self.w * self.w + self.x * self.x + self.y * self.y + self.z * self.z
+    }
+
+    /// Returns the norm of the quaternion
+    pub fn norm(self) -> T
+    where
+        T: Sqrt,
+    {
+        self.norm_squared().sqrt()
+    }
+
+    /// Returns the unit quaternion that has the same direction as this quaternion
+    pub fn normalize(self) -> Self
+    where
+        T: ops::Div<T, Output = T> + Sqrt,
+    {
+        let n = self.norm();
+        Quaternion::new(self.w / n, self.x / n, self.y / n, self.z / n)
+    }
+
+    /// Returns the dot product of two quaternions seen as 4-vectors
+    pub fn dot(self, other: Self) -> T {
+        self.w * other.w + self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    /// Normalized linear interpolation between the unit quaternions `self` (`t = 0`) and `other`
+    /// (`t = 1`)
+    ///
+    /// This is cheaper than `slerp` and follows the same path but does not move at a constant
+    /// angular velocity. Interpolation takes the shortest path between the two rotations.
+    pub fn nlerp(self, other: Self, t: T) -> Self
+    where
+        T: ops::Div<T, Output = T> + PartialOrd + Sqrt,
+    {
+        let other = self.nearest(other);
+        self.weighted_sum(T::from(1) - t, other, t).normalize()
+    }
+
+    /// Spherical linear interpolation between the unit quaternions `self` (`t = 0`) and `other`
+    /// (`t = 1`)
+    ///
+    /// The interpolated rotation moves at a constant angular velocity along the shortest path
+    /// between the two rotations. Falls back to `nlerp` when the rotations are too close for the
+    /// interpolation weights to be computed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::f64::consts::FRAC_PI_2;
+    ///
+    /// use mat::geometry::Quaternion;
+    ///
+    /// let z = mat::vec3(0., 0., 1.);
+    /// let a = Quaternion::identity();
+    /// let b = Quaternion::from_axis_angle(&z, FRAC_PI_2);
+    ///
+    /// let q = a.slerp(b, 1. / 3.);
+    /// let expected = Quaternion::from_axis_angle(&z, FRAC_PI_2 / 3.);
+    /// assert!((q.dot(expected) - 1.).abs() < 1e-12);
+    /// ```
+    pub fn slerp(self, other: Self, t: T) -> Self
+    where
+        T: ops::Div<T, Output = T> + PartialOrd + Sqrt + Trig,
+    {
+        let other = self.nearest(other);
+        let one = T::from(1);
+
+        let cos = self.dot(other);
+        if cos >= one {
+            return self.nlerp(other, t);
+        }
+
+        let sin = (one - cos * cos).sqrt();
+        if sin == T::zero() {
+            return self.nlerp(other, t);
+        }
+
+        let theta = sin.atan2(cos);
+        let a = ((one - t) * theta).sin() / sin;
+        let b = (t * theta).sin() / sin;
+        self.weighted_sum(a, other, b)
+    }
+
+    /// Rotates the 3-vector `v` by this unit quaternion
+    pub fn rotate<V>(self, v: V) -> Vec3<T>
+    where
+        V: Matrix<Elem = T, NROWS = U3, NCOLS = U1>,
+    {
+        let (vx, vy, vz) = (v.get(0, 0), v.get(1, 0), v.get(2, 0));
+        let two = T::from(2);
+
+        // t = 2 q_v × v; v' = v + w t + q_v × t
+        let tx = two * (self.y * vz - self.z * vy);
+        let ty = two * (self.z * vx - self.x * vz);
+        let tz = two * (self.x * vy - self.y * vx);
+
+        ::vec3(
+            vx + self.w * tx + (self.y * tz - self.z * ty),
+            vy + self.w * ty + (self.z * tx - self.x * tz),
+            vz + self.w * tz + (self.x * ty - self.y * tx),
+        )
+    }
+
+    // returns `other` or `-other`, whichever is closer to `self`
+    fn nearest(self, other: Self) -> Self
+    where
+        T: PartialOrd,
+    {
+        if self.dot(other) < T::zero() {
+            Quaternion::new(
+                T::zero() - other.w,
+                T::zero() - other.x,
+                T::zero() - other.y,
+                T::zero() - other.z,
+            )
+        } else {
+            other
+        }
+    }
+
+    // `a self + b other`
+    fn weighted_sum(self, a: T, other: Self, b: T) -> Self {
+        Quaternion::new(
+            a * self.w + b * other.w,
+            a * self.x + b * other.x,
+            a * self.y + b * other.y,
+            a * self.z + b * other.z,
+        )
+    }
+
+    /// Returns the rotation matrix that represents the same rotation as this unit quaternion
+    pub fn to_matrix(self) -> Mat3<T> {
+        let Quaternion { w, x, y, z } = self;
+        let (one, two) = (T::from(1), T::from(2));
+
+        ::mat3([
+            [
+                one - two * (y * y + z * z),
+                two * (x * y - w * z),
+                two * (x * z + w * y),
+            ],
+            [
+                two * (x * y + w * z),
+                one - two * (x * x + z * z),
+                two * (y * z - w * x),
+            ],
+            [
+                two * (x * z - w * y),
+                two * (y * z + w * x),
+                one - two * (x * x + y * y),
+            ],
+        ])
+    }
+}
+
+/// Hamilton product; `p * q` rotates by `q` first and then by `p`
+impl<T> ops::Mul for Quaternion<T>
+where
+    T: ops::Add<T, Output = T> + ops::Mul<T, Output = T> + ops::Sub<T, Output = T> + Copy,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        let (a, b) = (self, rhs);
+        Quaternion {
+            w: a.w * b.w - a.x * b.x - a.y * b.y - a.z * b.z,
+            x: a.w * b.x + a.x * b.w + a.y * b.z - a.z * b.y,
+            y: a.w * b.y - a.x * b.z + a.y * b.w + a.z * b.x,
+            z: a.w * b.z + a.x * b.y - a.y * b.x + a.z * b.w,
+        }
+    }
+}
+
+/// Returns the angle, in radians, between the vectors `a` and `b`
+///
+/// The cosine of the angle is clamped into `[-1, 1]` before calling `Trig::acos` so rounding errors
+/// in nearly (anti)parallel vectors can't produce `NaN`. Returns `None` if either vector is zero.
+///
+/// # Example
+///
+/// ```
+/// use std::f64::consts::FRAC_PI_4;
+///
+/// use mat::geometry;
+///
+/// let a = mat::vec3(1., 0., 0.);
+/// let b = mat::vec3(1., 1., 0.);
+/// assert!((geometry::angle_between(&a, &b).unwrap() - FRAC_PI_4).abs() < 1e-12);
+///
+/// assert_eq!(geometry::angle_between(&a, &a), Some(0.));
+/// assert!(geometry::angle_between(&a, &mat::vec3(0., 0., 0.)).is_none());
+/// ```
+pub fn angle_between<A, B, T>(a: A, b: B) -> Option<T>
+where
+    A: Matrix<Elem = T>,
+    B: Matrix<Elem = T, NROWS = A::NROWS, NCOLS = A::NCOLS>,
+    A::NROWS: Min<A::NCOLS, Output = U1>,
+    T: ops::Add<T, Output = T>
+        + ops::Div<T, Output = T>
+        + ops::Mul<T, Output = T>
+        + ops::Sub<T, Output = T>
+        + Copy
+        + From<u8>
+        + PartialOrd
+        + Sqrt
+        + Trig
+        + Zero,
+{
+    let norms = a.norm() * b.norm();
+    if norms == T::zero() {
+        return None;
+    }
+
+    Some(clamp_unit(a.dot(b) / norms).acos())
+}
+
+/// Returns the cross product of two 3-vectors
+///
+/// Both operands must be column vectors or both must be row vectors.
+pub fn cross<A, B, T, NROWS, NCOLS>(a: A, b: B) -> MatGen<T, NROWS, NCOLS>
+where
+    A: Matrix<Elem = T, NROWS = NROWS, NCOLS = NCOLS>,
+    B: Matrix<Elem = T, NROWS = NROWS, NCOLS = NCOLS>,
+    NROWS: Unsigned + Min<NCOLS, Output = U1> + ops::Mul<NCOLS, Output = U3>,
+    NCOLS: Unsigned,
+    Prod<NROWS, NCOLS>: ArrayLength<T>,
+    T: ops::Mul<T, Output = T> + ops::Sub<T, Output = T> + Copy,
+{
+    let (a0, a1, a2) = (elem(a, 0), elem(a, 1), elem(a, 2));
+    let (b0, b1, b2) = (elem(b, 0), elem(b, 1), elem(b, 2));
+    let c = [a1 * b2 - a2 * b1, a2 * b0 - a0 * b2, a0 * b1 - a1 * b0];
+
+    MatGen::from_fn(|r, col| c[r + col])
+}
+
+/// Returns the matrix that rotates 2D vectors counterclockwise by `theta` radians
+pub fn rotation2<T>(theta: T) -> Mat2<T>
+where
+    T: ops::Sub<T, Output = T> + Copy + Trig + Zero,
+{
+    let (s, c) = (theta.sin(), theta.cos());
+    ::mat2([[c, T::zero() - s], [s, c]])
+}
+
+/// Returns the matrix that rotates 3D vectors by `theta` radians around the X axis
+pub fn rotation3_x<T>(theta: T) -> Mat3<T>
+where
+    T: ops::Sub<T, Output = T> + Copy + From<u8> + Trig + Zero,
+{
+    let (s, c) = (theta.sin(), theta.cos());
+    let (o, z) = (T::from(1), T::zero());
+    ::mat3([[o, z, z], [z, c, z - s], [z, s, c]])
+}
+
+/// Returns the matrix that rotates 3D vectors by `theta` radians around the Y axis
+pub fn rotation3_y<T>(theta: T) -> Mat3<T>
+where
+    T: ops::Sub<T, Output = T> + Copy + From<u8> + Trig + Zero,
+{
+    let (s, c) = (theta.sin(), theta.cos());
+    let (o, z) = (T::from(1), T::zero());
+    ::mat3([[c, z, s], [z, o, z], [z - s, z, c]])
+}
+
+/// Returns the matrix that rotates 3D vectors by `theta` radians around the Z axis
+pub fn rotation3_z<T>(theta: T) -> Mat3<T>
+where
+    T: ops::Sub<T, Output = T> + Copy + From<u8> + Trig + Zero,
+{
+    let (s, c) = (theta.sin(), theta.cos());
+    let (o, z) = (T::from(1), T::zero());
+    ::mat3([[c, z - s, z], [s, c, z], [z, z, o]])
+}
+
+/// Returns the matrix that rotates 3D vectors by `angle` radians around `axis`
+///
+/// `axis` must be a unit vector. The matrix is computed with Rodrigues' rotation formula.
+pub fn rotation3_axis_angle<A, T>(axis: A, angle: T) -> Mat3<T>
+where
+    A: Matrix<Elem = T, NROWS = U3, NCOLS = U1>,
+    T: ops::Add<T, Output = T>
+        + ops::Mul<T, Output = T>
+        + ops::Sub<T, Output = T>
+        + Copy
+        + From<u8>
+        + Trig
+        + Zero,
+{
+    let (s, c) = (angle.sin(), angle.cos());
+    let t = T::from(1) - c;
+    let k = [axis.get(0, 0), axis.get(1, 0), axis.get(2, 0)];
+
+    // c I + s [k]ₓ + (1 - c) k kᵀ
+    let skew = hat(axis);
+    MatGen::from_fn(|r, col| {
+        let diag = if r == col { c } else { T::zero() };
+        diag + s * skew.get(r, col) + t * k[r] * k[col]
+    })
+}
+
+/// Performs one re-orthonormalization step on the nearly orthogonal matrix `m`
+///
+/// This corrects the numerical drift that accumulates when a rotation matrix is integrated over
+/// time. The orthogonality error of the first two rows is split evenly between them, the third row
+/// is rebuilt as their cross product and each row is then normalized using a first order Taylor
+/// expansion, which avoids square roots. Calling this once per integration step keeps `m` close to
+/// a rotation matrix.
+///
+/// # Example
+///
+/// ```
+/// use mat::geometry;
+/// use mat::traits::Matrix;
+///
+/// let r = geometry::rotation3_z(0.5);
+/// let drifted = mat::mat3([
+///     [r.get(0, 0) * 1.001, r.get(0, 1), r.get(0, 2) + 1e-3],
+///     [r.get(1, 0), r.get(1, 1), r.get(1, 2)],
+///     [r.get(2, 0), r.get(2, 1), r.get(2, 2)],
+/// ]);
+///
+/// let fixed = geometry::renormalize(&drifted);
+///
+/// // Rᵀ R ≈ I
+/// for i in 0..3 {
+///     for j in 0..3 {
+///         let mut dot = 0.;
+///         for k in 0..3 {
+///             dot += fixed.get(k, i) * fixed.get(k, j);
+///         }
+///         let expected: f64 = if i == j { 1. } else { 0. };
+///         assert!((dot - expected).abs() < 1e-5);
+///     }
+/// }
+/// ```
+pub fn renormalize<M, T>(m: M) -> Mat3<T>
+where
+    M: Matrix<Elem = T, NROWS = U3, NCOLS = U3>,
+    T: ops::Add<T, Output = T>
+        + ops::Div<T, Output = T>
+        + ops::Mul<T, Output = T>
+        + ops::Sub<T, Output = T>
+        + Copy
+        + From<u8>,
+{
+    let row = |r| [m.get(r, 0), m.get(r, 1), m.get(r, 2)];
+    let dot = |a: [T; 3], b: [T; 3]| a[0] * b[0] + a[1] * b[1] + a[2] * b[2];
+    let (x, y) = (row(0), row(1));
+
+    let half_error = dot(x, y) / T::from(2);
+    let (x, y) = (
+        [
+            x[0] - half_error * y[0],
+            x[1] - half_error * y[1],
+            x[2] - half_error * y[2],
+        ],
+        [
+            y[0] - half_error * x[0],
+            y[1] - half_error * x[1],
+            y[2] - half_error * x[2],
+        ],
+    );
+    let z = [
+        x[1] * y[2] - x[2] * y[1],
+        x[2] * y[0] - x[0] * y[2],
+        x[0] * y[1] - x[1] * y[0],
+    ];
+
+    // 1 / |v| ≈ (3 - v · v) / 2 when |v| ≈ 1
+    let rows = [x, y, z];
+    MatGen::from_fn(|r, c| {
+        let v = rows[r];
+        v[c] * (T::from(3) - dot(v, v)) / T::from(2)
+    })
+}
+
+/// Returns the skew-symmetric matrix `[v]ₓ` such that `[v]ₓ w = v × w`
+///
+/// # Example
+///
+/// ```
+/// use mat::geometry;
+///
+/// let v = mat::vec3(1, 2, 3);
+/// let w = mat::vec3(4, 5, 6);
+///
+/// let vx = geometry::hat(&v);
+/// let lhs = (&vx * &w).eval();
+/// let rhs = geometry::cross(&v, &w);
+/// assert_eq!((lhs[0], lhs[1], lhs[2]), (rhs[0], rhs[1], rhs[2]));
+///
+/// let v2 = geometry::vee(&vx);
+/// assert_eq!((v2[0], v2[1], v2[2]), (1, 2, 3));
+/// ```
+pub fn hat<V, T>(v: V) -> Mat3<T>
+where
+    V: Matrix<Elem = T, NROWS = U3, NCOLS = U1>,
+    T: ops::Sub<T, Output = T> + Copy + Zero,
+{
+    let z = T::zero();
+    let (x, y, w) = (v.get(0, 0), v.get(1, 0), v.get(2, 0));
+    let rows = [[z, z - w, y], [w, z, z - x], [z - y, x, z]];
+    MatGen::from_fn(|r, c| rows[r][c])
+}
+
+/// Returns the vector `v` of the skew-symmetric matrix `m = [v]ₓ`; this is the inverse of `hat`
+///
+/// Only the elements `m[2][1]`, `m[0][2]` and `m[1][0]` are read.
+pub fn vee<M, T>(m: M) -> Vec3<T>
+where
+    M: Matrix<Elem = T, NROWS = U3, NCOLS = U3>,
+    T: Copy,
+{
+    let v = [m.get(2, 1), m.get(0, 2), m.get(1, 0)];
+    MatGen::from_fn(|r, _| v[r])
+}
+
+/// Returns the rotation matrix of the Euler `angles`
+pub fn from_euler<T>(seq: EulerSequence, angles: [T; 3]) -> Mat3<T>
+where
+    T: ops::Add<T, Output = T>
+        + ops::Mul<T, Output = T>
+        + ops::Sub<T, Output = T>
+        + Copy
+        + From<u8>
+        + Trig
+        + Zero,
+{
+    let [a, b, c] = angles;
+    match seq {
+        EulerSequence::Zyx => {
+            let rzy = (&rotation3_z(a) * &rotation3_y(b)).eval();
+            (&rzy * &rotation3_x(c)).eval()
+        }
+        EulerSequence::Xyz => {
+            let rxy = (&rotation3_x(a) * &rotation3_y(b)).eval();
+            (&rxy * &rotation3_z(c)).eval()
+        }
+    }
+}
+
+/// Returns the Euler angles of the rotation matrix `m`
+///
+/// See `EulerSequence` for the handling of gimbal lock.
+pub fn to_euler<M, T>(seq: EulerSequence, m: M) -> [T; 3]
+where
+    M: Matrix<Elem = T, NROWS = U3, NCOLS = U3>,
+    T: ops::Add<T, Output = T>
+        + ops::Div<T, Output = T>
+        + ops::Mul<T, Output = T>
+        + ops::Sub<T, Output = T>
+        + Copy
+        + From<u8>
+        + PartialOrd
+        + Trig
+        + Zero,
+{
+    let z = T::zero();
+    match seq {
+        EulerSequence::Zyx => {
+            // m[2][0] = -sin(b); m[0][0] = cos(a) cos(b) and m[1][0] = sin(a) cos(b)
+            let b = clamp_unit(z - m.get(2, 0)).asin();
+            if gimbal_locked(m.get(0, 0), m.get(1, 0)) {
+                [(z - m.get(0, 1)).atan2(m.get(1, 1)), b, z]
+            } else {
+                [
+                    m.get(1, 0).atan2(m.get(0, 0)),
+                    b,
+                    m.get(2, 1).atan2(m.get(2, 2)),
+                ]
+            }
+        }
+        EulerSequence::Xyz => {
+            // m[0][2] = sin(b); m[0][0] = cos(b) cos(c) and m[0][1] = -cos(b) sin(c)
+            let b = clamp_unit(m.get(0, 2)).asin();
+            if gimbal_locked(m.get(0, 0), m.get(0, 1)) {
+                [m.get(2, 1).atan2(m.get(1, 1)), b, z]
+            } else {
+                [
+                    (z - m.get(1, 2)).atan2(m.get(2, 2)),
+                    b,
+                    (z - m.get(0, 1)).atan2(m.get(0, 0)),
+                ]
+            }
+        }
+    }
+}
+
+/// Returns the homogeneous transform that translates points by `v`
+pub fn translation<V, T>(v: V) -> Mat4<T>
+where
+    V: Matrix<Elem = T, NROWS = U3, NCOLS = U1>,
+    T: Copy + From<u8> + Zero,
+{
+    MatGen::from_fn(|r, c| {
+        if r == c {
+            T::from(1)
+        } else if c == 3 {
+            v.get(r, 0)
+        } else {
+            T::zero()
+        }
+    })
+}
+
+/// Returns the homogeneous transform that scales each axis by the corresponding element of `v`
+pub fn scaling<V, T>(v: V) -> Mat4<T>
+where
+    V: Matrix<Elem = T, NROWS = U3, NCOLS = U1>,
+    T: Copy + From<u8> + Zero,
+{
+    MatGen::from_fn(|r, c| {
+        if r != c {
+            T::zero()
+        } else if r == 3 {
+            T::from(1)
+        } else {
+            v.get(r, 0)
+        }
+    })
+}
+
+/// Returns the homogeneous transform that rotates by `r` and then translates by `t`
+///
+/// `r` should be a rotation matrix but this is not checked.
+pub fn rigid_transform<R, V, T>(r: R, t: V) -> Mat4<T>
+where
+    R: Matrix<Elem = T, NROWS = U3, NCOLS = U3>,
+    V: Matrix<Elem = T, NROWS = U3, NCOLS = U1>,
+    T: Copy + From<u8> + Zero,
+{
+    MatGen::from_fn(|row, c| match (row, c) {
+        (3, 3) => T::from(1),
+        (3, _) => T::zero(),
+        (_, 3) => t.get(row, 0),
+        _ => r.get(row, c),
+    })
+}
+
+/// Composes two transforms, i.e. returns `a b`
+///
+/// The result applies `b` first and then `a`.
+pub fn compose<A, B, T, N>(a: A, b: B) -> MatGen<T, N, N>
+where
+    A: Matrix<Elem = T, NROWS = N, NCOLS = N>,
+    B: Matrix<Elem = T, NROWS = N, NCOLS = N>,
+    N: Unsigned + ops::Mul<N>,
+    Prod<N, N>: ArrayLength<T>,
+    T: ops::Add<T, Output = T> + ops::Mul<T, Output = T> + Copy + Zero,
+{
+    Product { l: a, r: b }.eval()
+}
+
+/// Composes a chain of transforms, i.e. returns `transforms[0] transforms[1] ...`
+///
+/// Returns the identity if `transforms` is empty.
+pub fn compose_all<T, N>(transforms: &[MatGen<T, N, N>]) -> MatGen<T, N, N>
+where
+    N: Unsigned + ops::Mul<N>,
+    Prod<N, N>: ArrayLength<T>,
+    T: ops::Add<T, Output = T> + ops::Mul<T, Output = T> + Copy + From<u8> + Zero,
+{
+    let identity = MatGen::from_fn(|r, c| if r == c { T::from(1) } else { T::zero() });
+    transforms.iter().fold(identity, |acc, t| compose(&acc, t))
+}
+
+/// Returns the view matrix of a camera at `eye` looking at `target`
+///
+/// This follows the OpenGL convention: the camera looks down its negative Z axis and `up` is
+/// projected onto its Y axis. `up` must not be parallel to the viewing direction.
+pub fn look_at<E, G, U, T>(eye: E, target: G, up: U) -> Mat4<T>
+where
+    E: Matrix<Elem = T, NROWS = U3, NCOLS = U1>,
+    G: Matrix<Elem = T, NROWS = U3, NCOLS = U1>,
+    U: Matrix<Elem = T, NROWS = U3, NCOLS = U1>,
+    T: ops::Add<T, Output = T>
+        + ops::Div<T, Output = T>
+        + ops::Mul<T, Output = T>
+        + ops::Sub<T, Output = T>
+        + Copy
+        + From<u8>
+        + Sqrt
+        + Zero,
+{
+    let f = Vec3::from_fn(|r, _| target.get(r, 0) - eye.get(r, 0)).normalize();
+    let s = cross(&f, up).normalize();
+    let u = cross(&s, &f);
+
+    let z = T::zero();
+    let rows = [
+        [s[0], s[1], s[2], z - s.dot(eye)],
+        [u[0], u[1], u[2], z - u.dot(eye)],
+        [z - f[0], z - f[1], z - f[2], f.dot(eye)],
+        [z, z, z, T::from(1)],
+    ];
+    MatGen::from_fn(|r, c| rows[r][c])
+}
+
+/// Returns a perspective projection matrix
+///
+/// `fovy` is the vertical field of view (in radians), `aspect` the width to height ratio of the
+/// viewport and `near` and `far` the (positive) distances to the clipping planes. This follows the
+/// OpenGL convention: the view volume is mapped to the cube `[-1, 1]³`.
+///
+/// # Example
+///
+/// ```
+/// use std::f64::consts::FRAC_PI_2;
+///
+/// use mat::geometry;
+///
+/// let view = geometry::look_at(
+///     &mat::vec3(0., 0., 5.),
+///     &mat::vec3(0., 0., 0.),
+///     &mat::vec3(0., 1., 0.),
+/// );
+/// let proj = geometry::perspective(FRAC_PI_2, 1., 1., 9.);
+///
+/// // the origin is 5 units in front of the camera, on the optical axis
+/// let p = geometry::compose(&proj, &view).transform_point(&mat::vec3(0., 0., 0.));
+/// assert!(p[0].abs() < 1e-12 && p[1].abs() < 1e-12);
+/// assert!((p[2] - 0.8).abs() < 1e-12);
+/// ```
+pub fn perspective<T>(fovy: T, aspect: T, near: T, far: T) -> Mat4<T>
+where
+    T: ops::Add<T, Output = T>
+        + ops::Div<T, Output = T>
+        + ops::Mul<T, Output = T>
+        + ops::Sub<T, Output = T>
+        + Copy
+        + From<u8>
+        + Trig
+        + Zero,
+{
+    let (z, one, two) = (T::zero(), T::from(1), T::from(2));
+    let f = one / (fovy / two).tan();
+    let depth = near - far;
+
+    let rows = [
+        [f / aspect, z, z, z],
+        [z, f, z, z],
+        [z, z, (far + near) / depth, two * far * near / depth],
+        [z, z, z - one, z],
+    ];
+    MatGen::from_fn(|r, c| rows[r][c])
+}
+
+/// Returns an orthographic projection matrix
+///
+/// The box bounded by the `left`, `right`, `bottom` and `top` planes and by the clipping planes at
+/// distances `near` and `far` is mapped to the cube `[-1, 1]³`, following the OpenGL convention.
+pub fn orthographic<T>(left: T, right: T, bottom: T, top: T, near: T, far: T) -> Mat4<T>
+where
+    T: ops::Add<T, Output = T>
+        + ops::Div<T, Output = T>
+        + ops::Sub<T, Output = T>
+        + Copy
+        + From<u8>
+        + Zero,
+{
+    let (z, two) = (T::zero(), T::from(2));
+    let (w, h, d) = (right - left, top - bottom, far - near);
+
+    let rows = [
+        [two / w, z, z, z - (right + left) / w],
+        [z, two / h, z, z - (top + bottom) / h],
+        [z, z, z - two / d, z - (far + near) / d],
+        [z, z, z, T::from(1)],
+    ];
+    MatGen::from_fn(|r, c| rows[r][c])
+}
+
+/// Divides the homogeneous vector `v` by its last element
+///
+/// The last element of the result is `1`. Returns `None` if the magnitude of the last element of
+/// `v` is not greater than `eps`, i.e. if `v` is (close to) a point at infinity. Empty vectors
+/// have no last element and are rejected at compile time.
+///
+/// # Example
+///
+/// ```
+/// use mat::{geometry, mat};
+/// use mat::traits::Matrix;
+///
+/// let p = geometry::homogeneous_normalize(&mat::vec3(4., 6., 2.), 1e-9).unwrap();
+/// assert_eq!((p[0], p[1], p[2]), (2., 3., 1.));
+///
+/// assert!(geometry::homogeneous_normalize(&mat::vec3(4., 6., 0.), 1e-9).is_none());
+///
+/// // batched version: one point per column
+/// let mut points = mat![
+///     [2., 1., 9.],
+///     [4., 3., 9.],
+///     [2., 1., 0.],
+/// ].eval();
+/// assert_eq!(geometry::homogeneous_normalize_cols(&mut points, 1e-9), 1);
+/// assert_eq!((points.get(0, 0), points.get(1, 0), points.get(2, 0)), (1., 2., 1.));
+/// assert_eq!((points.get(0, 1), points.get(1, 1), points.get(2, 1)), (1., 3., 1.));
+/// assert_eq!(points.get(2, 2), 0.);
+/// ```
+pub fn homogeneous_normalize<V, T, N>(v: V, eps: T) -> Option<Vector<T, N>>
+where
+    V: Matrix<Elem = T, NROWS = N, NCOLS = U1>,
+    N: NonZero + Unsigned + ops::Mul<U1>,
+    Prod<N, U1>: ArrayLength<T>,
+    T: ops::Div<T, Output = T> + ops::Sub<T, Output = T> + Copy + PartialOrd + Zero,
+{
+    let w = v.get(N::to_usize() - 1, 0);
+    if abs(w) <= eps {
+        return None;
+    }
+
+    Some(MatGen::from_fn(|r, _| v.get(r, 0) / w))
+}
+
+/// Divides each column of `m` by its last element, in place
+///
+/// Columns whose last element has a magnitude not greater than `eps` are left untouched so they
+/// can still be identified as points at infinity. Returns the number of such columns.
+///
+/// Like `homogeneous_normalize`, `m` must have at least one row, which is checked at compile time.
+pub fn homogeneous_normalize_cols<T, N, C>(m: &mut MatGen<T, N, C>, eps: T) -> usize
+where
+    N: NonZero + Unsigned + ops::Mul<C>,
+    C: Unsigned,
+    Prod<N, C>: ArrayLength<T>,
+    T: ops::Div<T, Output = T> + ops::Sub<T, Output = T> + Copy + PartialOrd + Zero,
+{
+    let (n, ncols) = (N::to_usize(), C::to_usize());
+    let mut skipped = 0;
+
+    for c in 0..ncols {
+        let w = m.buffer[(n - 1) * ncols + c];
+        if abs(w) <= eps {
+            skipped += 1;
+            continue;
+        }
+
+        for r in 0..n {
+            m.buffer[r * ncols + c] = m.buffer[r * ncols + c] / w;
+        }
+    }
+
+    skipped
+}
+
+impl<T> Mat4<T>
+where
+    T: ops::Add<T, Output = T>
+        + ops::Div<T, Output = T>
+        + ops::Mul<T, Output = T>
+        + Copy
+        + Zero,
+{
+    /// Applies this homogeneous transform to the point `p`, i.e. to `[p, 1]`
+    ///
+    /// The result is divided by its homogeneous coordinate so this also works with projective
+    /// transforms.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mat::geometry;
+    ///
+    /// let t = geometry::translation(&mat::vec3(1., 2., 3.));
+    ///
+    /// let p = t.transform_point(&mat::vec3(1., 1., 1.));
+    /// assert_eq!((p[0], p[1], p[2]), (2., 3., 4.));
+    ///
+    /// // directions are not affected by translations
+    /// let v = t.transform_vector(&mat::vec3(1., 1., 1.));
+    /// assert_eq!((v[0], v[1], v[2]), (1., 1., 1.));
+    /// ```
+    pub fn transform_point<P>(&self, p: P) -> Vec3<T>
+    where
+        P: Matrix<Elem = T, NROWS = U3, NCOLS = U1>,
+    {
+        let w = self.affine_row(3, p);
+        MatGen::from_fn(|r, _| self.affine_row(r, p) / w)
+    }
+
+    /// Applies this homogeneous transform to the direction `v`, i.e. to `[v, 0]`
+    pub fn transform_vector<V>(&self, v: V) -> Vec3<T>
+    where
+        V: Matrix<Elem = T, NROWS = U3, NCOLS = U1>,
+    {
+        MatGen::from_fn(|r, _| self.linear_row(r, v))
+    }
+
+    // `r`-th element of `self * [p, 1]`
+    fn affine_row<P>(&self, r: usize, p: P) -> T
+    where
+        P: Matrix<Elem = T, NROWS = U3, NCOLS = U1>,
+    {
+        self.linear_row(r, p) + self.get(r, 3)
+    }
+
+    // `r`-th element of `self * [v, 0]`
+    fn linear_row<V>(&self, r: usize, v: V) -> T
+    where
+        V: Matrix<Elem = T, NROWS = U3, NCOLS = U1>,
+    {
+        (0..3).fold(T::zero(), |acc, c| acc + self.get(r, c) * v.get(c, 0))
+    }
+}
+
+impl<T> Mat3<T>
+where
+    T: ops::Add<T, Output = T>
+        + ops::Div<T, Output = T>
+        + ops::Mul<T, Output = T>
+        + Copy
+        + Zero,
+{
+    /// Applies this 2D homogeneous transform to the point `p`, i.e. to `[p, 1]`
+    ///
+    /// The result is divided by its homogeneous coordinate so this also works with projective
+    /// transforms.
+    pub fn transform_point<P>(&self, p: P) -> Vec2<T>
+    where
+        P: Matrix<Elem = T, NROWS = U2, NCOLS = U1>,
+    {
+        let w = self.affine_row(2, p);
+        MatGen::from_fn(|r, _| self.affine_row(r, p) / w)
+    }
+
+    /// Applies this 2D homogeneous transform to the direction `v`, i.e. to `[v, 0]`
+    pub fn transform_vector<V>(&self, v: V) -> Vec2<T>
+    where
+        V: Matrix<Elem = T, NROWS = U2, NCOLS = U1>,
+    {
+        MatGen::from_fn(|r, _| self.linear_row(r, v))
+    }
+
+    // `r`-th element of `self * [p, 1]`
+    fn affine_row<P>(&self, r: usize, p: P) -> T
+    where
+        P: Matrix<Elem = T, NROWS = U2, NCOLS = U1>,
+    {
+        self.linear_row(r, p) + self.get(r, 2)
+    }
+
+    // `r`-th element of `self * [v, 0]`
+    fn linear_row<V>(&self, r: usize, v: V) -> T
+    where
+        V: Matrix<Elem = T, NROWS = U2, NCOLS = U1>,
+    {
+        (0..2).fold(T::zero(), |acc, c| acc + self.get(r, c) * v.get(c, 0))
+    }
+}
+
+/// Whether `cos(b)`, whose products with the cosine and sine of another angle are `x` and `y`, is
+/// small enough to consider the rotation to be at gimbal lock; see `EulerSequence`
+fn gimbal_locked<T>(x: T, y: T) -> bool
+where
+    T: ops::Add<T, Output = T>
+        + ops::Div<T, Output = T>
+        + ops::Mul<T, Output = T>
+        + Copy
+        + From<u8>
+        + PartialOrd,
+{
+    // 1e-6
+    let eps = T::from(1) / (T::from(250) * T::from(250) * T::from(16));
+    x * x + y * y <= eps * eps
+}
+
+// clamps `x` into the range `[-1, 1]` to protect `asin` against rounding errors
+fn clamp_unit<T>(x: T) -> T
+where
+    T: ops::Sub<T, Output = T> + Copy + From<u8> + PartialOrd + Zero,
+{
+    let one = T::from(1);
+    if x > one {
+        one
+    } else if x < T::zero() - one {
+        T::zero() - one
+    } else {
+        x
+    }
+}
+
+// `i`-th element of a row or column vector
+fn elem<V>(v: V, i: usize) -> V::Elem
+where
+    V: Matrix,
+{
+    if v.nrows() == 1 {
+        v.get(0, i)
+    } else {
+        v.get(i, 0)
+    }
+}
@@ -0,0 +1,279 @@
+//! Matrices over GF(2), the field of two elements
+//!
+//! In GF(2) addition is exclusive or and multiplication is logical and, so the product of two
+//! matrices accumulates the AND of each row-column pair with XOR. This is the algebra of parity
+//! checks and linear error-correcting codes.
+//!
+//! `Gf2` can be used as the element type of any matrix. `BitMat` stores each row as the bits of a
+//! `u32`, using 32 times less memory than a matrix of `Gf2`, and multiplies bit-packed vectors with
+//! a few bitwise operations per row.
+//!
+//! # Example
+//!
+//! Syndrome decoding with the parity check matrix of the Hamming(7, 4) code
+//!
+//! ```
+//! extern crate mat;
+//! extern crate typenum;
+//!
+//! use mat::Vector;
+//! use mat::gf2::{BitMat, Gf2};
+//! use typenum::{U3, U7};
+//!
+//! # fn main() {
+//! // column `c` is the binary representation of `c + 1`
+//! let h = BitMat::<U3, U7>::from_fn(|r, c| (c + 1) >> r & 1 == 1);
+//!
+//! // a valid codeword has a zero syndrome
+//! let codeword = 0b101_0101;
+//! assert_eq!(h.mul_packed(codeword), 0);
+//!
+//! // flipping bit 4 gives the syndrome `4 + 1`
+//! let received = codeword ^ 1 << 4;
+//! assert_eq!(h.mul_packed(received), 5);
+//!
+//! // the same computation with the lazy expression system
+//! let v = Vector::<_, U7>::from_fn(|r, _| Gf2(received >> r & 1 == 1));
+//! let s = (&h * &v).eval();
+//! assert_eq!((s[0], s[1], s[2]), (Gf2(true), Gf2(false), Gf2(true)));
+//! # }
+//! ```
+
+use core::marker::PhantomData;
+use core::ops;
+
+use generic_array::{ArrayLength, GenericArray};
+use typenum::{IsLessOrEqual, True, U32, Unsigned};
+
+use {array, Product};
+use traits::{Conj, Matrix, One, UnsafeGet, Zero};
+
+/// An element of GF(2)
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Gf2(pub bool);
+
+impl Conj for Gf2 {
+    fn conj(self) -> Self {
+        self
+    }
+}
+
+impl Zero for Gf2 {
+    fn zero() -> Self {
+        Gf2(false)
+    }
+}
+
+impl One for Gf2 {
+    fn one() -> Self {
+        Gf2(true)
+    }
+}
+
+/// Reduces `x` modulo 2
+impl From<u8> for Gf2 {
+    fn from(x: u8) -> Self {
+        Gf2(x & 1 == 1)
+    }
+}
+
+impl From<bool> for Gf2 {
+    fn from(x: bool) -> Self {
+        Gf2(x)
+    }
+}
+
+/// Exclusive or
+impl ops::Add for Gf2 {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Gf2(self.0 != rhs.0)
+    }
+}
+
+/// Exclusive or; every element of GF(2) is its own additive inverse
+impl ops::Sub for Gf2 {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Gf2(self.0 != rhs.0)
+    }
+}
+
+/// Logical and
+impl ops::Mul for Gf2 {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Gf2(self.0 && rhs.0)
+    }
+}
+
+/// Bit-packed matrix over GF(2) with up to 32 columns
+///
+/// Bit `c` of the `u32` that stores row `r` is the element at row `r` and column `c`.
+pub struct BitMat<NROWS, NCOLS>
+where
+    NROWS: ArrayLength<u32>,
+    NCOLS: IsLessOrEqual<U32, Output = True> + Unsigned,
+{
+    rows: GenericArray<u32, NROWS>,
+    ncols: PhantomData<NCOLS>,
+}
+
+impl<NROWS, NCOLS> Clone for BitMat<NROWS, NCOLS>
+where
+    NROWS: ArrayLength<u32>,
+    NCOLS: IsLessOrEqual<U32, Output = True> + Unsigned,
+{
+    fn clone(&self) -> Self {
+        let rows = array::as_slice(&self.rows);
+        BitMat {
+            rows: array::generate(|r| rows[r]),
+            ncols: PhantomData,
+        }
+    }
+}
+
+impl<NROWS, NCOLS> BitMat<NROWS, NCOLS>
+where
+    NROWS: ArrayLength<u32>,
+    NCOLS: IsLessOrEqual<U32, Output = True> + Unsigned,
+{
+    /// Creates a matrix where the element at row `r` and column `c` is `f(r, c)`
+    pub fn from_fn<F>(f: F) -> Self
+    where
+        F: Fn(usize, usize) -> bool,
+    {
+        BitMat {
+            rows: array::generate(|r| {
+                (0..NCOLS::to_usize()).fold(0, |row, c| row | (f(r, c) as u32) << c)
+            }),
+            ncols: PhantomData,
+        }
+    }
+
+    /// Creates a matrix filled with zeros
+    pub fn zeros() -> Self {
+        BitMat {
+            rows: array::generate(|_| 0),
+            ncols: PhantomData,
+        }
+    }
+
+    /// Returns row `r` packed into the low bits of a `u32`
+    ///
+    /// # Panics
+    ///
+    /// This operation panics if `r` exceeds the number of rows
+    pub fn row(&self, r: usize) -> u32 {
+        array::as_slice(&self.rows)[r]
+    }
+
+    /// Returns row `r` packed into the low bits of a `u32`, or `None` if `r` exceeds the number of
+    /// rows
+    pub fn row_checked(&self, r: usize) -> Option<u32> {
+        no_panic!(array::as_slice(&self.rows).get(r).cloned())
+    }
+
+    /// Sets the element at row `r` and column `c` to `value`
+    ///
+    /// # Panics
+    ///
+    /// This operation panics if `r` or `c` exceed the matrix dimensions
+    pub fn set(&mut self, r: usize, c: usize, value: bool) {
+        assert!(r < NROWS::to_usize() && c < NCOLS::to_usize());
+
+        if value {
+            array::as_mut_slice(&mut self.rows)[r] |= 1 << c;
+        } else {
+            array::as_mut_slice(&mut self.rows)[r] &= !(1 << c);
+        }
+    }
+
+    /// Sets the element at row `r` and column `c` to `value`
+    ///
+    /// Returns `value` back if `r` or `c` exceed the matrix dimensions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate mat;
+    /// extern crate typenum;
+    ///
+    /// use mat::gf2::BitMat;
+    /// use typenum::{U2, U3};
+    ///
+    /// # fn main() {
+    /// let mut m = BitMat::<U2, U3>::zeros();
+    ///
+    /// assert_eq!(m.set_checked(1, 2, true), Ok(()));
+    /// assert_eq!(m.set_checked(1, 3, true), Err(true));
+    /// assert_eq!(m.row_checked(1), Some(0b100));
+    /// assert_eq!(m.row_checked(2), None);
+    /// # }
+    /// ```
+    pub fn set_checked(&mut self, r: usize, c: usize, value: bool) -> Result<(), bool> {
+        no_panic!(if c < NCOLS::to_usize() {
+            match array::as_mut_slice(&mut self.rows).get_mut(r) {
+                Some(row) => {
+                    if value {
+                        *row |= 1 << c;
+                    } else {
+                        *row &= !(1 << c);
+                    }
+                    Ok(())
+                }
+                None => Err(value),
+            }
+        } else {
+            Err(value)
+        })
+    }
+
+    /// Multiplies the matrix by the column vector whose element `c` is bit `c` of `v`
+    ///
+    /// Bit `r` of the result is the parity of `row(r) & v`. Bits of `v` above the number of
+    /// columns are ignored.
+    pub fn mul_packed(&self, v: u32) -> u32 {
+        array::as_slice(&self.rows)
+            .iter()
+            .enumerate()
+            .fold(0, |acc, (r, row)| acc | ((row & v).count_ones() & 1) << r)
+    }
+}
+
+impl<NROWS, NCOLS> Matrix for &BitMat<NROWS, NCOLS>
+where
+    NROWS: ArrayLength<u32>,
+    NCOLS: IsLessOrEqual<U32, Output = True> + Unsigned,
+{
+    type NROWS = NROWS;
+    type NCOLS = NCOLS;
+}
+
+impl<NROWS, NCOLS> UnsafeGet for &BitMat<NROWS, NCOLS>
+where
+    NROWS: ArrayLength<u32>,
+    NCOLS: IsLessOrEqual<U32, Output = True> + Unsigned,
+{
+    type Elem = Gf2;
+
+    unsafe fn unsafe_get(self, r: usize, c: usize) -> Gf2 {
+        Gf2(array::as_slice(&self.rows).get_unchecked(r) >> c & 1 == 1)
+    }
+}
+
+impl<'a, NROWS, NCOLS, R> ops::Mul<R> for &'a BitMat<NROWS, NCOLS>
+where
+    NROWS: ArrayLength<u32>,
+    NCOLS: IsLessOrEqual<U32, Output = True> + Unsigned,
+    R: Matrix<NROWS = NCOLS>,
+{
+    type Output = Product<&'a BitMat<NROWS, NCOLS>, R>;
+
+    fn mul(self, rhs: R) -> Self::Output {
+        Product { l: self, r: rhs }
+    }
+}
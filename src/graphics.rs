@@ -0,0 +1,99 @@
+//! Interoperability with `embedded-graphics`
+//!
+//! Applies 2D affine transforms to `embedded-graphics` points and vertex based primitives.
+//! Coordinates are converted to `f32`, transformed and rounded to the nearest pixel.
+//!
+//! Affine transforms can be stored as `3 x 3` homogeneous matrices or, to save space, as the
+//! `2 x 3` matrix of their first two rows.
+//!
+//! # Example
+//!
+//! Rotate shapes a quarter turn around the point `(10, 10)`
+//!
+//! ```
+//! extern crate embedded_graphics;
+//! extern crate mat;
+//! extern crate typenum;
+//!
+//! use embedded_graphics::geometry::Point;
+//! use embedded_graphics::primitives::{Line, Triangle};
+//! use mat::{Mat3, MatGen};
+//! use mat::graphics::AffineTransform;
+//! use mat::traits::Matrix;
+//! use typenum::{U2, U3};
+//!
+//! # fn main() {
+//! let t: Mat3<f32> = mat::mat3([
+//!     [0., -1., 20.],
+//!     [1., 0., 0.],
+//!     [0., 0., 1.],
+//! ]);
+//!
+//! let line = t.map_line(Line::new(Point::new(10, 10), Point::new(20, 10)));
+//! assert_eq!(line.start, Point::new(10, 10));
+//! assert_eq!(line.end, Point::new(10, 20));
+//!
+//! // the same transform stored as a 2 x 3 matrix
+//! let t = MatGen::<_, U2, U3>::from_fn(|r, c| t.get(r, c));
+//! let triangle = t.map_triangle(Triangle::new(
+//!     Point::new(10, 10),
+//!     Point::new(20, 10),
+//!     Point::new(10, 15),
+//! ));
+//! assert_eq!(triangle.vertices, [Point::new(10, 10), Point::new(10, 20), Point::new(5, 10)]);
+//! # }
+//! ```
+
+use embedded_graphics::geometry::Point;
+use embedded_graphics::primitives::{Line, Triangle};
+use typenum::{U2, U3};
+
+use {Mat3, MatGen};
+use traits::Matrix;
+
+/// A 2D affine transform that can be applied to `embedded-graphics` geometry
+pub trait AffineTransform {
+    /// Applies the transform to the point `(x, y)`
+    fn map_xy(&self, x: f32, y: f32) -> (f32, f32);
+
+    /// Transforms a point, rounding the result to the nearest pixel
+    fn map_point(&self, p: Point) -> Point {
+        let (x, y) = self.map_xy(p.x as f32, p.y as f32);
+        Point::new(round(x), round(y))
+    }
+
+    /// Transforms the end points of a line
+    fn map_line(&self, line: Line) -> Line {
+        Line::new(self.map_point(line.start), self.map_point(line.end))
+    }
+
+    /// Transforms the vertices of a triangle
+    fn map_triangle(&self, triangle: Triangle) -> Triangle {
+        let [a, b, c] = triangle.vertices;
+        Triangle::new(self.map_point(a), self.map_point(b), self.map_point(c))
+    }
+}
+
+/// The homogeneous coordinate of the result is divided out, so projective transforms also work
+impl AffineTransform for Mat3<f32> {
+    fn map_xy(&self, x: f32, y: f32) -> (f32, f32) {
+        let p = self.transform_point(&::vec2(x, y));
+        (p[0], p[1])
+    }
+}
+
+impl AffineTransform for MatGen<f32, U2, U3> {
+    fn map_xy(&self, x: f32, y: f32) -> (f32, f32) {
+        let row = |r| self.get(r, 0) * x + self.get(r, 1) * y + self.get(r, 2);
+        (row(0), row(1))
+    }
+}
+
+// rounds half away from zero
+fn round(x: f32) -> i32 {
+    if x < 0. {
+        (x - 0.5) as i32
+    } else {
+        (x + 0.5) as i32
+    }
+}
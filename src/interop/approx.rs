@@ -0,0 +1,142 @@
+//! `approx` support
+//!
+//! Two matrices are approximately equal if all their elements are approximately equal. This makes
+//! the `approx` assertion macros work with matrices.
+//!
+//! ```
+//! #[macro_use]
+//! extern crate approx;
+//! extern crate mat;
+//! extern crate typenum;
+//!
+//! use mat::MatGen;
+//! use mat::traits::{Matrix, Transpose};
+//! use typenum::{U2, U3};
+//!
+//! # fn main() {
+//! let a = MatGen::<f32, U2, U3>::from_fn(|r, c| (r + c) as f32 / 10.);
+//! let b = MatGen::<f32, U3, U2>::from_fn(|r, c| (r + c) as f32 / 10.);
+//!
+//! let expected = MatGen::<f32, U2, U2>::from_fn(|r, c| [[0.05, 0.08], [0.08, 0.14]][r][c]);
+//! assert_relative_eq!((&a * &b).eval(), expected);
+//! assert_ulps_eq!((&a * &b).eval(), (b.t() * a.t()).eval().t().eval());
+//! assert_relative_ne!(expected, MatGen::from_fn(|r, c| (r + c) as f32));
+//! # }
+//! ```
+
+use core::ops;
+
+use approx::{AbsDiffEq, RelativeEq, UlpsEq};
+use generic_array::ArrayLength;
+use typenum::{Prod, Unsigned};
+
+use {Mat, MatGen};
+
+impl<T, BUFFER, NROWS, NCOLS> AbsDiffEq for Mat<T, BUFFER, NROWS, NCOLS>
+where
+    BUFFER: AsRef<[T]>,
+    NROWS: Unsigned,
+    NCOLS: Unsigned,
+    T: AbsDiffEq + Clone,
+    T::Epsilon: Clone,
+{
+    type Epsilon = T::Epsilon;
+
+    fn default_epsilon() -> T::Epsilon {
+        T::default_epsilon()
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: T::Epsilon) -> bool {
+        let (lhs, rhs): (&[T], &[T]) = (self.buffer.as_ref(), other.buffer.as_ref());
+        lhs.abs_diff_eq(rhs, epsilon)
+    }
+}
+
+impl<T, BUFFER, NROWS, NCOLS> RelativeEq for Mat<T, BUFFER, NROWS, NCOLS>
+where
+    BUFFER: AsRef<[T]>,
+    NROWS: Unsigned,
+    NCOLS: Unsigned,
+    T: Clone + RelativeEq,
+    T::Epsilon: Clone,
+{
+    fn default_max_relative() -> T::Epsilon {
+        T::default_max_relative()
+    }
+
+    fn relative_eq(&self, other: &Self, epsilon: T::Epsilon, max_relative: T::Epsilon) -> bool {
+        let (lhs, rhs): (&[T], &[T]) = (self.buffer.as_ref(), other.buffer.as_ref());
+        lhs.relative_eq(rhs, epsilon, max_relative)
+    }
+}
+
+impl<T, BUFFER, NROWS, NCOLS> UlpsEq for Mat<T, BUFFER, NROWS, NCOLS>
+where
+    BUFFER: AsRef<[T]>,
+    NROWS: Unsigned,
+    NCOLS: Unsigned,
+    T: Clone + UlpsEq,
+    T::Epsilon: Clone,
+{
+    fn default_max_ulps() -> u32 {
+        T::default_max_ulps()
+    }
+
+    fn ulps_eq(&self, other: &Self, epsilon: T::Epsilon, max_ulps: u32) -> bool {
+        let (lhs, rhs): (&[T], &[T]) = (self.buffer.as_ref(), other.buffer.as_ref());
+        lhs.ulps_eq(rhs, epsilon, max_ulps)
+    }
+}
+
+impl<T, NROWS, NCOLS> AbsDiffEq for MatGen<T, NROWS, NCOLS>
+where
+    NROWS: Unsigned + ops::Mul<NCOLS>,
+    NCOLS: Unsigned,
+    Prod<NROWS, NCOLS>: ArrayLength<T>,
+    T: AbsDiffEq + Clone,
+    T::Epsilon: Clone,
+{
+    type Epsilon = T::Epsilon;
+
+    fn default_epsilon() -> T::Epsilon {
+        T::default_epsilon()
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: T::Epsilon) -> bool {
+        self.as_slice().abs_diff_eq(other.as_slice(), epsilon)
+    }
+}
+
+impl<T, NROWS, NCOLS> RelativeEq for MatGen<T, NROWS, NCOLS>
+where
+    NROWS: Unsigned + ops::Mul<NCOLS>,
+    NCOLS: Unsigned,
+    Prod<NROWS, NCOLS>: ArrayLength<T>,
+    T: Clone + RelativeEq,
+    T::Epsilon: Clone,
+{
+    fn default_max_relative() -> T::Epsilon {
+        T::default_max_relative()
+    }
+
+    fn relative_eq(&self, other: &Self, epsilon: T::Epsilon, max_relative: T::Epsilon) -> bool {
+        self.as_slice().relative_eq(other.as_slice(), epsilon, max_relative)
+    }
+}
+
+impl<T, NROWS, NCOLS> UlpsEq for MatGen<T, NROWS, NCOLS>
+where
+    NROWS: Unsigned + ops::Mul<NCOLS>,
+    NCOLS: Unsigned,
+    Prod<NROWS, NCOLS>: ArrayLength<T>,
+    T: Clone + UlpsEq,
+    T::Epsilon: Clone,
+{
+    fn default_max_ulps() -> u32 {
+        T::default_max_ulps()
+    }
+
+    fn ulps_eq(&self, other: &Self, epsilon: T::Epsilon, max_ulps: u32) -> bool {
+        self.as_slice().ulps_eq(other.as_slice(), epsilon, max_ulps)
+    }
+}
@@ -0,0 +1,70 @@
+//! `bytemuck` support
+//!
+//! `Mat` and `MatGen` are `repr(C)` structs whose only non zero-sized field is their buffer, which
+//! has the layout of an array of elements in row major order. Matrices of `Pod` elements are
+//! therefore `Pod` themselves and can be viewed as bytes (e.g. to compute a CRC or to write them to
+//! flash) or cast back from suitably aligned bytes.
+//!
+//! ```
+//! extern crate bytemuck;
+//! extern crate mat;
+//!
+//! use mat::{Mat2, Mat3};
+//! use mat::traits::Matrix;
+//!
+//! # fn main() {
+//! let m = mat::mat2([[1_u8, 2], [3, 4]]);
+//! assert_eq!(bytemuck::bytes_of(&m), &[1, 2, 3, 4]);
+//!
+//! let m: &Mat2<u8> = bytemuck::from_bytes(&[5, 6, 7, 8]);
+//! assert_eq!(m.get(1, 0), 7);
+//!
+//! let z: Mat3<f32> = bytemuck::Zeroable::zeroed();
+//! assert_eq!(z.get(2, 2), 0.);
+//! # }
+//! ```
+
+use core::ops;
+
+use bytemuck::{Pod, Zeroable};
+use generic_array::{ArrayLength, GenericArray};
+use typenum::{Prod, Unsigned};
+
+use {Mat, MatGen};
+
+unsafe impl<T, BUFFER, NROWS, NCOLS> Zeroable for Mat<T, BUFFER, NROWS, NCOLS>
+where
+    BUFFER: AsRef<[T]> + Zeroable,
+    NROWS: Unsigned,
+    NCOLS: Unsigned,
+    T: Clone + Zeroable,
+{
+}
+
+unsafe impl<T, BUFFER, NROWS, NCOLS> Pod for Mat<T, BUFFER, NROWS, NCOLS>
+where
+    BUFFER: AsRef<[T]> + Pod,
+    NROWS: Unsigned + 'static,
+    NCOLS: Unsigned + 'static,
+    T: Pod,
+{
+}
+
+unsafe impl<T, NROWS, NCOLS> Zeroable for MatGen<T, NROWS, NCOLS>
+where
+    NROWS: Unsigned + ops::Mul<NCOLS>,
+    NCOLS: Unsigned,
+    Prod<NROWS, NCOLS>: ArrayLength<T>,
+    T: Clone + Zeroable,
+{
+}
+
+unsafe impl<T, NROWS, NCOLS> Pod for MatGen<T, NROWS, NCOLS>
+where
+    NROWS: Unsigned + ops::Mul<NCOLS> + 'static,
+    NCOLS: Unsigned + 'static,
+    Prod<NROWS, NCOLS>: ArrayLength<T>,
+    GenericArray<T, Prod<NROWS, NCOLS>>: Copy,
+    T: Pod,
+{
+}
@@ -0,0 +1,100 @@
+//! `cgmath` conversions
+//!
+//! ```
+//! extern crate cgmath;
+//! extern crate mat;
+//!
+//! use mat::geometry;
+//!
+//! # fn main() {
+//! let t = geometry::translation(&mat::vec3(1., 2., 3.));
+//!
+//! let c: cgmath::Matrix4<f64> = t.clone().into();
+//! assert_eq!(c.w, cgmath::Vector4::new(1., 2., 3., 1.));
+//!
+//! let back: mat::Mat4<f64> = c.into();
+//! assert_eq!(format!("{:?}", back), format!("{:?}", t));
+//! # }
+//! ```
+
+use cgmath::{Matrix3, Matrix4, Vector3, Vector4};
+
+use {Mat3, Mat4, Vec3};
+use traits::Matrix;
+
+impl<S> From<Vec3<S>> for Vector3<S>
+where
+    S: Copy,
+{
+    fn from(v: Vec3<S>) -> Self {
+        Vector3::new(v[0], v[1], v[2])
+    }
+}
+
+impl<S> From<Vector3<S>> for Vec3<S>
+where
+    S: Copy,
+{
+    fn from(v: Vector3<S>) -> Self {
+        ::vec3(v.x, v.y, v.z)
+    }
+}
+
+// NOTE cgmath matrices are stored in column major order
+impl<S> From<Mat3<S>> for Matrix3<S>
+where
+    S: Copy,
+{
+    fn from(m: Mat3<S>) -> Self {
+        let col = |c| Vector3::new(m.get(0, c), m.get(1, c), m.get(2, c));
+        Matrix3 {
+            x: col(0),
+            y: col(1),
+            z: col(2),
+        }
+    }
+}
+
+impl<S> From<Matrix3<S>> for Mat3<S>
+where
+    S: Copy,
+{
+    fn from(m: Matrix3<S>) -> Self {
+        let cols: [[S; 3]; 3] = m.into();
+        ::mat3([
+            [cols[0][0], cols[1][0], cols[2][0]],
+            [cols[0][1], cols[1][1], cols[2][1]],
+            [cols[0][2], cols[1][2], cols[2][2]],
+        ])
+    }
+}
+
+impl<S> From<Mat4<S>> for Matrix4<S>
+where
+    S: Copy,
+{
+    fn from(m: Mat4<S>) -> Self {
+        let col = |c| Vector4::new(m.get(0, c), m.get(1, c), m.get(2, c), m.get(3, c));
+        Matrix4 {
+            x: col(0),
+            y: col(1),
+            z: col(2),
+            w: col(3),
+        }
+    }
+}
+
+impl<S> From<Matrix4<S>> for Mat4<S>
+where
+    S: Copy,
+{
+    fn from(m: Matrix4<S>) -> Self {
+        let cols: [[S; 4]; 4] = m.into();
+        ::mat4([
+            [cols[0][0], cols[1][0], cols[2][0], cols[3][0]],
+            [cols[0][1], cols[1][1], cols[2][1], cols[3][1]],
+            [cols[0][2], cols[1][2], cols[2][2], cols[3][2]],
+            [cols[0][3], cols[1][3], cols[2][3], cols[3][3]],
+        ])
+    }
+}
@@ -0,0 +1,125 @@
+//! `fixed` element support
+//!
+//! Implements `Zero`, `One`, `Conj` and `Sqrt` for the fixed point types of the `fixed` crate so
+//! the expression system (products, sums, `eval`, `dot`, `norm`, ...) can run in Q-format
+//! arithmetic. `One` is only implemented for the types that can represent the number one.
+//! Matrices of fixed point numbers can be built with the `mat!` macro, which accepts any element
+//! expression. Routines that need small integer constants (a `From<u8>` bound) can only be used
+//! with the integer (`U0`) fixed point types. The 16-bit types also implement
+//! `quant::Interpolated`, so nonlinearities can be applied to them with `MatGen::apply_lut_interp`.
+//!
+//! ```
+//! extern crate fixed;
+//! extern crate mat;
+//!
+//! use fixed::types::I16F16;
+//! use mat::mat;
+//! use mat::traits::Matrix;
+//!
+//! # fn main() {
+//! let q = |x: f64| I16F16::from_num(x);
+//! let a = mat![
+//!     [q(0.5), q(-1.25)],
+//!     [q(2.), q(0.75)],
+//! ];
+//! let x = mat![[q(4.)], [q(2.)]];
+//!
+//! let y = (&a * &x).eval();
+//! assert_eq!(y[0], q(-0.5));
+//! assert_eq!(y[1], q(9.5));
+//!
+//! assert_eq!(mat::vec2(q(3.), q(4.)).norm(), q(5.));
+//! # }
+//! ```
+
+use fixed::types::extra::{LeEqU128, LeEqU16, LeEqU32, LeEqU64, LeEqU8};
+#[cfg(not(feature = "num-traits"))]
+use fixed::types::extra::{IsLessOrEqual, True, U126, U127, U14, U15, U30, U31, U6, U62, U63, U7};
+use fixed::{FixedI128, FixedI16, FixedI32, FixedI64, FixedI8};
+use fixed::{FixedU128, FixedU16, FixedU32, FixedU64, FixedU8};
+
+use quant::Interpolated;
+#[cfg(not(feature = "num-traits"))]
+use traits::{One, Zero};
+use traits::{Conj, Sqrt};
+
+macro_rules! fixed {
+    ($($fixed:ident: $LeEqU:ident, $OneMaxFrac:ident),+) => {
+        $(
+            #[cfg(not(feature = "num-traits"))]
+            impl<Frac> Zero for $fixed<Frac>
+            where
+                Frac: $LeEqU,
+            {
+                fn zero() -> Self {
+                    $fixed::ZERO
+                }
+            }
+
+            // one is only representable if there are enough integer bits
+            #[cfg(not(feature = "num-traits"))]
+            impl<Frac> One for $fixed<Frac>
+            where
+                Frac: $LeEqU + IsLessOrEqual<$OneMaxFrac, Output = True>,
+            {
+                fn one() -> Self {
+                    $fixed::ONE
+                }
+            }
+
+            impl<Frac> Conj for $fixed<Frac>
+            where
+                Frac: $LeEqU,
+            {
+                fn conj(self) -> Self {
+                    self
+                }
+            }
+
+            impl<Frac> Sqrt for $fixed<Frac>
+            where
+                Frac: $LeEqU,
+            {
+                fn sqrt(self) -> Self {
+                    $fixed::sqrt(self)
+                }
+            }
+        )+
+    };
+}
+
+fixed!(
+    FixedI8: LeEqU8, U6,
+    FixedI16: LeEqU16, U14,
+    FixedI32: LeEqU32, U30,
+    FixedI64: LeEqU64, U62,
+    FixedI128: LeEqU128, U126,
+    FixedU8: LeEqU8, U7,
+    FixedU16: LeEqU16, U15,
+    FixedU32: LeEqU32, U31,
+    FixedU64: LeEqU64, U63,
+    FixedU128: LeEqU128, U127
+);
+
+macro_rules! interpolated {
+    ($($fixed:ident: $bits:ident),+) => {
+        $(
+            impl<Frac> Interpolated for $fixed<Frac>
+            where
+                Frac: LeEqU16,
+            {
+                const MIN: i32 = $bits::MIN as i32;
+
+                fn to_bits(self) -> i32 {
+                    $fixed::to_bits(self) as i32
+                }
+
+                fn from_bits(x: i32) -> Self {
+                    $fixed::from_bits(x as $bits)
+                }
+            }
+        )+
+    };
+}
+
+interpolated!(FixedI16: i16, FixedU16: u16);
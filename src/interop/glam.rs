@@ -0,0 +1,75 @@
+//! `glam` conversions
+//!
+//! ```
+//! extern crate glam;
+//! extern crate mat;
+//!
+//! use mat::geometry;
+//!
+//! # fn main() {
+//! let r = geometry::rotation3_z(0.5_f32);
+//!
+//! let g: glam::Mat3 = r.clone().into();
+//! let v: mat::Vec3<f32> = (g * glam::Vec3::X).into();
+//! // NOTE `fast-math` approximates the trigonometric functions
+//! assert!((v[0] - 0.5_f32.cos()).abs() < 1e-3);
+//! assert!((v[1] - 0.5_f32.sin()).abs() < 1e-3);
+//!
+//! let back: mat::Mat3<f32> = g.into();
+//! assert_eq!(format!("{:?}", back), format!("{:?}", r));
+//! # }
+//! ```
+
+use glam::{DMat3, DMat4, DVec3, Mat3, Mat4, Vec3};
+use typenum::{U3, U4};
+
+use MatGen;
+use traits::Matrix;
+
+macro_rules! vec3 {
+    ($glam:ident, $ty:ident) => {
+        impl From<::Vec3<$ty>> for $glam {
+            fn from(v: ::Vec3<$ty>) -> Self {
+                $glam::new(v[0], v[1], v[2])
+            }
+        }
+
+        impl From<$glam> for ::Vec3<$ty> {
+            fn from(v: $glam) -> Self {
+                ::vec3(v.x, v.y, v.z)
+            }
+        }
+    };
+}
+
+// NOTE glam matrices are stored in column major order
+macro_rules! mat {
+    ($glam:ident, $ty:ident, $n:expr, $N:ident) => {
+        impl From<MatGen<$ty, $N, $N>> for $glam {
+            fn from(m: MatGen<$ty, $N, $N>) -> Self {
+                let mut cols = [[0.; $n]; $n];
+                for (c, col) in cols.iter_mut().enumerate() {
+                    for (r, elem) in col.iter_mut().enumerate() {
+                        *elem = m.get(r, c);
+                    }
+                }
+                $glam::from_cols_array_2d(&cols)
+            }
+        }
+
+        impl From<$glam> for MatGen<$ty, $N, $N> {
+            fn from(m: $glam) -> Self {
+                let cols = m.to_cols_array_2d();
+                MatGen::from_fn(|r, c| cols[c][r])
+            }
+        }
+    };
+}
+
+vec3!(Vec3, f32);
+vec3!(DVec3, f64);
+
+mat!(Mat3, f32, 3, U3);
+mat!(Mat4, f32, 4, U4);
+mat!(DMat3, f64, 3, U3);
+mat!(DMat4, f64, 4, U4);
@@ -0,0 +1,79 @@
+//! `half` element support
+//!
+//! Implements `Zero`, `One` and `Conj` for the 16-bit floating point types `f16` and `bf16`, which
+//! halve the memory used by large tables (e.g. calibration data) compared to `f32`. `half` provides
+//! the arithmetic operators and `From<u8>`; with the `libm` or `fast-math` feature `Sqrt` is also
+//! implemented by a round trip through `f32`.
+//!
+//! Every operation on a 16-bit float rounds its result, so long sums lose precision quickly. Use
+//! `Matrix::dot_wide` and `Matrix::mul_wide` to accumulate inner and matrix products in `f32`.
+//!
+//! ```
+//! extern crate half;
+//! extern crate mat;
+//!
+//! use half::f16;
+//! use mat::mat;
+//! use mat::traits::Matrix;
+//!
+//! # fn main() {
+//! let h = f16::from_f32;
+//! let gains = mat![
+//!     [h(1.5), h(-0.25)],
+//!     [h(0.5), h(2.)],
+//! ];
+//! let x = mat![[h(4.)], [h(8.)]];
+//!
+//! let y = (&gains * &x).eval();
+//! assert_eq!((y[0], y[1]), (h(4.), h(18.)));
+//!
+//! // 2048 + 1 is not representable as an `f16`, but it is as an `f32`
+//! let a = mat::vec3(h(2048.), h(1.), h(0.5));
+//! let b = mat::vec3(h(1.), h(1.), h(2.));
+//! assert_eq!(a.dot(&b), h(2048.));
+//! assert_eq!(a.dot_wide::<f32, _>(&b), 2050.);
+//! # }
+//! ```
+
+use half::{bf16, f16};
+
+#[cfg(any(feature = "libm", feature = "fast-math"))]
+use traits::Sqrt;
+use traits::Conj;
+#[cfg(not(feature = "num-traits"))]
+use traits::{One, Zero};
+
+macro_rules! half {
+    ($($ty:ident),+) => {
+        $(
+            #[cfg(not(feature = "num-traits"))]
+            impl Zero for $ty {
+                fn zero() -> Self {
+                    $ty::ZERO
+                }
+            }
+
+            #[cfg(not(feature = "num-traits"))]
+            impl One for $ty {
+                fn one() -> Self {
+                    $ty::ONE
+                }
+            }
+
+            impl Conj for $ty {
+                fn conj(self) -> Self {
+                    self
+                }
+            }
+
+            #[cfg(any(feature = "libm", feature = "fast-math"))]
+            impl Sqrt for $ty {
+                fn sqrt(self) -> Self {
+                    $ty::from_f32(Sqrt::sqrt(self.to_f32()))
+                }
+            }
+        )+
+    };
+}
+
+half!(f16, bf16);
@@ -0,0 +1,133 @@
+//! `heapless` conversions
+//!
+//! ```
+//! extern crate heapless;
+//! extern crate mat;
+//! extern crate typenum;
+//!
+//! use std::convert::TryFrom;
+//!
+//! use heapless::consts::U8;
+//! use mat::MatGen;
+//! use mat::traits::Matrix;
+//! use typenum::{U2, U3};
+//!
+//! # fn main() {
+//! let mut samples = heapless::Vec::<i32, U8>::new();
+//! samples.extend_from_slice(&[1, 2, 3, 4, 5, 6]).unwrap();
+//!
+//! // row major
+//! let m = MatGen::<i32, U2, U3>::try_from(samples.clone()).unwrap();
+//! assert_eq!(m.get(1, 0), 4);
+//!
+//! assert_eq!(&m.to_heapless()[..], &samples[..]);
+//!
+//! // wrong number of elements
+//! assert!(MatGen::<i32, U2, U2>::try_from(samples).is_err());
+//! # }
+//! ```
+//!
+//! Matrices of `hash32::Hash` elements implement `hash32::Hash` so they can be used as the keys of
+//! `heapless` maps.
+//!
+//! ```
+//! extern crate heapless;
+//! extern crate mat;
+//! extern crate typenum;
+//!
+//! use heapless::FnvIndexMap;
+//! use heapless::consts::U4;
+//! use mat::{vec2, Vec2};
+//!
+//! # fn main() {
+//! // gain matrices precomputed at a few operating points
+//! let mut gains = FnvIndexMap::<Vec2<i32>, i32, U4>::new();
+//! gains.insert(vec2(0, 0), 10).unwrap();
+//! gains.insert(vec2(0, 1), 20).unwrap();
+//!
+//! assert_eq!(gains.get(&vec2(0, 1)), Some(&20));
+//! assert_eq!(gains.get(&vec2(1, 0)), None);
+//! # }
+//! ```
+
+use core::convert::TryFrom;
+use core::ops;
+
+use generic_array::ArrayLength;
+use hash32::{Hash, Hasher};
+use heapless::Vec;
+use typenum::{Prod, Unsigned};
+
+use {Mat, MatGen};
+
+impl<T, NROWS, NCOLS> MatGen<T, NROWS, NCOLS>
+where
+    NROWS: Unsigned + ops::Mul<NCOLS>,
+    NCOLS: Unsigned,
+    Prod<NROWS, NCOLS>: ArrayLength<T>,
+    T: Copy,
+{
+    /// Copies the elements of this matrix, in row major order, into a `heapless` vector
+    pub fn to_heapless(&self) -> Vec<T, Prod<NROWS, NCOLS>> {
+        let mut v = Vec::new();
+        for &elem in self.as_slice() {
+            // NOTE the capacity of `v` is the number of elements of the matrix
+            let _ = v.push(elem);
+        }
+        v
+    }
+}
+
+/// Builds a matrix from its elements in row major order
+///
+/// Returns the vector back if its length is not `NROWS * NCOLS`
+impl<T, N, NROWS, NCOLS> TryFrom<Vec<T, N>> for MatGen<T, NROWS, NCOLS>
+where
+    N: ArrayLength<T>,
+    NROWS: Unsigned + ops::Mul<NCOLS>,
+    NCOLS: Unsigned,
+    Prod<NROWS, NCOLS>: ArrayLength<T>,
+    T: Copy,
+{
+    type Error = Vec<T, N>;
+
+    fn try_from(v: Vec<T, N>) -> Result<Self, Vec<T, N>> {
+        if v.len() != NROWS::to_usize() * NCOLS::to_usize() {
+            return Err(v);
+        }
+
+        let ncols = NCOLS::to_usize();
+        Ok(MatGen::from_fn(|r, c| v[r * ncols + c]))
+    }
+}
+
+impl<T, BUFFER, NROWS, NCOLS> Hash for Mat<T, BUFFER, NROWS, NCOLS>
+where
+    BUFFER: AsRef<[T]>,
+    NROWS: Unsigned,
+    NCOLS: Unsigned,
+    T: Clone + Hash,
+{
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: Hasher,
+    {
+        let slice: &[T] = self.buffer.as_ref();
+        slice.hash(state)
+    }
+}
+
+impl<T, NROWS, NCOLS> Hash for MatGen<T, NROWS, NCOLS>
+where
+    NROWS: Unsigned + ops::Mul<NCOLS>,
+    NCOLS: Unsigned,
+    Prod<NROWS, NCOLS>: ArrayLength<T>,
+    T: Clone + Hash,
+{
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: Hasher,
+    {
+        self.as_slice().hash(state)
+    }
+}
@@ -0,0 +1,48 @@
+//! `micromath` conversions
+//!
+//! ```
+//! extern crate mat;
+//! extern crate micromath;
+//!
+//! use micromath::vector::F32x3;
+//!
+//! # fn main() {
+//! let v: F32x3 = mat::vec3(1., 2., 3.).into();
+//! assert_eq!((v.x, v.y, v.z), (1., 2., 3.));
+//!
+//! let w: mat::Vec3<f32> = v.into();
+//! assert_eq!(w[2], 3.);
+//! # }
+//! ```
+
+use micromath::vector::{F32x2, F32x3};
+
+use {Vec2, Vec3};
+
+impl From<Vec2<f32>> for F32x2 {
+    fn from(v: Vec2<f32>) -> Self {
+        F32x2 { x: v[0], y: v[1] }
+    }
+}
+
+impl From<F32x2> for Vec2<f32> {
+    fn from(v: F32x2) -> Self {
+        ::vec2(v.x, v.y)
+    }
+}
+
+impl From<Vec3<f32>> for F32x3 {
+    fn from(v: Vec3<f32>) -> Self {
+        F32x3 {
+            x: v[0],
+            y: v[1],
+            z: v[2],
+        }
+    }
+}
+
+impl From<F32x3> for Vec3<f32> {
+    fn from(v: F32x3) -> Self {
+        ::vec3(v.x, v.y, v.z)
+    }
+}
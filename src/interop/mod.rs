@@ -0,0 +1,37 @@
+//! Conversions to and from the types of other crates, and support for their numeric types as
+//! matrix elements
+//!
+//! Each conversion is enabled by the Cargo feature of the same name as the other crate, except for
+//! the `ndarray` conversions which are enabled by the `std` feature. Conversions that can't fail
+//! are `From` implementations so they can be used with `into()`.
+
+#[cfg(feature = "approx")]
+mod approx;
+#[cfg(feature = "bytemuck")]
+mod bytemuck;
+#[cfg(feature = "cgmath")]
+mod cgmath;
+#[cfg(feature = "fixed")]
+mod fixed;
+#[cfg(feature = "glam")]
+mod glam;
+#[cfg(feature = "half")]
+mod half;
+#[cfg(feature = "heapless")]
+mod heapless;
+#[cfg(feature = "micromath")]
+mod micromath;
+#[cfg(feature = "nalgebra")]
+mod nalgebra;
+#[cfg(feature = "std")]
+mod ndarray;
+#[cfg(feature = "quickcheck")]
+mod quickcheck;
+#[cfg(feature = "rkyv")]
+mod rkyv;
+#[cfg(feature = "serde")]
+mod serde;
+#[cfg(feature = "ufmt")]
+mod ufmt;
+#[cfg(feature = "zerocopy")]
+mod zerocopy;
@@ -0,0 +1,61 @@
+//! `nalgebra` conversions
+//!
+//! Conversions are provided between `MatGen` and `SMatrix` for every size up to `4 x 4`.
+//!
+//! ```
+//! extern crate mat;
+//! extern crate nalgebra;
+//!
+//! use mat::traits::Matrix;
+//!
+//! # fn main() {
+//! let m = mat::mat3([[1., 2., 3.], [4., 5., 6.], [7., 8., 10.]]);
+//!
+//! let n: nalgebra::Matrix3<f64> = m.clone().into();
+//! assert_eq!(n[(1, 2)], 6.);
+//!
+//! let inverse: mat::Mat3<f64> = n.try_inverse().unwrap().into();
+//! let identity = (&m * &inverse).eval();
+//! for r in 0..3 {
+//!     for c in 0..3 {
+//!         let expected: f64 = if r == c { 1. } else { 0. };
+//!         assert!((identity.get(r, c) - expected).abs() < 1e-12);
+//!     }
+//! }
+//! # }
+//! ```
+
+use nalgebra::{SMatrix, Scalar};
+use typenum::{U1, U2, U3, U4};
+
+use MatGen;
+use traits::Matrix;
+
+macro_rules! smatrix {
+    ($R:ident = $r:expr; $($C:ident = $c:expr),+) => {
+        $(
+            impl<T> From<MatGen<T, $R, $C>> for SMatrix<T, $r, $c>
+            where
+                T: Copy + Scalar,
+            {
+                fn from(m: MatGen<T, $R, $C>) -> Self {
+                    SMatrix::from_fn(|r, c| Matrix::get(&m, r, c))
+                }
+            }
+
+            impl<T> From<SMatrix<T, $r, $c>> for MatGen<T, $R, $C>
+            where
+                T: Copy + Scalar,
+            {
+                fn from(m: SMatrix<T, $r, $c>) -> Self {
+                    MatGen::from_fn(|r, c| m[(r, c)])
+                }
+            }
+        )+
+    };
+}
+
+smatrix!(U1 = 1; U1 = 1, U2 = 2, U3 = 3, U4 = 4);
+smatrix!(U2 = 2; U1 = 1, U2 = 2, U3 = 3, U4 = 4);
+smatrix!(U3 = 3; U1 = 1, U2 = 2, U3 = 3, U4 = 4);
+smatrix!(U4 = 4; U1 = 1, U2 = 2, U3 = 3, U4 = 4);
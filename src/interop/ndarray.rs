@@ -0,0 +1,56 @@
+//! `ndarray` conversions
+//!
+//! ```
+//! extern crate mat;
+//! extern crate ndarray;
+//! extern crate typenum;
+//!
+//! use mat::MatGen;
+//! use typenum::{U2, U3};
+//!
+//! # fn main() {
+//! let m = mat::mat2([[1, 2], [3, 4]]);
+//!
+//! let a = m.to_ndarray();
+//! assert_eq!(a, ndarray::arr2(&[[1, 2], [3, 4]]));
+//!
+//! assert!(MatGen::<i32, U2, U2>::from_ndarray(&a).is_some());
+//! assert!(MatGen::<i32, U2, U3>::from_ndarray(&a).is_none());
+//! # }
+//! ```
+
+use core::ops;
+
+use generic_array::ArrayLength;
+use ndarray::{Array2, ArrayBase, Data, Ix2};
+use typenum::{Prod, Unsigned};
+
+use MatGen;
+
+impl<T, NROWS, NCOLS> MatGen<T, NROWS, NCOLS>
+where
+    NROWS: Unsigned + ops::Mul<NCOLS>,
+    NCOLS: Unsigned,
+    Prod<NROWS, NCOLS>: ArrayLength<T>,
+    T: Copy,
+{
+    /// Copies this matrix into a two dimensional `ndarray` array
+    pub fn to_ndarray(&self) -> Array2<T> {
+        let ncols = NCOLS::to_usize();
+        Array2::from_shape_fn((NROWS::to_usize(), ncols), |(r, c)| self.buffer[r * ncols + c])
+    }
+
+    /// Copies a two dimensional `ndarray` array into a matrix
+    ///
+    /// Returns `None` if the shape of `a` is not `NROWS` by `NCOLS`
+    pub fn from_ndarray<S>(a: &ArrayBase<S, Ix2>) -> Option<Self>
+    where
+        S: Data<Elem = T>,
+    {
+        if a.dim() != (NROWS::to_usize(), NCOLS::to_usize()) {
+            return None;
+        }
+
+        Some(MatGen::from_fn(|r, c| a[(r, c)]))
+    }
+}
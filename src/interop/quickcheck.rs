@@ -0,0 +1,54 @@
+//! `quickcheck` support
+//!
+//! Generated matrices have arbitrary elements and shrink one element at a time.
+//!
+//! ```
+//! extern crate mat;
+//! extern crate quickcheck;
+//! extern crate typenum;
+//!
+//! use mat::MatGen;
+//! use mat::traits::Transpose;
+//! use typenum::{U2, U3};
+//!
+//! fn transpose_is_an_involution(m: MatGen<i32, U2, U3>) -> bool {
+//!     m == m.t().t()
+//! }
+//!
+//! # fn main() {
+//! quickcheck::quickcheck(transpose_is_an_involution as fn(MatGen<i32, U2, U3>) -> bool);
+//! # }
+//! ```
+
+use core::ops;
+use std::boxed::Box;
+
+use generic_array::ArrayLength;
+use quickcheck::{Arbitrary, Gen};
+use typenum::{Prod, Unsigned};
+
+use {array, MatGen};
+
+impl<T, NROWS, NCOLS> Arbitrary for MatGen<T, NROWS, NCOLS>
+where
+    NROWS: Unsigned + ops::Mul<NCOLS> + 'static,
+    NCOLS: Unsigned + 'static,
+    Prod<NROWS, NCOLS>: ArrayLength<T>,
+    T: Arbitrary,
+{
+    fn arbitrary(g: &mut Gen) -> Self {
+        MatGen::new(array::generate(|_| T::arbitrary(g)))
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        let m = self.clone();
+        Box::new((0..m.as_slice().len()).flat_map(move |i| {
+            let m = m.clone();
+            m.buffer[i].shrink().map(move |elem| {
+                let mut m = m.clone();
+                m.buffer[i] = elem;
+                m
+            })
+        }))
+    }
+}
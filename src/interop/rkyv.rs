@@ -0,0 +1,90 @@
+//! `rkyv` support
+//!
+//! The archived form of a `MatGen` is a `MatGen` of archived elements with the same row major
+//! layout, so an archived matrix (e.g. calibration data stored in flash) can be used in matrix
+//! expressions directly, without deserializing it first.
+//!
+//! ```
+//! extern crate mat;
+//! extern crate rkyv;
+//! extern crate typenum;
+//!
+//! use mat::MatGen;
+//! use mat::traits::{Matrix, Transpose};
+//! use rkyv::Deserialize;
+//! use typenum::{U2, U3};
+//!
+//! # fn main() {
+//! let m = MatGen::<f32, U2, U3>::from_fn(|r, c| (3 * r + c) as f32);
+//! let bytes = rkyv::to_bytes::<_, 256>(&m).unwrap();
+//!
+//! // zero-copy access
+//! let archived = unsafe { rkyv::archived_root::<MatGen<f32, U2, U3>>(&bytes[..]) };
+//! assert_eq!(archived.get(1, 2), 5.);
+//! assert_eq!((archived.t() * archived).eval().get(2, 2), 29.);
+//!
+//! let m: MatGen<f32, U2, U3> = archived.deserialize(&mut rkyv::Infallible).unwrap();
+//! assert_eq!(m.get(0, 1), 1.);
+//! # }
+//! ```
+
+use core::mem;
+use core::ops;
+
+use generic_array::{ArrayLength, GenericArray};
+use rkyv::{Archive, Deserialize, Fallible, Serialize};
+use typenum::{Prod, Unsigned};
+
+use {array, MatGen};
+
+impl<T, NROWS, NCOLS> Archive for MatGen<T, NROWS, NCOLS>
+where
+    NROWS: Unsigned + ops::Mul<NCOLS>,
+    NCOLS: Unsigned,
+    Prod<NROWS, NCOLS>: ArrayLength<T> + ArrayLength<T::Archived> + ArrayLength<T::Resolver>,
+    T: Archive + Clone,
+    T::Archived: Clone,
+{
+    type Archived = MatGen<T::Archived, NROWS, NCOLS>;
+    type Resolver = GenericArray<T::Resolver, Prod<NROWS, NCOLS>>;
+
+    unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
+        // NOTE `MatGen` is `repr(C)` so its buffer starts at offset 0
+        let out = out as *mut T::Archived;
+        for (i, (elem, resolver)) in self.as_slice().iter().zip(resolver).enumerate() {
+            let offset = i * mem::size_of::<T::Archived>();
+            elem.resolve(pos + offset, resolver, out.add(i));
+        }
+    }
+}
+
+impl<S, T, NROWS, NCOLS> Serialize<S> for MatGen<T, NROWS, NCOLS>
+where
+    S: Fallible + ?Sized,
+    NROWS: Unsigned + ops::Mul<NCOLS>,
+    NCOLS: Unsigned,
+    Prod<NROWS, NCOLS>: ArrayLength<T> + ArrayLength<T::Archived> + ArrayLength<T::Resolver>,
+    T: Clone + Serialize<S>,
+    T::Archived: Clone,
+{
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        let slice = self.as_slice();
+        array::try_generate(|i| slice[i].serialize(serializer))
+    }
+}
+
+impl<D, T, NROWS, NCOLS> Deserialize<MatGen<T, NROWS, NCOLS>, D>
+    for MatGen<T::Archived, NROWS, NCOLS>
+where
+    D: Fallible + ?Sized,
+    NROWS: Unsigned + ops::Mul<NCOLS>,
+    NCOLS: Unsigned,
+    Prod<NROWS, NCOLS>: ArrayLength<T> + ArrayLength<T::Archived>,
+    T: Archive + Clone,
+    T::Archived: Clone + Deserialize<T, D>,
+{
+    fn deserialize(&self, deserializer: &mut D) -> Result<MatGen<T, NROWS, NCOLS>, D::Error> {
+        let slice = self.as_slice();
+        array::try_generate(|i| slice[i].deserialize(deserializer)).map(MatGen::new)
+    }
+}
@@ -0,0 +1,212 @@
+//! `serde` support
+//!
+//! `Mat` and `MatGen` are serialized as an array of rows, each row an array of elements. `MatGen`
+//! can also be deserialized from that representation; the number of rows and columns must match
+//! the dimensions of the matrix type exactly. (`Mat` can't be deserialized because its buffer type
+//! is chosen by the `mat!` macro.)
+//!
+//! ```
+//! extern crate mat;
+//! extern crate serde_json;
+//! extern crate typenum;
+//!
+//! use mat::{mat, MatGen};
+//! use mat::traits::Matrix;
+//! use typenum::{U2, U3};
+//!
+//! # fn main() {
+//! let m = mat![
+//!     [1, 2, 3],
+//!     [4, 5, 6],
+//! ];
+//!
+//! let json = serde_json::to_string(&m).unwrap();
+//! assert_eq!(json, "[[1,2,3],[4,5,6]]");
+//!
+//! let m: MatGen<i32, U2, U3> = serde_json::from_str(&json).unwrap();
+//! assert_eq!(m.get(1, 2), 6);
+//!
+//! // the shape is validated
+//! assert!(serde_json::from_str::<MatGen<i32, U2, U3>>("[[1,2,3],[4,5]]").is_err());
+//! assert!(serde_json::from_str::<MatGen<i32, U2, U3>>("[[1,2,3],[4,5,6],[7,8,9]]").is_err());
+//! # }
+//! ```
+
+use core::fmt;
+use core::marker::PhantomData;
+use core::ops;
+
+use generic_array::ArrayLength;
+use serde::de::{self, Deserialize, DeserializeSeed, Deserializer, IgnoredAny, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeSeq, Serializer};
+use typenum::{Prod, Unsigned};
+
+use {array, Mat, MatGen};
+
+impl<T, BUFFER, NROWS, NCOLS> Serialize for Mat<T, BUFFER, NROWS, NCOLS>
+where
+    BUFFER: AsRef<[T]>,
+    NROWS: Unsigned,
+    NCOLS: Unsigned,
+    T: Clone + Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let slice: &[T] = self.buffer.as_ref();
+        serialize_rows(serializer, slice, NROWS::to_usize(), NCOLS::to_usize())
+    }
+}
+
+impl<T, NROWS, NCOLS> Serialize for MatGen<T, NROWS, NCOLS>
+where
+    NROWS: Unsigned + ops::Mul<NCOLS>,
+    NCOLS: Unsigned,
+    Prod<NROWS, NCOLS>: ArrayLength<T>,
+    T: Clone + Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_rows(serializer, self.as_slice(), NROWS::to_usize(), NCOLS::to_usize())
+    }
+}
+
+impl<'de, T, NROWS, NCOLS> Deserialize<'de> for MatGen<T, NROWS, NCOLS>
+where
+    NROWS: Unsigned + ops::Mul<NCOLS>,
+    NCOLS: Unsigned,
+    Prod<NROWS, NCOLS>: ArrayLength<T>,
+    T: Clone + Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(RowsVisitor(PhantomData))
+    }
+}
+
+fn serialize_rows<S, T>(
+    serializer: S,
+    buffer: &[T],
+    nrows: usize,
+    ncols: usize,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    T: Serialize,
+{
+    let mut seq = serializer.serialize_seq(Some(nrows))?;
+    for r in 0..nrows {
+        seq.serialize_element(&buffer[r * ncols..(r + 1) * ncols])?;
+    }
+    seq.end()
+}
+
+struct RowsVisitor<T, NROWS, NCOLS>(PhantomData<(T, NROWS, NCOLS)>);
+
+impl<'de, T, NROWS, NCOLS> Visitor<'de> for RowsVisitor<T, NROWS, NCOLS>
+where
+    NROWS: Unsigned + ops::Mul<NCOLS>,
+    NCOLS: Unsigned,
+    Prod<NROWS, NCOLS>: ArrayLength<T>,
+    T: Clone + Deserialize<'de>,
+{
+    type Value = MatGen<T, NROWS, NCOLS>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "an array of {} rows", NROWS::to_usize())
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let (nrows, ncols) = (NROWS::to_usize(), NCOLS::to_usize());
+
+        let mut elems = array::Builder::new();
+        for r in 0..nrows {
+            let row = Row {
+                elems: &mut elems,
+                ncols,
+            };
+            if seq.next_element_seed(row)?.is_none() {
+                return Err(de::Error::invalid_length(r, &self));
+            }
+        }
+
+        if seq.next_element::<IgnoredAny>()?.is_some() {
+            return Err(de::Error::invalid_length(nrows + 1, &self));
+        }
+
+        // the shapes have been validated so every element has been pushed
+        match elems.finish() {
+            Some(elems) => Ok(MatGen::new(elems)),
+            None => Err(de::Error::invalid_length(nrows, &self)),
+        }
+    }
+}
+
+/// Deserializes an array of exactly `ncols` elements and appends them to `elems`
+struct Row<'a, T, N>
+where
+    N: ArrayLength<T>,
+    T: 'a,
+{
+    elems: &'a mut array::Builder<T, N>,
+    ncols: usize,
+}
+
+impl<'a, 'de, T, N> DeserializeSeed<'de> for Row<'a, T, N>
+where
+    N: ArrayLength<T>,
+    T: Deserialize<'de>,
+{
+    type Value = ();
+
+    fn deserialize<D>(self, deserializer: D) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'a, 'de, T, N> Visitor<'de> for Row<'a, T, N>
+where
+    N: ArrayLength<T>,
+    T: Deserialize<'de>,
+{
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "an array of length {}", self.ncols)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<(), A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let n = self.ncols;
+        for i in 0..n {
+            let e = match seq.next_element()? {
+                Some(e) => e,
+                None => return Err(de::Error::invalid_length(i, &self)),
+            };
+
+            // NOTE `RowsVisitor` deserializes at most `nrows` rows so there's room for this row
+            if self.elems.push(e).is_err() {
+                return Err(de::Error::invalid_length(i, &self));
+            }
+        }
+
+        if seq.next_element::<IgnoredAny>()?.is_some() {
+            return Err(de::Error::invalid_length(n + 1, &self));
+        }
+
+        Ok(())
+    }
+}
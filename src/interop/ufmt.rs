@@ -0,0 +1,179 @@
+//! `ufmt` support
+//!
+//! `uDebug` uses the same format as `fmt::Debug`, an array of rows, and prints every element.
+//! `uDisplay` uses a compact single-line format where elements are separated by commas and rows by
+//! semicolons. To keep the output of large matrices short over slow serial links, `uDisplay` only
+//! prints the first and last 3 rows (columns) of matrices with more than 7 rows (columns).
+//!
+//! ```
+//! extern crate mat;
+//! extern crate typenum;
+//! extern crate ufmt;
+//!
+//! use mat::MatGen;
+//! use typenum::{U2, U3, U9};
+//! use ufmt::{uWrite, uwrite};
+//!
+//! struct Serial(String);
+//!
+//! impl uWrite for Serial {
+//!     type Error = ();
+//!
+//!     fn write_str(&mut self, s: &str) -> Result<(), ()> {
+//!         self.0.push_str(s);
+//!         Ok(())
+//!     }
+//! }
+//!
+//! # fn main() {
+//! let m = MatGen::<i32, U2, U3>::from_fn(|r, c| (3 * r + c) as i32);
+//!
+//! let mut serial = Serial(String::new());
+//! uwrite!(serial, "{:?}", m).unwrap();
+//! assert_eq!(serial.0, "[[0, 1, 2], [3, 4, 5]]");
+//!
+//! let mut serial = Serial(String::new());
+//! uwrite!(serial, "{}", m).unwrap();
+//! assert_eq!(serial.0, "[0, 1, 2; 3, 4, 5]");
+//!
+//! let m = MatGen::<i32, U2, U9>::from_fn(|r, c| (9 * r + c) as i32);
+//!
+//! let mut serial = Serial(String::new());
+//! uwrite!(serial, "{}", m).unwrap();
+//! assert_eq!(serial.0, "[0, 1, 2, ..., 6, 7, 8; 9, 10, 11, ..., 15, 16, 17]");
+//! # }
+//! ```
+
+use core::ops;
+
+use generic_array::ArrayLength;
+use typenum::{Prod, Unsigned};
+use ufmt::{uDebug, uDisplay, uWrite, Formatter};
+
+use {Mat, MatGen};
+
+/// Number of leading (trailing) rows and columns printed by `uDisplay` when it truncates a matrix
+const EDGE: usize = 3;
+
+impl<T, BUFFER, NROWS, NCOLS> uDebug for Mat<T, BUFFER, NROWS, NCOLS>
+where
+    BUFFER: AsRef<[T]>,
+    NROWS: Unsigned,
+    NCOLS: Unsigned,
+    T: Clone + uDebug,
+{
+    fn fmt<W>(&self, f: &mut Formatter<W>) -> Result<(), W::Error>
+    where
+        W: uWrite + ?Sized,
+    {
+        let slice: &[T] = self.buffer.as_ref();
+        debug(f, slice, NROWS::to_usize(), NCOLS::to_usize())
+    }
+}
+
+impl<T, BUFFER, NROWS, NCOLS> uDisplay for Mat<T, BUFFER, NROWS, NCOLS>
+where
+    BUFFER: AsRef<[T]>,
+    NROWS: Unsigned,
+    NCOLS: Unsigned,
+    T: Clone + uDisplay,
+{
+    fn fmt<W>(&self, f: &mut Formatter<W>) -> Result<(), W::Error>
+    where
+        W: uWrite + ?Sized,
+    {
+        let slice: &[T] = self.buffer.as_ref();
+        display(f, slice, NROWS::to_usize(), NCOLS::to_usize())
+    }
+}
+
+impl<T, NROWS, NCOLS> uDebug for MatGen<T, NROWS, NCOLS>
+where
+    NROWS: Unsigned + ops::Mul<NCOLS>,
+    NCOLS: Unsigned,
+    Prod<NROWS, NCOLS>: ArrayLength<T>,
+    T: Clone + uDebug,
+{
+    fn fmt<W>(&self, f: &mut Formatter<W>) -> Result<(), W::Error>
+    where
+        W: uWrite + ?Sized,
+    {
+        debug(f, self.as_slice(), NROWS::to_usize(), NCOLS::to_usize())
+    }
+}
+
+impl<T, NROWS, NCOLS> uDisplay for MatGen<T, NROWS, NCOLS>
+where
+    NROWS: Unsigned + ops::Mul<NCOLS>,
+    NCOLS: Unsigned,
+    Prod<NROWS, NCOLS>: ArrayLength<T>,
+    T: Clone + uDisplay,
+{
+    fn fmt<W>(&self, f: &mut Formatter<W>) -> Result<(), W::Error>
+    where
+        W: uWrite + ?Sized,
+    {
+        display(f, self.as_slice(), NROWS::to_usize(), NCOLS::to_usize())
+    }
+}
+
+fn debug<T, W>(
+    f: &mut Formatter<W>,
+    buffer: &[T],
+    nrows: usize,
+    ncols: usize,
+) -> Result<(), W::Error>
+where
+    T: uDebug,
+    W: uWrite + ?Sized,
+{
+    // NOTE not `chunks` because it panics when `ncols` is zero
+    f.debug_list()?
+        .entries((0..nrows).map(|r| &buffer[r * ncols..(r + 1) * ncols]))?
+        .finish()
+}
+
+fn display<T, W>(
+    f: &mut Formatter<W>,
+    buffer: &[T],
+    nrows: usize,
+    ncols: usize,
+) -> Result<(), W::Error>
+where
+    T: uDisplay,
+    W: uWrite + ?Sized,
+{
+    f.write_str("[")?;
+    let mut r = 0;
+    while r < nrows {
+        if r != 0 {
+            f.write_str("; ")?;
+        }
+
+        if r == EDGE && nrows > 2 * EDGE + 1 {
+            f.write_str("...")?;
+            r = nrows - EDGE;
+            continue;
+        }
+
+        let row = &buffer[r * ncols..(r + 1) * ncols];
+        let mut c = 0;
+        while c < ncols {
+            if c != 0 {
+                f.write_str(", ")?;
+            }
+
+            if c == EDGE && ncols > 2 * EDGE + 1 {
+                f.write_str("...")?;
+                c = ncols - EDGE;
+                continue;
+            }
+
+            row[c].fmt(f)?;
+            c += 1;
+        }
+
+        r += 1;
+    }
+    f.write_str("]")
+}
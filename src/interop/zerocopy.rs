@@ -0,0 +1,136 @@
+//! `zerocopy` support
+//!
+//! `zerocopy` only allows its traits to be derived, which rules out implementing them for generic
+//! matrix types, so matrices of `FromBytes` / `AsBytes` elements get inherent methods that go
+//! through the element traits instead. `MatGen::read_from` and `write_to` parse matrices out of,
+//! and write them into, packet buffers without any `unsafe` code; both return `None` if the length
+//! of the buffer doesn't match the size of the matrix. Matrices of `Unaligned` elements, like the
+//! byte order aware integers of `zerocopy::byteorder`, can also be borrowed in place from a buffer
+//! with `Mat::ref_from`.
+//!
+//! ```
+//! extern crate mat;
+//! extern crate typenum;
+//! extern crate zerocopy;
+//!
+//! use mat::{Mat, Mat2};
+//! use mat::traits::Matrix;
+//! use typenum::U2;
+//! use zerocopy::byteorder::little_endian::I16;
+//!
+//! # fn main() {
+//! let packet = [0x01, 0x00, 0xfe, 0xff, 0x00, 0x01, 0x04, 0x00];
+//!
+//! let raw = Mat2::<I16>::read_from(&packet[..]).unwrap();
+//! let m = Mat2::from_fn(|r, c| raw.get(r, c).get());
+//! assert_eq!(m.get(0, 1), -2);
+//! assert_eq!(m.get(1, 0), 256);
+//!
+//! // the length of the buffer must match the size of the matrix
+//! assert!(Mat2::<I16>::read_from(&packet[..6]).is_none());
+//!
+//! let mut buffer = [0; 8];
+//! raw.write_to(&mut buffer[..]).unwrap();
+//! assert_eq!(buffer, packet);
+//!
+//! // no copy
+//! let view = Mat::<I16, _, U2, U2>::ref_from(&packet[..]).unwrap();
+//! assert_eq!((&view).get(1, 1).get(), 4);
+//! # }
+//! ```
+
+use core::{mem, ops};
+
+use generic_array::ArrayLength;
+use typenum::{Prod, Unsigned};
+use zerocopy::{AsBytes, FromBytes, Unaligned};
+
+use {array, Mat, MatGen};
+
+impl<T, BUFFER, NROWS, NCOLS> Mat<T, BUFFER, NROWS, NCOLS>
+where
+    BUFFER: AsRef<[T]>,
+    NROWS: Unsigned,
+    NCOLS: Unsigned,
+    T: AsBytes + Clone,
+{
+    /// Views the elements of this matrix, in row major order, as bytes
+    pub fn as_bytes(&self) -> &[u8] {
+        self.buffer.as_ref().as_bytes()
+    }
+
+    /// Copies the elements of this matrix, in row major order, into `bytes`
+    ///
+    /// Returns `None` if the length of `bytes` is not the size of the matrix.
+    pub fn write_to(&self, bytes: &mut [u8]) -> Option<()> {
+        write_to(self.as_bytes(), bytes)
+    }
+}
+
+impl<'a, T, NROWS, NCOLS> Mat<T, &'a [T], NROWS, NCOLS>
+where
+    NROWS: Unsigned,
+    NCOLS: Unsigned,
+    T: Clone + FromBytes + Unaligned,
+{
+    /// Borrows a matrix whose elements are stored, in row major order, in `bytes`
+    ///
+    /// Returns `None` if the length of `bytes` is not the size of the matrix.
+    pub fn ref_from(bytes: &'a [u8]) -> Option<Self> {
+        Mat::from_buffer(T::slice_from(bytes)?).ok()
+    }
+}
+
+impl<T, NROWS, NCOLS> MatGen<T, NROWS, NCOLS>
+where
+    NROWS: Unsigned + ops::Mul<NCOLS>,
+    NCOLS: Unsigned,
+    Prod<NROWS, NCOLS>: ArrayLength<T>,
+    T: Clone,
+{
+    /// Reads a matrix whose elements are stored, in row major order, in `bytes`
+    ///
+    /// Returns `None` if the length of `bytes` is not the size of the matrix. `bytes` need not be
+    /// aligned.
+    pub fn read_from(bytes: &[u8]) -> Option<Self>
+    where
+        T: FromBytes,
+    {
+        let size = mem::size_of::<T>();
+        if bytes.len() != NROWS::to_usize() * NCOLS::to_usize() * size {
+            return None;
+        }
+
+        // NOTE `read_from` can't fail: every chunk has the size of an element
+        Some(MatGen::new(array::generate(|i| {
+            T::read_from(&bytes[i * size..(i + 1) * size]).unwrap()
+        })))
+    }
+
+    /// Views the elements of this matrix, in row major order, as bytes
+    pub fn as_bytes(&self) -> &[u8]
+    where
+        T: AsBytes,
+    {
+        self.as_slice().as_bytes()
+    }
+
+    /// Copies the elements of this matrix, in row major order, into `bytes`
+    ///
+    /// Returns `None` if the length of `bytes` is not the size of the matrix.
+    pub fn write_to(&self, bytes: &mut [u8]) -> Option<()>
+    where
+        T: AsBytes,
+    {
+        write_to(self.as_bytes(), bytes)
+    }
+}
+
+fn write_to(src: &[u8], dst: &mut [u8]) -> Option<()> {
+    if src.len() != dst.len() {
+        return None;
+    }
+
+    dst.copy_from_slice(src);
+    Some(())
+}
@@ -0,0 +1,215 @@
+//! Interpolation
+//!
+//! `natural_spline` computes the coefficients of the natural cubic spline through a set of knots.
+//! Evaluating the spline only needs the knots and the coefficient matrix, so the coefficients can
+//! be computed once, e.g. at build time, and stored in firmware.
+//!
+//! `resampling` and `decimation` build the matrices of linear rate conversions, so changing the
+//! rate of a short window of samples becomes a single multiplication whose dimensions are checked
+//! at compile time.
+//!
+//! # Example
+//!
+//! ```
+//! extern crate mat;
+//! extern crate typenum;
+//!
+//! use mat::{interp, Vector};
+//! use mat::traits::Matrix;
+//! use typenum::U5;
+//!
+//! # fn main() {
+//! let x = Vector::<f64, U5>::from_fn(|r, _| [0., 1., 2., 4., 5.][r]);
+//! let y = Vector::<f64, U5>::from_fn(|r, _| [0., 1., 0., 1., 2.][r]);
+//!
+//! // one row of coefficients per interval
+//! let s = interp::natural_spline(&x, &y);
+//!
+//! let eval = |t: f64| {
+//!     let i = (0..4).rev().find(|&i| t >= x[i]).unwrap_or(0);
+//!     let dx = t - x[i];
+//!     s.get(i, 0) + dx * (s.get(i, 1) + dx * (s.get(i, 2) + dx * s.get(i, 3)))
+//! };
+//!
+//! // the spline goes through the knots
+//! for i in 0..5 {
+//!     assert!((eval(x[i]) - y[i]).abs() < 1e-12);
+//! }
+//!
+//! // and is smooth at the interior knots
+//! let h = 1e-6;
+//! for i in 1..4 {
+//!     let left = (eval(x[i]) - eval(x[i] - h)) / h;
+//!     let right = (eval(x[i] + h) - eval(x[i])) / h;
+//!     assert!((left - right).abs() < 1e-4);
+//! }
+//! # }
+//! ```
+
+use core::ops;
+
+use generic_array::ArrayLength;
+use typenum::{B1, PartialDiv, PartialQuot, Prod, Sub1, U1, U4, Unsigned};
+
+use traits::Zero;
+use {MatGen, Vector};
+
+/// Computes the natural cubic spline through the knots `(x[i], y[i])`
+///
+/// Row `i` of the result holds the coefficients `[a, b, c, d]` of the cubic polynomial
+/// `a + b t + c t² + d t³`, where `t = x - x[i]`, that interpolates the interval from `x[i]` to
+/// `x[i + 1]`. The second derivative of the spline is zero at both ends.
+///
+/// The second derivatives at the knots are the solution of a tridiagonal, diagonally dominant,
+/// system, which is solved in `O(N)` operations without pivoting.
+///
+/// # Panics
+///
+/// This operation panics if the knots `x` are not strictly increasing
+pub fn natural_spline<T, N>(x: &Vector<T, N>, y: &Vector<T, N>) -> MatGen<T, Sub1<N>, U4>
+where
+    N: Unsigned + ops::Mul<U1> + ops::Sub<B1>,
+    Sub1<N>: Unsigned + ops::Mul<U4>,
+    Prod<N, U1>: ArrayLength<T>,
+    Prod<Sub1<N>, U4>: ArrayLength<T>,
+    T: ops::Add<T, Output = T>
+        + ops::Div<T, Output = T>
+        + ops::Mul<T, Output = T>
+        + ops::Sub<T, Output = T>
+        + Copy
+        + From<u8>
+        + PartialOrd
+        + Zero,
+{
+    let n = N::to_usize();
+    let (x, y) = (&x.buffer, &y.buffer);
+    for i in 1..n {
+        assert!(x[i] > x[i - 1]);
+    }
+
+    // half of the second derivative at each knot; zero at both ends
+    let mut c: Vector<T, N> = MatGen::zeros();
+
+    // forward elimination; `diag` and `rhs` hold the diagonal and the right hand side of the
+    // eliminated system
+    let mut diag: Vector<T, N> = MatGen::zeros();
+    let mut rhs: Vector<T, N> = MatGen::zeros();
+    for i in 1..n.saturating_sub(1) {
+        let (h0, h1) = (x[i] - x[i - 1], x[i + 1] - x[i]);
+        let mut d = T::from(2) * (h0 + h1);
+        let mut r = T::from(3) * ((y[i + 1] - y[i]) / h1 - (y[i] - y[i - 1]) / h0);
+
+        if i > 1 {
+            let w = h0 / diag.buffer[i - 1];
+            d = d - w * h0;
+            r = r - w * rhs.buffer[i - 1];
+        }
+
+        diag.buffer[i] = d;
+        rhs.buffer[i] = r;
+    }
+
+    // back substitution
+    for i in (1..n.saturating_sub(1)).rev() {
+        let h1 = x[i + 1] - x[i];
+        c.buffer[i] = (rhs.buffer[i] - h1 * c.buffer[i + 1]) / diag.buffer[i];
+    }
+
+    let c = &c.buffer;
+    MatGen::from_fn(|i, j| {
+        let h = x[i + 1] - x[i];
+        match j {
+            0 => y[i],
+            1 => (y[i + 1] - y[i]) / h - h * (T::from(2) * c[i] + c[i + 1]) / T::from(3),
+            2 => c[i],
+            _ => (c[i + 1] - c[i]) / (T::from(3) * h),
+        }
+    })
+}
+
+/// Returns the `M` by `N` matrix that resamples `N` samples into `M` samples by linear
+/// interpolation
+///
+/// The first and the last output samples coincide with the first and the last input samples and
+/// the output samples in between are evenly spaced. Each row has at most two non-zero elements,
+/// which add up to one.
+///
+/// # Example
+///
+/// ```
+/// extern crate mat;
+/// extern crate typenum;
+///
+/// use mat::{interp, MatGen, Vector};
+/// use typenum::{U4, U7};
+///
+/// # fn main() {
+/// // upsample 4 samples of a ramp to 7 samples
+/// let r = interp::resampling::<f32, U7, U4>();
+/// let x = Vector::<f32, U4>::from_fn(|r, _| [0., 3., 6., 9.][r]);
+///
+/// let y = (&r * &x).eval();
+/// assert_eq!(y, MatGen::from_fn(|r, _| [0., 1.5, 3., 4.5, 6., 7.5, 9.][r]));
+/// # }
+/// ```
+pub fn resampling<T, M, N>() -> MatGen<T, M, N>
+where
+    M: Unsigned + ops::Mul<N>,
+    N: Unsigned,
+    Prod<M, N>: ArrayLength<T>,
+    T: ops::Div<T, Output = T> + ops::Sub<T, Output = T> + Copy + From<u16> + Zero,
+{
+    let (m, n) = (M::to_usize(), N::to_usize());
+    // output sample `i` is at `i * (n - 1) / (m - 1)` in units of the input sampling period
+    let (num, den) = (n.saturating_sub(1), m.saturating_sub(1).max(1));
+
+    MatGen::from_fn(|i, j| {
+        let (k, rem) = (i * num / den, i * num % den);
+        let frac = T::from(rem as u16) / T::from(den as u16);
+
+        if j == k {
+            T::from(1) - frac
+        } else if j == k + 1 {
+            frac
+        } else {
+            T::zero()
+        }
+    })
+}
+
+/// Returns the matrix that decimates `N` samples by `FACTOR`
+///
+/// Each output sample is the average of `FACTOR` consecutive input samples, a simple anti-aliasing
+/// filter. `N` must be a multiple of `FACTOR`, which is checked at compile time.
+///
+/// # Example
+///
+/// ```
+/// extern crate mat;
+/// extern crate typenum;
+///
+/// use mat::{interp, MatGen, Vector};
+/// use typenum::{U3, U6};
+///
+/// # fn main() {
+/// let d = interp::decimation::<f32, U6, U3>();
+/// let x = Vector::<f32, U6>::from_fn(|r, _| [1., 2., 3., 6., 0., 0.][r]);
+///
+/// // 2 samples
+/// let y = (&d * &x).eval();
+/// assert_eq!(y, MatGen::from_fn(|r, _| [2., 2.][r]));
+/// # }
+/// ```
+pub fn decimation<T, N, FACTOR>() -> MatGen<T, PartialQuot<N, FACTOR>, N>
+where
+    N: Unsigned + PartialDiv<FACTOR>,
+    FACTOR: Unsigned,
+    PartialQuot<N, FACTOR>: Unsigned + ops::Mul<N>,
+    Prod<PartialQuot<N, FACTOR>, N>: ArrayLength<T>,
+    T: ops::Div<T, Output = T> + Copy + From<u16> + Zero,
+{
+    let factor = FACTOR::to_usize();
+    let weight = T::from(1) / T::from(factor as u16);
+
+    MatGen::from_fn(|i, j| if j / factor == i { weight } else { T::zero() })
+}
@@ -0,0 +1,277 @@
+//! Interval arithmetic
+//!
+//! An `Interval` holds a lower and an upper bound of an uncertain quantity. The arithmetic
+//! operators return intervals that contain every possible result, so evaluating a matrix expression
+//! with interval elements yields worst-case bounds of each output.
+//!
+//! The bounds are computed with the element type's own arithmetic. With integers and fixed point
+//! numbers (that don't overflow) they are exact. Floating point operations round to nearest, so
+//! the bounds of a float interval can be off by a few ULPs; use `inflate` to add a safety margin.
+//!
+//! # Example
+//!
+//! Output range of a 2 x 2 gain matrix whose elements have a ±1% tolerance
+//!
+//! ```
+//! extern crate mat;
+//! extern crate typenum;
+//!
+//! use mat::MatGen;
+//! use mat::interval::Interval;
+//! use typenum::{U1, U2};
+//!
+//! # fn main() {
+//! let tol = |x: i32| Interval::point(100 * x).inflate(x.abs());
+//! let gains = [[tol(2), tol(-1)], [tol(0), tol(3)]];
+//! let a = MatGen::<_, U2, U2>::from_fn(|r, c| gains[r][c]);
+//!
+//! // the input is known to within ±5 units
+//! let x = MatGen::<_, U2, U1>::from_fn(|r, _| [Interval::new(95, 105), Interval::new(-5, 5)][r]);
+//!
+//! let y = (&a * &x).eval();
+//! assert_eq!(y[0], Interval::new(18_305, 21_715));
+//! assert_eq!(y[1], Interval::new(-1_515, 1_515));
+//! assert!(y[0].contains(20_000));
+//! # }
+//! ```
+
+use core::ops;
+
+use traits::{Conj, One, Sqrt, Zero};
+
+/// A closed interval, `[lo, hi]`
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Interval<T> {
+    /// Lower bound
+    pub lo: T,
+    /// Upper bound
+    pub hi: T,
+}
+
+impl<T> Interval<T>
+where
+    T: PartialOrd,
+{
+    /// Creates the interval `[lo, hi]`
+    ///
+    /// # Panics
+    ///
+    /// This operation panics if `lo` is greater than `hi`
+    pub fn new(lo: T, hi: T) -> Self {
+        assert!(lo <= hi);
+
+        Interval { lo, hi }
+    }
+
+    /// Returns `true` if `x` lies within the interval
+    pub fn contains(&self, x: T) -> bool {
+        self.lo <= x && x <= self.hi
+    }
+}
+
+impl<T> Interval<T>
+where
+    T: Copy + PartialOrd,
+{
+    /// Creates the degenerate interval `[x, x]`
+    pub fn point(x: T) -> Self {
+        Interval { lo: x, hi: x }
+    }
+
+    /// Returns the smallest interval that contains both `self` and `other`
+    pub fn hull(self, other: Self) -> Self {
+        Interval {
+            lo: min(self.lo, other.lo),
+            hi: max(self.hi, other.hi),
+        }
+    }
+}
+
+impl<T> Interval<T>
+where
+    T: ops::Add<T, Output = T> + ops::Sub<T, Output = T> + Copy,
+{
+    /// Returns the width of the interval, `hi - lo`
+    pub fn width(self) -> T {
+        self.hi - self.lo
+    }
+
+    /// Widens the interval by `margin` on both sides
+    pub fn inflate(self, margin: T) -> Self {
+        Interval {
+            lo: self.lo - margin,
+            hi: self.hi + margin,
+        }
+    }
+}
+
+impl<T> Interval<T>
+where
+    T: ops::Div<T, Output = T> + Copy + PartialOrd + Zero,
+{
+    /// Divides `self` by `rhs`
+    ///
+    /// Returns `None` if `rhs` contains zero, in which case the quotient is unbounded.
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        if rhs.contains(T::zero()) {
+            None
+        } else {
+            Some(bounds(
+                self.lo / rhs.lo,
+                self.lo / rhs.hi,
+                self.hi / rhs.lo,
+                self.hi / rhs.hi,
+            ))
+        }
+    }
+}
+
+/// Real intervals are their own conjugate
+impl<T> Conj for Interval<T>
+where
+    T: Copy,
+{
+    fn conj(self) -> Self {
+        self
+    }
+}
+
+impl<T> Zero for Interval<T>
+where
+    T: Zero,
+{
+    fn zero() -> Self {
+        Interval {
+            lo: T::zero(),
+            hi: T::zero(),
+        }
+    }
+}
+
+impl<T> One for Interval<T>
+where
+    T: One,
+{
+    fn one() -> Self {
+        Interval {
+            lo: T::one(),
+            hi: T::one(),
+        }
+    }
+}
+
+impl<T> From<u8> for Interval<T>
+where
+    T: Copy + From<u8>,
+{
+    fn from(x: u8) -> Self {
+        let x = T::from(x);
+        Interval { lo: x, hi: x }
+    }
+}
+
+impl<T> ops::Add for Interval<T>
+where
+    T: ops::Add<T, Output = T>,
+{
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Interval {
+            lo: self.lo + rhs.lo,
+            hi: self.hi + rhs.hi,
+        }
+    }
+}
+
+impl<T> ops::Sub for Interval<T>
+where
+    T: ops::Sub<T, Output = T>,
+{
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Interval {
+            lo: self.lo - rhs.hi,
+            hi: self.hi - rhs.lo,
+        }
+    }
+}
+
+impl<T> ops::Mul for Interval<T>
+where
+    T: ops::Mul<T, Output = T> + Copy + PartialOrd,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        bounds(
+            self.lo * rhs.lo,
+            self.lo * rhs.hi,
+            self.hi * rhs.lo,
+            self.hi * rhs.hi,
+        )
+    }
+}
+
+/// # Panics
+///
+/// This operation panics if `rhs` contains zero; see `checked_div`
+impl<T> ops::Div for Interval<T>
+where
+    T: ops::Div<T, Output = T> + Copy + PartialOrd + Zero,
+{
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self {
+        assert!(!rhs.contains(T::zero()));
+
+        self.checked_div(rhs).unwrap()
+    }
+}
+
+/// The lower bound is clamped to zero, the smallest value in the domain of the square root
+impl<T> Sqrt for Interval<T>
+where
+    T: Copy + PartialOrd + Sqrt + Zero,
+{
+    fn sqrt(self) -> Self {
+        Interval {
+            lo: max(self.lo, T::zero()).sqrt(),
+            hi: self.hi.sqrt(),
+        }
+    }
+}
+
+// smallest interval that contains the four candidate bounds
+fn bounds<T>(a: T, b: T, c: T, d: T) -> Interval<T>
+where
+    T: Copy + PartialOrd,
+{
+    Interval {
+        lo: min(min(a, b), min(c, d)),
+        hi: max(max(a, b), max(c, d)),
+    }
+}
+
+fn min<T>(a: T, b: T) -> T
+where
+    T: PartialOrd,
+{
+    if b < a {
+        b
+    } else {
+        a
+    }
+}
+
+fn max<T>(a: T, b: T) -> T
+where
+    T: PartialOrd,
+{
+    if b > a {
+        b
+    } else {
+        a
+    }
+}
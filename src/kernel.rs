@@ -0,0 +1,154 @@
+//! Kernels that operate on row major slices
+//!
+//! These functions are generic over the element type but not over the matrix dimensions, which are
+//! passed at runtime, so all the matrix sizes an application uses share a single copy of each
+//! kernel. The typed APIs are thin wrappers around them.
+
+use core::ops;
+
+use linalg;
+use traits::Zero;
+
+/// Stores the product of the `m` by `k` matrix `a` and the `k` by `n` matrix `b` in the `m` by `n`
+/// matrix `c`
+///
+/// Each row of `c` is accumulated by sweeping over the rows of `b`
+pub fn gemm<T>(a: &[T], b: &[T], c: &mut [T], m: usize, k: usize, n: usize)
+where
+    T: ops::Add<T, Output = T> + ops::Mul<T, Output = T> + Clone + Zero,
+{
+    for elem in c[..m * n].iter_mut() {
+        *elem = T::zero();
+    }
+    accumulate(a, b, c, m, k, n);
+}
+
+/// Adds `a * x`, where `a` is `m` by `k` and `x` is `k` by `n`, to the `m` by `n` matrix `c`
+///
+/// Like `gemm`, but `c` must already hold the bias, so the accumulators start at it and no second
+/// pass over `c` is needed
+pub fn affine<T>(a: &[T], x: &[T], c: &mut [T], m: usize, k: usize, n: usize)
+where
+    T: ops::Add<T, Output = T> + ops::Mul<T, Output = T> + Clone,
+{
+    accumulate(a, x, c, m, k, n);
+}
+
+// `c += a * b`
+fn accumulate<T>(a: &[T], b: &[T], c: &mut [T], m: usize, k: usize, n: usize)
+where
+    T: ops::Add<T, Output = T> + ops::Mul<T, Output = T> + Clone,
+{
+    if n == 0 {
+        return;
+    }
+
+    for r in 0..m {
+        let (a, c) = (&a[r * k..(r + 1) * k], &mut c[r * n..(r + 1) * n]);
+        for (a, b) in a.iter().zip(b.chunks(n)) {
+            for (elem, b) in c.iter_mut().zip(b) {
+                *elem = elem.clone() + a.clone() * b.clone();
+            }
+        }
+    }
+}
+
+/// Solves the `n` by `n` linear system `a x = b` in place, overwriting `b` with `x`
+///
+/// This uses Gaussian elimination with partial pivoting; `a` is overwritten with the eliminated
+/// system. Returns `false` if `a` is singular.
+pub fn solve<T>(a: &mut [T], x: &mut [T], n: usize) -> bool
+where
+    T: ops::Add<T, Output = T>
+        + ops::Div<T, Output = T>
+        + ops::Mul<T, Output = T>
+        + ops::Sub<T, Output = T>
+        + Copy
+        + PartialOrd
+        + Zero,
+{
+    solve_matrix(a, x, n, 1)
+}
+
+/// Solves the `n` by `n` linear system `a x = b`, where `b` is `n` by `m`, in place, overwriting
+/// `b` with `x`
+///
+/// `a` is factorized once for all the columns of `b`. Returns `false` if `a` is singular.
+pub fn solve_matrix<T>(a: &mut [T], x: &mut [T], n: usize, m: usize) -> bool
+where
+    T: ops::Add<T, Output = T>
+        + ops::Div<T, Output = T>
+        + ops::Mul<T, Output = T>
+        + ops::Sub<T, Output = T>
+        + Copy
+        + PartialOrd
+        + Zero,
+{
+    if eliminate(a, x, n, m).is_none() {
+        return false;
+    }
+
+    // back substitution
+    for k in (0..n).rev() {
+        for j in 0..m {
+            let mut sum = x[k * m + j];
+            for c in k + 1..n {
+                sum = sum - a[k * n + c] * x[c * m + j];
+            }
+            x[k * m + j] = sum / a[k * n + k];
+        }
+    }
+
+    true
+}
+
+/// Reduces the `n` by `n` matrix `a` to an upper triangular matrix in place
+///
+/// This uses Gaussian elimination with partial pivoting. The same row operations are applied to
+/// the `n` by `m` matrix `x`. Returns the number of row swaps, or `None` if `a` is singular.
+pub fn eliminate<T>(a: &mut [T], x: &mut [T], n: usize, m: usize) -> Option<usize>
+where
+    T: ops::Add<T, Output = T>
+        + ops::Div<T, Output = T>
+        + ops::Mul<T, Output = T>
+        + ops::Sub<T, Output = T>
+        + Copy
+        + PartialOrd
+        + Zero,
+{
+    let mut swaps = 0;
+    for k in 0..n {
+        let mut pivot = k;
+        for r in k + 1..n {
+            if linalg::abs(a[r * n + k]) > linalg::abs(a[pivot * n + k]) {
+                pivot = r;
+            }
+        }
+
+        if a[pivot * n + k] == T::zero() {
+            return None;
+        }
+
+        if pivot != k {
+            for c in k..n {
+                a.swap(k * n + c, pivot * n + c);
+            }
+            for c in 0..m {
+                x.swap(k * m + c, pivot * m + c);
+            }
+            swaps += 1;
+        }
+
+        for r in k + 1..n {
+            let factor = a[r * n + k] / a[k * n + k];
+            for c in k + 1..n {
+                a[r * n + c] = a[r * n + c] - factor * a[k * n + c];
+            }
+            for c in 0..m {
+                x[r * m + c] = x[r * m + c] - factor * x[k * m + c];
+            }
+        }
+    }
+
+    Some(swaps)
+}
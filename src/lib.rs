@@ -13,8 +13,6 @@
 //! tree*. `get` can be used to force evaluation of such a tree; see below:
 //!
 //! ```
-//! #![feature(proc_macro)]
-//!
 //! use mat::mat;
 //! use mat::traits::Matrix;
 //!
@@ -42,6 +40,47 @@
 //! performs the operations required to get the element at row 0 and column 0 that such matrix C
 //! would have.
 //!
+//! # Empty matrices
+//!
+//! Either dimension of a matrix can be zero. Empty matrices follow the usual mathematical
+//! conventions: the product of an `M`x`0` matrix and a `0`x`N` matrix is the `M`x`N` zero matrix,
+//! an empty sum is zero and solving an empty linear system succeeds. So generic code where a
+//! dimension collapses to `U0` compiles and behaves sanely.
+//!
+//! ```
+//! extern crate mat;
+//! extern crate typenum;
+//!
+//! use mat::MatGen;
+//! use typenum::{U0, U2, U3};
+//!
+//! # fn main() {
+//! let a = MatGen::<f32, U2, U0>::zeros();
+//! let b = MatGen::<f32, U0, U3>::zeros();
+//!
+//! assert_eq!((&a * &b).eval(), MatGen::<f32, U2, U3>::zeros());
+//! assert_eq!(a.matmul(&b), MatGen::<f32, U2, U3>::zeros());
+//! assert_eq!(format!("{:?}", a), "[[], []]");
+//! assert_eq!(b.clone(), b);
+//!
+//! let d = mat::DiagMat::<f32, U0>::from_fn(|_| 1.);
+//! assert!(d.clone().diag().is_empty());
+//! # }
+//! ```
+//!
+//! The `mat!` macro needs at least one row to infer the number of columns, so `mat![]` is a
+//! compile error.
+//!
+//! ``` compile_fail
+//! extern crate mat;
+//!
+//! use mat::mat;
+//!
+//! # fn main() {
+//! let m = mat![];
+//! # }
+//! ```
+//!
 //! # Out of scope
 //!
 //! The following features are out of scope for this library.
@@ -63,33 +102,139 @@
 
 #![deny(missing_docs)]
 #![deny(warnings)]
-#![feature(proc_macro)]
-#![feature(unsize)]
 #![no_std]
 
+#[cfg(feature = "approx")]
+extern crate approx;
+#[cfg(feature = "bytemuck")]
+extern crate bytemuck;
+#[cfg(feature = "cgmath")]
+extern crate cgmath;
+#[cfg(feature = "critical-section")]
+extern crate critical_section;
+#[cfg(feature = "embedded-graphics")]
+extern crate embedded_graphics;
+#[cfg(feature = "fixed")]
+extern crate fixed;
+extern crate generic_array;
+#[cfg(feature = "glam")]
+extern crate glam;
+#[cfg(feature = "half")]
+extern crate half;
+#[cfg(feature = "heapless")]
+extern crate hash32;
+#[cfg(feature = "heapless")]
+extern crate heapless;
+#[cfg(feature = "libm")]
+extern crate libm;
 extern crate mat_macros;
+#[cfg(feature = "micromath")]
+extern crate micromath;
+#[cfg(feature = "nalgebra")]
+extern crate nalgebra;
+#[cfg(feature = "std")]
+extern crate ndarray;
+#[cfg(feature = "num-traits")]
+extern crate num_traits;
+#[cfg(feature = "proptest")]
+#[macro_use]
+extern crate proptest;
+#[cfg(feature = "quickcheck")]
+extern crate quickcheck;
+#[cfg(feature = "rand_core")]
+extern crate rand_core;
+#[cfg(feature = "rkyv")]
+extern crate rkyv;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(any(feature = "proptest", feature = "quickcheck"))]
+extern crate std;
 #[doc(hidden)]
 pub extern crate typenum;
+#[cfg(feature = "ufmt")]
+extern crate ufmt;
+#[cfg(feature = "zerocopy")]
+extern crate zerocopy;
 
 use core::ops;
-use core::marker::{PhantomData, Unsize};
-use core::fmt;
+use core::marker::PhantomData;
+use core::{cmp, fmt, hash, slice};
 
+use generic_array::{ArrayLength, GenericArray};
 pub use mat_macros::mat;
-use typenum::Unsigned;
+use typenum::{IsLess, Min, Prod, True, U1, U2, U3, U4, Unsigned};
+
+// NOTE first so the other modules can use its macro
+#[macro_use]
+mod no_panic;
 
+pub mod activation;
+mod array;
+pub mod block;
+pub mod compare;
+pub mod complex;
+pub mod conv;
+mod diag;
+pub mod diff;
+pub mod dims;
+pub mod double;
+pub mod dual;
+mod error;
+pub mod filter;
+#[cfg(feature = "fusion")]
+pub mod fusion;
+pub mod gemm;
+pub mod geometry;
+pub mod gf2;
+#[cfg(feature = "embedded-graphics")]
+pub mod graphics;
+mod interop;
+pub mod interp;
+pub mod interval;
+mod kernel;
+pub mod linalg;
+pub mod ode;
+pub mod optimize;
+mod permutation;
+pub mod quant;
+mod small;
+mod sparse;
+pub mod stats;
+mod sym;
+#[cfg(feature = "proptest")]
+pub mod strategy;
+mod text;
 pub mod traits;
+mod triangular;
+pub mod window;
 
-use traits::{Matrix, UnsafeGet, Zero};
+pub use diag::{DiagMat, DiagProduct};
+pub use error::Error;
+pub use permutation::Permutation;
+pub use sparse::SparseMat;
+pub use sym::{PackedLen, SymMat};
+pub use triangular::{LowerTriangular, TriangularProduct, UpperTriangular};
+pub use text::ParseError;
+
+use traits::{Conj, Matrix, One, UnsafeGet, Zero};
 
 /// Statically allocated (row major order) matrix
+///
+/// The elements are stored in `BUFFER`, which can be any type that implements `AsRef<[T]>`: the
+/// array created by the `mat!` macro, a borrowed slice or a wrapper that over-aligns an array. See
+/// `Mat::from_buffer`.
+///
+/// Nothing forces an `AsRef` implementation to return a slice of the same length every time, so
+/// element access is bounds checked against the slice it returns: an implementation whose slice
+/// shrinks after `from_buffer` makes accessing the matrix panic.
 #[derive(Clone)]
+#[repr(C)]
 pub struct Mat<T, BUFFER, NROWS, NCOLS>
 where
-    BUFFER: Unsize<[T]>,
+    BUFFER: AsRef<[T]>,
     NCOLS: Unsigned,
     NROWS: Unsigned,
-    T: Copy,
+    T: Clone,
 {
     buffer: BUFFER,
     ty: PhantomData<[T; 0]>,
@@ -97,6 +242,129 @@ where
     ncols: PhantomData<NCOLS>,
 }
 
+/// Statically allocated (row major order) matrix whose buffer is derived from its dimensions
+///
+/// Unlike `Mat` this matrix can be created in generic code, e.g. by evaluating an expression tree
+/// with `Matrix::eval`.
+///
+/// Elements only need to be `Clone`, so wrapper types that own resources or are too big to be
+/// `Copy` can be stored too.
+///
+/// # Example
+///
+/// ```
+/// extern crate mat;
+///
+/// use std::ops;
+///
+/// use mat::Mat2;
+/// use mat::traits::{Matrix, Zero};
+///
+/// // a saturating 16-bit integer that is deliberately not `Copy`
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Sat(i16);
+///
+/// impl ops::Add for Sat {
+///     type Output = Sat;
+///
+///     fn add(self, rhs: Sat) -> Sat {
+///         Sat(self.0.saturating_add(rhs.0))
+///     }
+/// }
+///
+/// impl ops::Mul for Sat {
+///     type Output = Sat;
+///
+///     fn mul(self, rhs: Sat) -> Sat {
+///         Sat(self.0.saturating_mul(rhs.0))
+///     }
+/// }
+///
+/// impl Zero for Sat {
+///     fn zero() -> Sat {
+///         Sat(0)
+///     }
+/// }
+///
+/// # fn main() {
+/// let a = Mat2::from_fn(|r, c| Sat(100 * (r + c) as i16 + 1));
+/// let b = Mat2::scalar_matrix(Sat(200));
+///
+/// let c = (&a * &b).eval();
+/// assert_eq!(c.get(0, 0), Sat(200));
+/// assert_eq!(c.get(1, 1), Sat(i16::max_value()));
+/// # }
+/// ```
+#[repr(C)]
+pub struct MatGen<T, NROWS, NCOLS>
+where
+    NROWS: Unsigned + ops::Mul<NCOLS>,
+    NCOLS: Unsigned,
+    Prod<NROWS, NCOLS>: ArrayLength<T>,
+    T: Clone,
+{
+    buffer: GenericArray<T, Prod<NROWS, NCOLS>>,
+    nrows: PhantomData<NROWS>,
+    ncols: PhantomData<NCOLS>,
+}
+
+impl<T, BUFFER, NROWS, NCOLS> Copy for Mat<T, BUFFER, NROWS, NCOLS>
+where
+    BUFFER: Copy + AsRef<[T]>,
+    NROWS: Copy + Unsigned,
+    NCOLS: Copy + Unsigned,
+    T: Copy,
+{
+}
+
+// NOTE not derived so that every array is built by `array::generate`
+impl<T, NROWS, NCOLS> Clone for MatGen<T, NROWS, NCOLS>
+where
+    NROWS: Unsigned + ops::Mul<NCOLS>,
+    NCOLS: Unsigned,
+    Prod<NROWS, NCOLS>: ArrayLength<T>,
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        let slice = self.as_slice();
+        MatGen::new(array::generate(|i| slice[i].clone()))
+    }
+}
+
+impl<T, NROWS, NCOLS> Copy for MatGen<T, NROWS, NCOLS>
+where
+    NROWS: Copy + Unsigned + ops::Mul<NCOLS>,
+    NCOLS: Copy + Unsigned,
+    Prod<NROWS, NCOLS>: ArrayLength<T>,
+    GenericArray<T, Prod<NROWS, NCOLS>>: Copy,
+    T: Copy,
+{
+}
+
+/// Column vector
+pub type Vector<T, N> = MatGen<T, N, U1>;
+
+/// Row vector
+pub type RowVector<T, N> = MatGen<T, U1, N>;
+
+/// 2 by 2 matrix
+pub type Mat2<T> = MatGen<T, U2, U2>;
+
+/// 3 by 3 matrix
+pub type Mat3<T> = MatGen<T, U3, U3>;
+
+/// 4 by 4 matrix
+pub type Mat4<T> = MatGen<T, U4, U4>;
+
+/// 2 element column vector
+pub type Vec2<T> = Vector<T, U2>;
+
+/// 3 element column vector
+pub type Vec3<T> = Vector<T, U3>;
+
+/// 4 element column vector
+pub type Vec4<T> = Vector<T, U4>;
+
 /// The product of two matrices
 #[derive(Clone, Copy)]
 pub struct Product<L, R> {
@@ -117,12 +385,46 @@ pub struct Transpose<M> {
     m: M,
 }
 
+/// The element-wise complex conjugate of a matrix
+#[derive(Clone, Copy)]
+pub struct Conjugate<M> {
+    m: M,
+}
+
+/// The outer product of two column vectors
+#[derive(Clone, Copy)]
+pub struct Outer<L, R> {
+    l: L,
+    r: R,
+}
+
+/// The affine map `w * x + b`
+#[derive(Clone, Copy)]
+pub struct Affine<W, X, B> {
+    w: W,
+    x: X,
+    b: B,
+}
+
+/// The Kronecker product of two matrices
+#[derive(Clone, Copy)]
+pub struct Kronecker<L, R> {
+    l: L,
+    r: R,
+}
+
+/// Formats the structure of an expression tree; see `Matrix::tree`
+#[derive(Clone, Copy)]
+pub struct Tree<M> {
+    m: M,
+}
+
 impl<T, BUFFER, NROWS, NCOLS> Mat<T, BUFFER, NROWS, NCOLS>
 where
-    BUFFER: Unsize<[T]>,
+    BUFFER: AsRef<[T]>,
     NROWS: Unsigned,
     NCOLS: Unsigned,
-    T: Copy,
+    T: Clone,
 {
     #[doc(hidden)]
     pub unsafe fn new(buffer: BUFFER) -> Self {
@@ -133,64 +435,254 @@ where
             ncols: PhantomData,
         }
     }
+
+    /// Creates a matrix backed by `buffer`, which contains the elements in row major order
+    ///
+    /// Returns `buffer` back if its length is not `NROWS * NCOLS`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate mat;
+    /// extern crate typenum;
+    ///
+    /// use mat::Mat;
+    /// use mat::traits::Matrix;
+    /// use typenum::{U2, U3};
+    ///
+    /// # fn main() {
+    /// let data = [0, 1, 2, 3, 4, 5, 6];
+    ///
+    /// // borrow the last six elements as a 2 by 3 matrix
+    /// let m = Mat::<i32, &[i32], U2, U3>::from_buffer(&data[1..]).unwrap();
+    /// assert_eq!((&m).get(1, 0), 4);
+    ///
+    /// assert!(Mat::<i32, &[i32], U2, U3>::from_buffer(&data[..]).is_err());
+    /// # }
+    /// ```
+    pub fn from_buffer(buffer: BUFFER) -> Result<Self, BUFFER> {
+        if buffer.as_ref().len() != NROWS::to_usize() * NCOLS::to_usize() {
+            return Err(buffer);
+        }
+
+        Ok(unsafe { Mat::new(buffer) })
+    }
+
+    /// Copies the matrix into a `MatGen` of the same element type and dimensions
+    ///
+    /// `MatGen` also implements `From<Mat>`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate mat;
+    ///
+    /// use mat::{mat, Mat2};
+    ///
+    /// # fn main() {
+    /// let m = mat![
+    ///     [1, 2],
+    ///     [3, 4],
+    /// ];
+    ///
+    /// let g: Mat2<i32> = m.to_gen();
+    /// assert_eq!(g, &m);
+    /// assert_eq!(Mat2::from(m), g);
+    /// # }
+    /// ```
+    pub fn to_gen(&self) -> MatGen<T, NROWS, NCOLS>
+    where
+        NROWS: ops::Mul<NCOLS>,
+        Prod<NROWS, NCOLS>: ArrayLength<T>,
+    {
+        let slice: &[T] = self.buffer.as_ref();
+        MatGen::new(array::generate(|i| slice[i].clone()))
+    }
+}
+
+impl<T, BUFFER, NROWS, NCOLS> Mat<T, BUFFER, NROWS, NCOLS>
+where
+    BUFFER: AsRef<[T]> + AsMut<[T]>,
+    NROWS: Unsigned,
+    NCOLS: Unsigned,
+    T: Clone,
+{
+    /// Sets the element at row `r` and column `c` to `value`
+    ///
+    /// Returns `value` back if `r` or `c` exceed the matrix dimensions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate mat;
+    /// extern crate typenum;
+    ///
+    /// use mat::Mat;
+    /// use typenum::U2;
+    ///
+    /// # fn main() {
+    /// let mut data = [0; 4];
+    /// {
+    ///     let mut m = Mat::<i32, &mut [i32], U2, U2>::from_buffer(&mut data[..]).unwrap();
+    ///
+    ///     assert_eq!(m.set_checked(1, 0, 3), Ok(()));
+    ///     assert_eq!(m.set_checked(2, 0, 4), Err(4));
+    /// }
+    /// assert_eq!(data, [0, 0, 3, 0]);
+    /// # }
+    /// ```
+    pub fn set_checked(&mut self, r: usize, c: usize, value: T) -> Result<(), T> {
+        no_panic!(if r < NROWS::to_usize() && c < NCOLS::to_usize() {
+            match self.buffer.as_mut().get_mut(r * NCOLS::to_usize() + c) {
+                Some(elem) => {
+                    *elem = value;
+                    Ok(())
+                }
+                None => Err(value),
+            }
+        } else {
+            Err(value)
+        })
+    }
+}
+
+impl<T, BUFFER, NROWS, NCOLS> From<Mat<T, BUFFER, NROWS, NCOLS>> for MatGen<T, NROWS, NCOLS>
+where
+    BUFFER: AsRef<[T]>,
+    NROWS: Unsigned + ops::Mul<NCOLS>,
+    NCOLS: Unsigned,
+    Prod<NROWS, NCOLS>: ArrayLength<T>,
+    T: Clone,
+{
+    fn from(m: Mat<T, BUFFER, NROWS, NCOLS>) -> Self {
+        m.to_gen()
+    }
 }
 
 impl<T, BUFFER, NROWS, NCOLS> fmt::Debug for Mat<T, BUFFER, NROWS, NCOLS>
 where
-    BUFFER: Unsize<[T]>,
+    BUFFER: AsRef<[T]>,
     NROWS: Unsigned,
     NCOLS: Unsigned,
-    T: Copy + fmt::Debug,
+    T: Clone + fmt::Debug,
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let mut is_first = true;
-        let slice: &[T] = &self.buffer;
-        f.write_str("[")?;
-        for row in slice.chunks(NCOLS::to_usize()) {
-            if is_first {
-                is_first = false;
-            } else {
-                f.write_str(", ")?;
-            }
+        text::debug(f, self)
+    }
+}
 
-            write!(f, "{:?}", row)?;
-        }
-        f.write_str("]")
+impl<T, BUFFER, NROWS, NCOLS> PartialEq for Mat<T, BUFFER, NROWS, NCOLS>
+where
+    BUFFER: AsRef<[T]>,
+    NROWS: Unsigned,
+    NCOLS: Unsigned,
+    T: Clone + PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        let lhs: &[T] = self.buffer.as_ref();
+        let rhs: &[T] = other.buffer.as_ref();
+        lhs == rhs
     }
 }
 
-impl<'a, T, BUFFER, NROWS, NCOLS> Matrix for &'a Mat<T, BUFFER, NROWS, NCOLS>
+impl<T, BUFFER, NROWS, NCOLS> Eq for Mat<T, BUFFER, NROWS, NCOLS>
 where
-    BUFFER: Unsize<[T]>,
+    BUFFER: AsRef<[T]>,
     NROWS: Unsigned,
     NCOLS: Unsigned,
-    T: Copy,
+    T: Clone + Eq,
+{
+}
+
+impl<T, BUFFER, NROWS, NCOLS> hash::Hash for Mat<T, BUFFER, NROWS, NCOLS>
+where
+    BUFFER: AsRef<[T]>,
+    NROWS: Unsigned,
+    NCOLS: Unsigned,
+    T: Clone + hash::Hash,
+{
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: hash::Hasher,
+    {
+        let slice: &[T] = self.buffer.as_ref();
+        slice.hash(state)
+    }
+}
+
+/// Lexicographic comparison of the elements in row major order
+impl<T, BUFFER, NROWS, NCOLS> PartialOrd for Mat<T, BUFFER, NROWS, NCOLS>
+where
+    BUFFER: AsRef<[T]>,
+    NROWS: Unsigned,
+    NCOLS: Unsigned,
+    T: Clone + PartialOrd,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        let lhs: &[T] = self.buffer.as_ref();
+        let rhs: &[T] = other.buffer.as_ref();
+        lhs.partial_cmp(rhs)
+    }
+}
+
+/// Lexicographic comparison of the elements in row major order
+impl<T, BUFFER, NROWS, NCOLS> Ord for Mat<T, BUFFER, NROWS, NCOLS>
+where
+    BUFFER: AsRef<[T]>,
+    NROWS: Unsigned,
+    NCOLS: Unsigned,
+    T: Clone + Ord,
+{
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        let lhs: &[T] = self.buffer.as_ref();
+        let rhs: &[T] = other.buffer.as_ref();
+        lhs.cmp(rhs)
+    }
+}
+
+impl<T, BUFFER, NROWS, NCOLS> Matrix for &Mat<T, BUFFER, NROWS, NCOLS>
+where
+    BUFFER: AsRef<[T]>,
+    NROWS: Unsigned,
+    NCOLS: Unsigned,
+    T: Clone,
 {
     type NROWS = NROWS;
     type NCOLS = NCOLS;
+
+    fn as_row_major(&self) -> Option<&[T]> {
+        let slice: &[T] = self.buffer.as_ref();
+        if slice.len() == NROWS::to_usize() * NCOLS::to_usize() {
+            Some(slice)
+        } else {
+            None
+        }
+    }
 }
 
-impl<'a, T, BUFFER, NROWS, NCOLS> UnsafeGet for &'a Mat<T, BUFFER, NROWS, NCOLS>
+impl<T, BUFFER, NROWS, NCOLS> UnsafeGet for &Mat<T, BUFFER, NROWS, NCOLS>
 where
-    BUFFER: Unsize<[T]>,
+    BUFFER: AsRef<[T]>,
     NROWS: Unsigned,
     NCOLS: Unsigned,
-    T: Copy,
+    T: Clone,
 {
     type Elem = T;
 
     unsafe fn unsafe_get(self, r: usize, c: usize) -> T {
-        let slice: &[T] = &self.buffer;
-        *slice.get_unchecked(r * NCOLS::to_usize() + c)
+        // NOTE `from_buffer` checked the length but `AsRef` is a safe trait, so a later call can
+        // return a shorter slice
+        let slice: &[T] = self.buffer.as_ref();
+        slice[r * NCOLS::to_usize() + c].clone()
     }
 }
 
 impl<'a, T, BUFFER, NROWS, NCOLS, R> ops::Mul<R> for &'a Mat<T, BUFFER, NROWS, NCOLS>
 where
-    BUFFER: Unsize<[T]>,
+    BUFFER: AsRef<[T]>,
     NROWS: Unsigned,
     NCOLS: Unsigned,
-    T: Copy,
+    T: Clone,
     R: Matrix<NROWS = NCOLS>,
 {
     type Output = Product<&'a Mat<T, BUFFER, NROWS, NCOLS>, R>;
@@ -200,6 +692,530 @@ where
     }
 }
 
+/// Returns the outer product, `l rᵀ`, of the column vectors `l` and `r`
+///
+/// Like other operations this is lazy; use `eval` to compute the whole matrix.
+///
+/// # Example
+///
+/// ```
+/// use mat::traits::Matrix;
+///
+/// let a = mat::vec2(1, 2);
+/// let b = mat::vec3(3, 4, 5);
+///
+/// let ab = mat::outer(&a, &b).eval();
+/// assert_eq!(ab.size(), (2, 3));
+/// assert_eq!(ab.get(1, 2), 10);
+/// ```
+pub fn outer<L, R>(l: L, r: R) -> Outer<L, R>
+where
+    L: Matrix<NCOLS = U1>,
+    R: Matrix<Elem = L::Elem, NCOLS = U1>,
+{
+    Outer { l, r }
+}
+
+/// Returns the affine map `w * x + b`
+///
+/// Each element starts with the element of the bias `b` and then accumulates the products, so
+/// the bias costs no extra pass over the result. Like other operations this is lazy; `eval`
+/// computes the whole matrix, with a kernel that works on the buffers directly when all the
+/// operands are `MatGen`s.
+///
+/// # Example
+///
+/// ```
+/// extern crate mat;
+/// extern crate typenum;
+///
+/// use mat::MatGen;
+/// use mat::traits::Matrix;
+/// use typenum::{U1, U2, U3};
+///
+/// # fn main() {
+/// // a dense layer with 3 inputs and 2 outputs
+/// let w = MatGen::<f32, U2, U3>::from_fn(|r, c| (r + c) as f32);
+/// let x = MatGen::<f32, U3, U1>::from_fn(|r, _| r as f32 + 1.);
+/// let b = MatGen::<f32, U2, U1>::from_fn(|r, _| -(r as f32));
+///
+/// let y = mat::affine(&w, &x, &b);
+/// assert_eq!(y.get(1, 0), 1. * 1. + 2. * 2. + 3. * 3. - 1.);
+/// assert_eq!(y.eval(), (&w * &x + &b).eval());
+/// # }
+/// ```
+pub fn affine<W, X, B>(w: W, x: X, b: B) -> Affine<W, X, B>
+where
+    W: Matrix,
+    X: Matrix<Elem = W::Elem, NROWS = W::NCOLS>,
+    B: Matrix<Elem = W::Elem, NROWS = W::NROWS, NCOLS = X::NCOLS>,
+{
+    Affine { w, x, b }
+}
+
+/// Returns the Kronecker product, `l ⊗ r`, of the matrices `l` and `r`
+///
+/// The product of an `M`x`N` matrix and a `P`x`Q` matrix is an `M·P`x`N·Q` block matrix whose block
+/// at row `i` and column `j` is `l[i, j] r`. Like other operations this is lazy; use `eval` to
+/// compute the whole matrix.
+///
+/// # Example
+///
+/// ```
+/// extern crate mat;
+/// extern crate typenum;
+///
+/// use mat::MatGen;
+/// use mat::traits::Matrix;
+/// use typenum::{U1, U2, U3, U6};
+///
+/// # fn main() {
+/// let a = mat::mat2([[1, 2], [3, 4]]);
+/// let b = MatGen::<i32, U1, U3>::from_fn(|_, c| c as i32 + 1);
+///
+/// let ab = mat::kron(&a, &b).eval();
+/// assert_eq!(ab.size(), (2, 6));
+///
+/// let expected = [[1, 2, 3, 2, 4, 6], [3, 6, 9, 4, 8, 12]];
+/// assert_eq!(ab, MatGen::<i32, U2, U6>::from_fn(|r, c| expected[r][c]));
+/// # }
+/// ```
+pub fn kron<L, R>(l: L, r: R) -> Kronecker<L, R>
+where
+    L: Matrix,
+    R: Matrix<Elem = L::Elem>,
+{
+    Kronecker { l, r }
+}
+
+/// Creates a 2 by 2 matrix from its rows
+pub fn mat2<T>(rows: [[T; 2]; 2]) -> Mat2<T>
+where
+    T: Clone,
+{
+    MatGen::from_fn(|r, c| rows[r][c].clone())
+}
+
+/// Creates a 3 by 3 matrix from its rows
+///
+/// # Example
+///
+/// ```
+/// use mat::traits::Matrix;
+///
+/// let m = mat::mat3([
+///     [1, 2, 3],
+///     [4, 5, 6],
+///     [7, 8, 9],
+/// ]);
+/// let v = mat::vec3(1, 0, -1);
+///
+/// let mv = &m * &v;
+/// assert_eq!((mv.get(0, 0), mv.get(1, 0), mv.get(2, 0)), (-2, -2, -2));
+/// ```
+pub fn mat3<T>(rows: [[T; 3]; 3]) -> Mat3<T>
+where
+    T: Clone,
+{
+    MatGen::from_fn(|r, c| rows[r][c].clone())
+}
+
+/// Creates a 4 by 4 matrix from its rows
+pub fn mat4<T>(rows: [[T; 4]; 4]) -> Mat4<T>
+where
+    T: Clone,
+{
+    MatGen::from_fn(|r, c| rows[r][c].clone())
+}
+
+/// Creates a 2 element column vector
+pub fn vec2<T>(x: T, y: T) -> Vec2<T>
+where
+    T: Clone,
+{
+    let elems = [x, y];
+    MatGen::from_fn(|r, _| elems[r].clone())
+}
+
+/// Creates a 3 element column vector
+pub fn vec3<T>(x: T, y: T, z: T) -> Vec3<T>
+where
+    T: Clone,
+{
+    let elems = [x, y, z];
+    MatGen::from_fn(|r, _| elems[r].clone())
+}
+
+/// Creates a 4 element column vector
+pub fn vec4<T>(x: T, y: T, z: T, w: T) -> Vec4<T>
+where
+    T: Clone,
+{
+    let elems = [x, y, z, w];
+    MatGen::from_fn(|r, _| elems[r].clone())
+}
+
+impl<T, NROWS, NCOLS> MatGen<T, NROWS, NCOLS>
+where
+    NROWS: Unsigned + ops::Mul<NCOLS>,
+    NCOLS: Unsigned,
+    Prod<NROWS, NCOLS>: ArrayLength<T>,
+    T: Clone,
+{
+    /// Number of rows
+    ///
+    /// These constants mirror `Matrix::NROWS`, `Matrix::NCOLS` and `Matrix::SHAPE`, which are
+    /// implemented for `&MatGen`, and can be used in constant expressions like array lengths.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mat::{Mat3, Vec4};
+    ///
+    /// let scratch = [0_u8; Mat3::<f32>::NROWS * Vec4::<f32>::NROWS];
+    /// assert_eq!(scratch.len(), 12);
+    /// assert_eq!(Mat3::<f32>::SHAPE, (3, 3));
+    /// ```
+    pub const NROWS: usize = NROWS::USIZE;
+
+    /// Number of columns
+    pub const NCOLS: usize = NCOLS::USIZE;
+
+    /// Number of rows and columns
+    pub const SHAPE: (usize, usize) = (NROWS::USIZE, NCOLS::USIZE);
+
+    /// Creates a matrix from a row major order buffer
+    pub fn new(buffer: GenericArray<T, Prod<NROWS, NCOLS>>) -> Self {
+        MatGen {
+            buffer,
+            nrows: PhantomData,
+            ncols: PhantomData,
+        }
+    }
+
+    /// Views the buffer as a slice
+    fn as_slice(&self) -> &[T] {
+        array::as_slice(&self.buffer)
+    }
+
+    /// Views the buffer as a mutable slice
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        array::as_mut_slice(&mut self.buffer)
+    }
+
+    /// Creates a matrix where the element at row `r` and column `c` is `f(r, c)`
+    pub fn from_fn<F>(f: F) -> Self
+    where
+        F: Fn(usize, usize) -> T,
+    {
+        let ncols = NCOLS::to_usize();
+        MatGen::new(array::generate(|i| f(i / ncols, i % ncols)))
+    }
+
+    /// Creates a matrix filled with zeros
+    pub fn zeros() -> Self
+    where
+        T: Zero,
+    {
+        MatGen::from_fn(|_, _| T::zero())
+    }
+
+    /// Creates a matrix with ones on the main diagonal and zeros elsewhere
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mat::Mat3;
+    /// use mat::traits::Matrix;
+    ///
+    /// let i = Mat3::<i32>::identity();
+    /// let s = Mat3::scalar_matrix(5);
+    ///
+    /// assert_eq!((&s * &i).get(1, 1), 5);
+    /// assert_eq!((&s * &i).get(1, 2), 0);
+    /// ```
+    pub fn identity() -> Self
+    where
+        T: One + Zero,
+    {
+        MatGen::scalar_matrix(T::one())
+    }
+
+    /// Creates a matrix with `c` on the main diagonal and zeros elsewhere
+    pub fn scalar_matrix(c: T) -> Self
+    where
+        T: Zero,
+    {
+        MatGen::from_fn(|r, col| if r == col { c.clone() } else { T::zero() })
+    }
+
+    /// Sets the element at row `r` and column `c` to `value`
+    ///
+    /// # Panics
+    ///
+    /// This operation panics if `r` or `c` exceed the matrix dimensions
+    pub fn set(&mut self, r: usize, c: usize, value: T) {
+        assert!(r < NROWS::to_usize() && c < NCOLS::to_usize());
+
+        self.buffer[r * NCOLS::to_usize() + c] = value;
+    }
+
+    /// Sets the element at row `R` and column `C` to `value`
+    ///
+    /// Like `Matrix::get_at`, the indices are checked at compile time.
+    pub fn set_at<R, C>(&mut self, value: T)
+    where
+        R: IsLess<NROWS, Output = True> + Unsigned,
+        C: IsLess<NCOLS, Output = True> + Unsigned,
+    {
+        let i = R::to_usize() * NCOLS::to_usize() + C::to_usize();
+        unsafe { *self.buffer.get_unchecked_mut(i) = value }
+    }
+
+    /// Sets the element at row `r` and column `c` to `value`
+    ///
+    /// Returns `value` back if `r` or `c` exceed the matrix dimensions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mat::Mat2;
+    ///
+    /// let mut m = Mat2::<i32>::zeros();
+    ///
+    /// assert_eq!(m.set_checked(1, 0, 3), Ok(()));
+    /// assert_eq!(m.set_checked(0, 2, 4), Err(4));
+    /// assert_eq!(m, mat::mat2([[0, 0], [3, 0]]));
+    /// ```
+    pub fn set_checked(&mut self, r: usize, c: usize, value: T) -> Result<(), T> {
+        no_panic!(if r < NROWS::to_usize() && c < NCOLS::to_usize() {
+            self.as_mut_slice()[r * NCOLS::to_usize() + c] = value;
+            Ok(())
+        } else {
+            Err(value)
+        })
+    }
+
+    /// Returns the transpose of the matrix as a new matrix
+    ///
+    /// Unlike the lazy `Transpose::t`, this copies the elements once so reading the transpose
+    /// many times doesn't pay for the strided access of the transposed view.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate mat;
+    /// extern crate typenum;
+    ///
+    /// use mat::MatGen;
+    /// use mat::traits::Transpose;
+    /// use typenum::{U2, U3};
+    ///
+    /// # fn main() {
+    /// let m = MatGen::<i32, U2, U3>::from_fn(|r, c| (3 * r + c) as i32);
+    ///
+    /// let t: MatGen<i32, U3, U2> = m.transposed();
+    /// assert_eq!(t, (&m).t());
+    /// assert_eq!(t.transposed(), m);
+    /// # }
+    /// ```
+    pub fn transposed(&self) -> MatGen<T, NCOLS, NROWS>
+    where
+        NCOLS: ops::Mul<NROWS>,
+        Prod<NCOLS, NROWS>: ArrayLength<T>,
+    {
+        let ncols = NCOLS::to_usize();
+        MatGen::from_fn(|r, c| self.buffer[c * ncols + r].clone())
+    }
+}
+
+/// Vector API
+///
+/// These methods are only available on `Vector`s and `RowVector`s
+///
+/// # Example
+///
+/// ```
+/// extern crate mat;
+/// extern crate typenum;
+///
+/// use mat::Vector;
+/// use typenum::U3;
+///
+/// # fn main() {
+/// let mut v = Vector::<i32, U3>::from_fn(|i, _| i as i32);
+/// v[2] = 4;
+///
+/// assert_eq!(v.get(1), 1);
+/// assert_eq!(v.iter().sum::<i32>(), 5);
+/// # }
+/// ```
+impl<T, NROWS, NCOLS> MatGen<T, NROWS, NCOLS>
+where
+    NROWS: Unsigned + ops::Mul<NCOLS> + Min<NCOLS, Output = U1>,
+    NCOLS: Unsigned,
+    Prod<NROWS, NCOLS>: ArrayLength<T>,
+    T: Clone,
+{
+    /// Returns the `i`-th element of the vector
+    ///
+    /// # Panics
+    ///
+    /// This operation panics if `i` exceeds the vector length
+    pub fn get(&self, i: usize) -> T {
+        self.as_slice()[i].clone()
+    }
+
+    /// Returns an iterator over the elements of the vector
+    pub fn iter<'a>(&'a self) -> slice::Iter<'a, T> {
+        self.as_slice().iter()
+    }
+}
+
+impl<T, NROWS, NCOLS> ops::Index<usize> for MatGen<T, NROWS, NCOLS>
+where
+    NROWS: Unsigned + ops::Mul<NCOLS> + Min<NCOLS, Output = U1>,
+    NCOLS: Unsigned,
+    Prod<NROWS, NCOLS>: ArrayLength<T>,
+    T: Clone,
+{
+    type Output = T;
+
+    fn index(&self, i: usize) -> &T {
+        &self.buffer[i]
+    }
+}
+
+impl<T, NROWS, NCOLS> ops::IndexMut<usize> for MatGen<T, NROWS, NCOLS>
+where
+    NROWS: Unsigned + ops::Mul<NCOLS> + Min<NCOLS, Output = U1>,
+    NCOLS: Unsigned,
+    Prod<NROWS, NCOLS>: ArrayLength<T>,
+    T: Clone,
+{
+    fn index_mut(&mut self, i: usize) -> &mut T {
+        &mut self.buffer[i]
+    }
+}
+
+impl<T, NROWS, NCOLS> fmt::Debug for MatGen<T, NROWS, NCOLS>
+where
+    NROWS: Unsigned + ops::Mul<NCOLS>,
+    NCOLS: Unsigned,
+    Prod<NROWS, NCOLS>: ArrayLength<T>,
+    T: Clone + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        text::debug(f, self)
+    }
+}
+
+impl<T, NROWS, NCOLS> PartialEq for MatGen<T, NROWS, NCOLS>
+where
+    NROWS: Unsigned + ops::Mul<NCOLS>,
+    NCOLS: Unsigned,
+    Prod<NROWS, NCOLS>: ArrayLength<T>,
+    T: Clone + PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<T, NROWS, NCOLS> Eq for MatGen<T, NROWS, NCOLS>
+where
+    NROWS: Unsigned + ops::Mul<NCOLS>,
+    NCOLS: Unsigned,
+    Prod<NROWS, NCOLS>: ArrayLength<T>,
+    T: Clone + Eq,
+{
+}
+
+impl<T, NROWS, NCOLS> hash::Hash for MatGen<T, NROWS, NCOLS>
+where
+    NROWS: Unsigned + ops::Mul<NCOLS>,
+    NCOLS: Unsigned,
+    Prod<NROWS, NCOLS>: ArrayLength<T>,
+    T: Clone + hash::Hash,
+{
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: hash::Hasher,
+    {
+        self.as_slice().hash(state)
+    }
+}
+
+/// Lexicographic comparison of the elements in row major order
+impl<T, NROWS, NCOLS> PartialOrd for MatGen<T, NROWS, NCOLS>
+where
+    NROWS: Unsigned + ops::Mul<NCOLS>,
+    NCOLS: Unsigned,
+    Prod<NROWS, NCOLS>: ArrayLength<T>,
+    T: Clone + PartialOrd,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        self.as_slice().partial_cmp(other.as_slice())
+    }
+}
+
+/// Lexicographic comparison of the elements in row major order
+impl<T, NROWS, NCOLS> Ord for MatGen<T, NROWS, NCOLS>
+where
+    NROWS: Unsigned + ops::Mul<NCOLS>,
+    NCOLS: Unsigned,
+    Prod<NROWS, NCOLS>: ArrayLength<T>,
+    T: Clone + Ord,
+{
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.as_slice().cmp(other.as_slice())
+    }
+}
+
+impl<T, NROWS, NCOLS> Matrix for &MatGen<T, NROWS, NCOLS>
+where
+    NROWS: Unsigned + ops::Mul<NCOLS>,
+    NCOLS: Unsigned,
+    Prod<NROWS, NCOLS>: ArrayLength<T>,
+    T: Clone,
+{
+    type NROWS = NROWS;
+    type NCOLS = NCOLS;
+
+    fn as_row_major(&self) -> Option<&[T]> {
+        Some(self.as_slice())
+    }
+}
+
+impl<T, NROWS, NCOLS> UnsafeGet for &MatGen<T, NROWS, NCOLS>
+where
+    NROWS: Unsigned + ops::Mul<NCOLS>,
+    NCOLS: Unsigned,
+    Prod<NROWS, NCOLS>: ArrayLength<T>,
+    T: Clone,
+{
+    type Elem = T;
+
+    unsafe fn unsafe_get(self, r: usize, c: usize) -> T {
+        self.buffer.get_unchecked(r * NCOLS::to_usize() + c).clone()
+    }
+}
+
+impl<'a, T, NROWS, NCOLS, R> ops::Mul<R> for &'a MatGen<T, NROWS, NCOLS>
+where
+    NROWS: Unsigned + ops::Mul<NCOLS>,
+    NCOLS: Unsigned,
+    Prod<NROWS, NCOLS>: ArrayLength<T>,
+    T: Clone,
+    R: Matrix<NROWS = NCOLS>,
+{
+    type Output = Product<&'a MatGen<T, NROWS, NCOLS>, R>;
+
+    fn mul(self, rhs: R) -> Self::Output {
+        Product { l: self, r: rhs }
+    }
+}
+
 impl<M> traits::Transpose for M
 where
     M: Matrix,
@@ -213,6 +1229,12 @@ where
     // NOTE reversed size!
     type NROWS = M::NCOLS;
     type NCOLS = M::NROWS;
+
+    fn fmt_tree(self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("T(")?;
+        self.m.fmt_tree(f)?;
+        f.write_str(")")
+    }
 }
 
 impl<M> UnsafeGet for Transpose<M>
@@ -239,21 +1261,65 @@ where
     }
 }
 
+impl<M> Matrix for Conjugate<M>
+where
+    M: Matrix,
+    M::Elem: Conj,
+{
+    type NROWS = M::NROWS;
+    type NCOLS = M::NCOLS;
+
+    fn fmt_tree(self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Conj(")?;
+        self.m.fmt_tree(f)?;
+        f.write_str(")")
+    }
+}
+
+impl<M> UnsafeGet for Conjugate<M>
+where
+    M: Matrix,
+    M::Elem: Conj,
+{
+    type Elem = M::Elem;
+
+    unsafe fn unsafe_get(self, r: usize, c: usize) -> M::Elem {
+        self.m.unsafe_get(r, c).conj()
+    }
+}
+
+impl<L, R> ops::Mul<R> for Conjugate<L>
+where
+    L: Matrix,
+    L::Elem: Conj,
+    R: Matrix<NROWS = L::NCOLS>,
+{
+    type Output = Product<Conjugate<L>, R>;
+
+    fn mul(self, rhs: R) -> Self::Output {
+        Product { l: self, r: rhs }
+    }
+}
+
 impl<L, R, T> Matrix for Product<L, R>
 where
     L: Matrix<Elem = T>,
     R: Matrix<Elem = T>,
-    T: ops::Add<T, Output = T> + ops::Mul<T, Output = T> + Copy + Zero,
+    T: ops::Add<T, Output = T> + ops::Mul<T, Output = T> + Clone + Zero,
 {
     type NROWS = L::NROWS;
     type NCOLS = R::NCOLS;
+
+    fn fmt_tree(self, f: &mut fmt::Formatter) -> fmt::Result {
+        text::fmt_node(f, "Mul", self.l, self.r)
+    }
 }
 
 impl<T, L, R> UnsafeGet for Product<L, R>
 where
     L: Matrix<Elem = T>,
     R: Matrix<Elem = T>,
-    T: ops::Add<T, Output = T> + ops::Mul<T, Output = T> + Copy + Zero,
+    T: ops::Add<T, Output = T> + ops::Mul<T, Output = T> + Clone + Zero,
 {
     type Elem = T;
 
@@ -266,6 +1332,124 @@ where
     }
 }
 
+impl<T, L, R> Product<L, R>
+where
+    L: Matrix<Elem = T>,
+    R: Matrix<Elem = T, NROWS = L::NCOLS>,
+    T: ops::Add<T, Output = T> + ops::Mul<T, Output = T> + Clone + Zero,
+{
+    /// Evaluates the product into a new matrix
+    ///
+    /// This shadows `Matrix::eval`. If both operands store their elements contiguously, i.e. they
+    /// are `MatGen` or `Mat` matrices, and the element type has a `gemm::DefaultBackend`, the
+    /// product is computed by that backend, e.g. CMSIS-DSP for `f32` when the `cmsis-dsp` feature
+    /// is enabled.
+    ///
+    /// Otherwise the result is computed in 4x4 tiles: each step of the inner loop reads 4 elements
+    /// of `L` and 4 elements of `R` and updates 16 accumulators, instead of reading 2 elements per
+    /// multiplication. Each element is still accumulated in the same order so the result is the
+    /// same as evaluating the elements one by one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate mat;
+    /// extern crate typenum;
+    ///
+    /// use mat::MatGen;
+    /// use mat::traits::{Matrix, UnsafeGet};
+    /// use typenum::{U6, U7, U9};
+    ///
+    /// # fn main() {
+    /// let a = MatGen::<f32, U7, U6>::from_fn(|r, c| (r as f32 - 0.5 * c as f32).sin());
+    /// let b = MatGen::<f32, U6, U9>::from_fn(|r, c| (r * c) as f32 / 7.);
+    ///
+    /// let p = &a * &b;
+    /// let c = p.eval();
+    /// for r in 0..7 {
+    ///     for col in 0..9 {
+    ///         assert_eq!((&c).get(r, col), unsafe { p.unsafe_get(r, col) });
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub fn eval(self) -> MatGen<T, L::NROWS, R::NCOLS>
+    where
+        L::NROWS: ops::Mul<R::NCOLS>,
+        Prod<L::NROWS, R::NCOLS>: ArrayLength<T>,
+    {
+        const TILE: usize = 4;
+
+        let (nrows, ncols, n) = (self.l.nrows(), self.r.ncols(), self.l.ncols());
+        let (l, r) = (self.l, self.r);
+        let mut out: MatGen<T, L::NROWS, R::NCOLS> = MatGen::zeros();
+
+        if let (Some(a), Some(b)) = (l.as_row_major(), r.as_row_major()) {
+            if T::gemm(a, b, out.as_mut_slice(), nrows, n, ncols) {
+                return out;
+            }
+        }
+
+        let mut r0 = 0;
+        while r0 + TILE <= nrows {
+            let mut c0 = 0;
+            while c0 + TILE <= ncols {
+                let mut acc = [
+                    [T::zero(), T::zero(), T::zero(), T::zero()],
+                    [T::zero(), T::zero(), T::zero(), T::zero()],
+                    [T::zero(), T::zero(), T::zero(), T::zero()],
+                    [T::zero(), T::zero(), T::zero(), T::zero()],
+                ];
+                for i in 0..n {
+                    let (a, b) = unsafe {
+                        (
+                            [
+                                l.unsafe_get(r0, i),
+                                l.unsafe_get(r0 + 1, i),
+                                l.unsafe_get(r0 + 2, i),
+                                l.unsafe_get(r0 + 3, i),
+                            ],
+                            [
+                                r.unsafe_get(i, c0),
+                                r.unsafe_get(i, c0 + 1),
+                                r.unsafe_get(i, c0 + 2),
+                                r.unsafe_get(i, c0 + 3),
+                            ],
+                        )
+                    };
+                    for (row, a) in acc.iter_mut().zip(a.iter()) {
+                        for (sum, b) in row.iter_mut().zip(b.iter()) {
+                            *sum = sum.clone() + a.clone() * b.clone();
+                        }
+                    }
+                }
+                for (dr, row) in acc.iter().enumerate() {
+                    let start = (r0 + dr) * ncols + c0;
+                    out.buffer[start..start + TILE].clone_from_slice(row);
+                }
+                c0 += TILE;
+            }
+
+            // the columns that don't fill a tile
+            for row in r0..r0 + TILE {
+                for col in c0..ncols {
+                    out.buffer[row * ncols + col] = unsafe { self.unsafe_get(row, col) };
+                }
+            }
+            r0 += TILE;
+        }
+
+        // the rows that don't fill a tile
+        for row in r0..nrows {
+            for col in 0..ncols {
+                out.buffer[row * ncols + col] = unsafe { self.unsafe_get(row, col) };
+            }
+        }
+
+        out
+    }
+}
+
 impl<L, R, RHS> ops::Add<RHS> for Product<L, R>
 where
     L: Matrix,
@@ -283,17 +1467,21 @@ impl<T, L, R> Matrix for Sum<L, R>
 where
     L: Matrix<Elem = T>,
     R: Matrix<Elem = T>,
-    T: ops::Add<T, Output = T> + Copy,
+    T: ops::Add<T, Output = T> + Clone,
 {
     type NROWS = L::NROWS;
     type NCOLS = L::NCOLS;
+
+    fn fmt_tree(self, f: &mut fmt::Formatter) -> fmt::Result {
+        text::fmt_node(f, "Add", self.l, self.r)
+    }
 }
 
 impl<T, L, R> UnsafeGet for Sum<L, R>
 where
     L: Matrix<Elem = T>,
     R: Matrix<Elem = T>,
-    T: ops::Add<T, Output = T> + Copy,
+    T: ops::Add<T, Output = T> + Clone,
 {
     type Elem = T;
 
@@ -301,3 +1489,304 @@ where
         self.l.unsafe_get(r, c) + self.r.unsafe_get(r, c)
     }
 }
+
+impl<L, R, T> Matrix for Outer<L, R>
+where
+    L: Matrix<Elem = T, NCOLS = U1>,
+    R: Matrix<Elem = T, NCOLS = U1>,
+    T: ops::Mul<T, Output = T> + Clone,
+{
+    type NROWS = L::NROWS;
+    type NCOLS = R::NROWS;
+
+    fn fmt_tree(self, f: &mut fmt::Formatter) -> fmt::Result {
+        text::fmt_node(f, "Outer", self.l, self.r)
+    }
+}
+
+impl<L, R, T> UnsafeGet for Outer<L, R>
+where
+    L: Matrix<Elem = T, NCOLS = U1>,
+    R: Matrix<Elem = T, NCOLS = U1>,
+    T: ops::Mul<T, Output = T> + Clone,
+{
+    type Elem = T;
+
+    unsafe fn unsafe_get(self, r: usize, c: usize) -> T {
+        self.l.unsafe_get(r, 0) * self.r.unsafe_get(c, 0)
+    }
+}
+
+impl<L, R, RHS> ops::Mul<RHS> for Outer<L, R>
+where
+    L: Matrix<NCOLS = U1>,
+    R: Matrix<NCOLS = U1>,
+    RHS: Matrix<NROWS = R::NROWS>,
+{
+    type Output = Product<Outer<L, R>, RHS>;
+
+    fn mul(self, rhs: RHS) -> Self::Output {
+        Product { l: self, r: rhs }
+    }
+}
+
+impl<L, R, RHS> ops::Add<RHS> for Outer<L, R>
+where
+    L: Matrix<NCOLS = U1>,
+    R: Matrix<NCOLS = U1>,
+    RHS: Matrix<NROWS = L::NROWS, NCOLS = R::NROWS>,
+{
+    type Output = Sum<Outer<L, R>, RHS>;
+
+    fn add(self, rhs: RHS) -> Self::Output {
+        Sum { l: self, r: rhs }
+    }
+}
+
+impl<W, X, B, T> Matrix for Affine<W, X, B>
+where
+    W: Matrix<Elem = T>,
+    X: Matrix<Elem = T, NROWS = W::NCOLS>,
+    B: Matrix<Elem = T, NROWS = W::NROWS, NCOLS = X::NCOLS>,
+    T: ops::Add<T, Output = T> + ops::Mul<T, Output = T> + Clone,
+{
+    type NROWS = W::NROWS;
+    type NCOLS = X::NCOLS;
+
+    fn fmt_tree(self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Affine(")?;
+        self.w.fmt_tree(f)?;
+        f.write_str(", ")?;
+        self.x.fmt_tree(f)?;
+        f.write_str(", ")?;
+        self.b.fmt_tree(f)?;
+        f.write_str(")")
+    }
+}
+
+impl<W, X, B, T> UnsafeGet for Affine<W, X, B>
+where
+    W: Matrix<Elem = T>,
+    X: Matrix<Elem = T, NROWS = W::NCOLS>,
+    B: Matrix<Elem = T, NROWS = W::NROWS, NCOLS = X::NCOLS>,
+    T: ops::Add<T, Output = T> + ops::Mul<T, Output = T> + Clone,
+{
+    type Elem = T;
+
+    unsafe fn unsafe_get(self, r: usize, c: usize) -> T {
+        let mut sum = self.b.unsafe_get(r, c);
+        for i in 0..self.w.ncols() {
+            sum = sum + self.w.unsafe_get(r, i) * self.x.unsafe_get(i, c);
+        }
+        sum
+    }
+}
+
+impl<T, M, K, N> Affine<&MatGen<T, M, K>, &MatGen<T, K, N>, &MatGen<T, M, N>>
+where
+    M: Unsigned + ops::Mul<K> + ops::Mul<N>,
+    K: Unsigned + ops::Mul<N>,
+    N: Unsigned,
+    Prod<M, K>: ArrayLength<T>,
+    Prod<K, N>: ArrayLength<T>,
+    Prod<M, N>: ArrayLength<T>,
+    T: ops::Add<T, Output = T> + ops::Mul<T, Output = T> + Clone,
+{
+    /// Evaluates the affine map into a new matrix
+    ///
+    /// This shadows `Matrix::eval` and runs a kernel over the row major buffers of the operands
+    pub fn eval(self) -> MatGen<T, M, N> {
+        let (m, k, n) = (M::to_usize(), K::to_usize(), N::to_usize());
+        // NOTE the kernel accumulates on top of the bias
+        let mut out = self.b.clone();
+        kernel::affine(self.w.as_slice(), self.x.as_slice(), out.as_mut_slice(), m, k, n);
+        out
+    }
+}
+
+impl<W, X, B, RHS> ops::Mul<RHS> for Affine<W, X, B>
+where
+    Affine<W, X, B>: Matrix,
+    RHS: Matrix<NROWS = <Affine<W, X, B> as Matrix>::NCOLS>,
+{
+    type Output = Product<Affine<W, X, B>, RHS>;
+
+    fn mul(self, rhs: RHS) -> Self::Output {
+        Product { l: self, r: rhs }
+    }
+}
+
+impl<W, X, B, RHS> ops::Add<RHS> for Affine<W, X, B>
+where
+    Affine<W, X, B>: Matrix,
+    RHS: Matrix<
+        NROWS = <Affine<W, X, B> as Matrix>::NROWS,
+        NCOLS = <Affine<W, X, B> as Matrix>::NCOLS,
+    >,
+{
+    type Output = Sum<Affine<W, X, B>, RHS>;
+
+    fn add(self, rhs: RHS) -> Self::Output {
+        Sum { l: self, r: rhs }
+    }
+}
+
+impl<L, R, T> Matrix for Kronecker<L, R>
+where
+    L: Matrix<Elem = T>,
+    R: Matrix<Elem = T>,
+    L::NROWS: ops::Mul<R::NROWS>,
+    L::NCOLS: ops::Mul<R::NCOLS>,
+    Prod<L::NROWS, R::NROWS>: Unsigned,
+    Prod<L::NCOLS, R::NCOLS>: Unsigned,
+    T: ops::Mul<T, Output = T> + Clone,
+{
+    type NROWS = Prod<L::NROWS, R::NROWS>;
+    type NCOLS = Prod<L::NCOLS, R::NCOLS>;
+
+    fn fmt_tree(self, f: &mut fmt::Formatter) -> fmt::Result {
+        text::fmt_node(f, "Kron", self.l, self.r)
+    }
+}
+
+impl<L, R, T> UnsafeGet for Kronecker<L, R>
+where
+    L: Matrix<Elem = T>,
+    R: Matrix<Elem = T>,
+    L::NROWS: ops::Mul<R::NROWS>,
+    L::NCOLS: ops::Mul<R::NCOLS>,
+    Prod<L::NROWS, R::NROWS>: Unsigned,
+    Prod<L::NCOLS, R::NCOLS>: Unsigned,
+    T: ops::Mul<T, Output = T> + Clone,
+{
+    type Elem = T;
+
+    unsafe fn unsafe_get(self, r: usize, c: usize) -> T {
+        let (nrows, ncols) = self.r.size();
+        self.l.unsafe_get(r / nrows, c / ncols) * self.r.unsafe_get(r % nrows, c % ncols)
+    }
+}
+
+impl<L, R, RHS> ops::Mul<RHS> for Kronecker<L, R>
+where
+    Kronecker<L, R>: Matrix,
+    RHS: Matrix<NROWS = <Kronecker<L, R> as Matrix>::NCOLS>,
+{
+    type Output = Product<Kronecker<L, R>, RHS>;
+
+    fn mul(self, rhs: RHS) -> Self::Output {
+        Product { l: self, r: rhs }
+    }
+}
+
+impl<L, R, RHS> ops::Add<RHS> for Kronecker<L, R>
+where
+    Kronecker<L, R>: Matrix,
+    RHS: Matrix<
+        NROWS = <Kronecker<L, R> as Matrix>::NROWS,
+        NCOLS = <Kronecker<L, R> as Matrix>::NCOLS,
+    >,
+{
+    type Output = Sum<Kronecker<L, R>, RHS>;
+
+    fn add(self, rhs: RHS) -> Self::Output {
+        Sum { l: self, r: rhs }
+    }
+}
+
+/// Element-wise comparison with a matrix expression of the same dimensions
+///
+/// # Example
+///
+/// ```
+/// extern crate mat;
+///
+/// use mat::mat;
+/// use mat::traits::Transpose;
+///
+/// # fn main() {
+/// let a = mat![
+///     [1, 2],
+///     [3, 4],
+/// ];
+///
+/// let expected = mat![
+///     [5, 11],
+///     [11, 25],
+/// ];
+///
+/// assert_eq!(expected, &a * a.t());
+/// assert_eq!(&a * a.t(), &expected);
+/// assert_ne!(a, a.t());
+/// # }
+/// ```
+impl<T, BUFFER, NROWS, NCOLS, RHS> PartialEq<RHS> for Mat<T, BUFFER, NROWS, NCOLS>
+where
+    BUFFER: AsRef<[T]>,
+    NROWS: Unsigned,
+    NCOLS: Unsigned,
+    RHS: Matrix<NROWS = NROWS, NCOLS = NCOLS>,
+    T: Clone + PartialEq<RHS::Elem>,
+{
+    fn eq(&self, other: &RHS) -> bool {
+        eq(self, *other)
+    }
+}
+
+/// Element-wise comparison with a matrix expression of the same dimensions
+impl<T, NROWS, NCOLS, RHS> PartialEq<RHS> for MatGen<T, NROWS, NCOLS>
+where
+    NROWS: Unsigned + ops::Mul<NCOLS>,
+    NCOLS: Unsigned,
+    Prod<NROWS, NCOLS>: ArrayLength<T>,
+    RHS: Matrix<NROWS = NROWS, NCOLS = NCOLS>,
+    T: Clone + PartialEq<RHS::Elem>,
+{
+    fn eq(&self, other: &RHS) -> bool {
+        eq(self, *other)
+    }
+}
+
+macro_rules! eq {
+    ($($Node:ident<$($P:ident),+>,)+) => {
+        $(
+            /// Element-wise comparison of the matrix the expression evaluates to with a matrix
+            /// expression of the same dimensions
+            impl<$($P,)+ RHS> PartialEq<RHS> for $Node<$($P),+>
+            where
+                $Node<$($P),+>: Matrix,
+                RHS: Matrix<
+                    NROWS = <$Node<$($P),+> as Matrix>::NROWS,
+                    NCOLS = <$Node<$($P),+> as Matrix>::NCOLS,
+                >,
+                <$Node<$($P),+> as UnsafeGet>::Elem: PartialEq<RHS::Elem>,
+            {
+                fn eq(&self, other: &RHS) -> bool {
+                    eq(*self, *other)
+                }
+            }
+        )+
+    }
+}
+
+eq!(
+    Affine<W, X, B>,
+    Conjugate<M>,
+    DiagProduct<D, M>,
+    Kronecker<L, R>,
+    Outer<L, R>,
+    Product<L, R>,
+    Sum<L, R>,
+    Transpose<M>,
+    TriangularProduct<L, R>,
+);
+
+fn eq<L, R>(l: L, r: R) -> bool
+where
+    L: Matrix,
+    R: Matrix<NROWS = L::NROWS, NCOLS = L::NCOLS>,
+    L::Elem: PartialEq<R::Elem>,
+{
+    let (nrows, ncols) = l.size();
+    (0..nrows).all(|i| (0..ncols).all(|j| unsafe { l.unsafe_get(i, j) == r.unsafe_get(i, j) }))
+}
@@ -0,0 +1,756 @@
+//! Linear algebra routines
+//!
+//! # Examples
+//!
+//! Solve a linear system
+//!
+//! ```
+//! use mat::linalg;
+//! use mat::mat;
+//!
+//! let a = mat![
+//!     [2., 1.],
+//!     [1., 3.],
+//! ];
+//!
+//! let b = mat![
+//!     [3.],
+//!     [5.],
+//! ];
+//!
+//! let x = linalg::solve(&a, &b).unwrap();
+//!
+//! assert!((x[0] - 0.8_f64).abs() < 1e-12);
+//! assert!((x[1] - 1.4_f64).abs() < 1e-12);
+//! ```
+//!
+//! Orthonormalize the columns of a matrix
+//!
+//! ```
+//! use mat::linalg;
+//! use mat::mat;
+//! use mat::traits::{Matrix, Transpose};
+//!
+//! let a = mat![
+//!     [1., 1.],
+//!     [0., 1.],
+//!     [1., 0.],
+//! ];
+//!
+//! let q = linalg::orthonormalize(&a).unwrap();
+//!
+//! // Qᵀ Q = I
+//! let qtq = (&q).t() * &q;
+//! for r in 0..2 {
+//!     for c in 0..2 {
+//!         let expected: f64 = if r == c { 1. } else { 0. };
+//!         assert!((qtq.get(r, c) - expected).abs() < 1e-12);
+//!     }
+//! }
+//! ```
+
+use core::ops;
+
+use generic_array::ArrayLength;
+use typenum::{Add1, B1, IsLessOrEqual, Prod, True, U1, Unsigned};
+
+use {kernel, Error, MatGen, Vector};
+use traits::{Ln, Matrix, Sqrt, Transpose, Zero};
+
+/// A Givens rotation
+///
+/// This is the rotation `G = [[c, s], [-s, c]]` acting on a pair of rows or columns.
+#[derive(Clone, Copy, Debug)]
+pub struct Givens<T> {
+    c: T,
+    s: T,
+}
+
+/// A Householder reflector
+///
+/// This is the reflection `H = I - beta v vᵀ` of an `N`-dimensional space.
+#[derive(Clone, Debug)]
+pub struct Householder<T, N>
+where
+    N: Unsigned + ops::Mul<U1>,
+    Prod<N, U1>: ArrayLength<T>,
+    T: Copy,
+{
+    v: Vector<T, N>,
+    beta: T,
+}
+
+/// Returns the Givens rotation that maps the vector `[a, b]` to `[r, 0]`
+///
+/// # Example
+///
+/// ```
+/// extern crate mat;
+/// extern crate typenum;
+///
+/// use mat::{linalg, MatGen};
+/// use mat::traits::Matrix;
+/// use typenum::U2;
+///
+/// # fn main() {
+/// let mut m = MatGen::<f64, U2, U2>::from_fn(|r, c| [[3., 1.], [4., 2.]][r][c]);
+///
+/// let g = linalg::givens(m.get(0, 0), m.get(1, 0));
+/// g.apply_left(&mut m, 0, 1);
+///
+/// assert!((m.get(0, 0) - 5.).abs() < 1e-12);
+/// assert!(m.get(1, 0).abs() < 1e-12);
+/// # }
+/// ```
+pub fn givens<T>(a: T, b: T) -> Givens<T>
+where
+    T: ops::Add<T, Output = T>
+        + ops::Div<T, Output = T>
+        + ops::Mul<T, Output = T>
+        + Copy
+        + From<u8>
+        + PartialEq
+        + Sqrt
+        + Zero,
+{
+    let r = (a * a + b * b).sqrt();
+
+    if r == T::zero() {
+        Givens {
+            c: T::from(1),
+            s: T::zero(),
+        }
+    } else {
+        Givens { c: a / r, s: b / r }
+    }
+}
+
+impl<T> Givens<T>
+where
+    T: ops::Add<T, Output = T>
+        + ops::Mul<T, Output = T>
+        + ops::Sub<T, Output = T>
+        + Copy,
+{
+    /// Returns the cosine of the rotation angle
+    pub fn c(&self) -> T {
+        self.c
+    }
+
+    /// Returns the sine of the rotation angle
+    pub fn s(&self) -> T {
+        self.s
+    }
+
+    /// Rotates rows `i` and `k` of `m`, i.e. `m ← G m`
+    ///
+    /// # Panics
+    ///
+    /// This operation panics if `i` or `k` exceed the number of rows of `m`
+    pub fn apply_left<R, C>(&self, m: &mut MatGen<T, R, C>, i: usize, k: usize)
+    where
+        R: Unsigned + ops::Mul<C>,
+        C: Unsigned,
+        Prod<R, C>: ArrayLength<T>,
+    {
+        assert!(i < R::to_usize() && k < R::to_usize());
+
+        let n = C::to_usize();
+        for c in 0..n {
+            let (x, y) = (m.buffer[i * n + c], m.buffer[k * n + c]);
+            m.buffer[i * n + c] = self.c * x + self.s * y;
+            m.buffer[k * n + c] = self.c * y - self.s * x;
+        }
+    }
+
+    /// Rotates columns `i` and `k` of `m`, i.e. `m ← m Gᵀ`
+    ///
+    /// # Panics
+    ///
+    /// This operation panics if `i` or `k` exceed the number of columns of `m`
+    pub fn apply_right<R, C>(&self, m: &mut MatGen<T, R, C>, i: usize, k: usize)
+    where
+        R: Unsigned + ops::Mul<C>,
+        C: Unsigned,
+        Prod<R, C>: ArrayLength<T>,
+    {
+        assert!(i < C::to_usize() && k < C::to_usize());
+
+        let n = C::to_usize();
+        for r in 0..R::to_usize() {
+            let (x, y) = (m.buffer[r * n + i], m.buffer[r * n + k]);
+            m.buffer[r * n + i] = self.c * x + self.s * y;
+            m.buffer[r * n + k] = self.c * y - self.s * x;
+        }
+    }
+}
+
+/// Returns the Householder reflector that maps the vector `x` to `alpha e₀`
+///
+/// `alpha` has the opposite sign of the first element of `x` and the magnitude of `x`.
+///
+/// # Example
+///
+/// ```
+/// extern crate mat;
+/// extern crate typenum;
+///
+/// use mat::{linalg, mat, Vector};
+/// use mat::traits::Matrix;
+/// use typenum::U3;
+///
+/// # fn main() {
+/// let x = mat![[2.], [1.], [2.]];
+/// let h = linalg::householder(&x);
+///
+/// let mut y = Vector::<f64, U3>::from_fn(|r, _| x.get(r, 0));
+/// h.apply_left(&mut y);
+///
+/// assert!((y[0] + 3.).abs() < 1e-12);
+/// assert!(y[1].abs() < 1e-12);
+/// assert!(y[2].abs() < 1e-12);
+/// # }
+/// ```
+pub fn householder<X, T, N>(x: X) -> Householder<T, N>
+where
+    X: Matrix<Elem = T, NROWS = N, NCOLS = U1>,
+    N: Unsigned + ops::Mul<U1>,
+    Prod<N, U1>: ArrayLength<T>,
+    T: ops::Add<T, Output = T>
+        + ops::Div<T, Output = T>
+        + ops::Mul<T, Output = T>
+        + ops::Sub<T, Output = T>
+        + Copy
+        + From<u8>
+        + PartialOrd
+        + Sqrt
+        + Zero,
+{
+    let mut v = x.eval();
+
+    let mut norm = T::zero();
+    for i in 0..N::to_usize() {
+        norm = norm + v.buffer[i] * v.buffer[i];
+    }
+    let norm = norm.sqrt();
+
+    if norm == T::zero() {
+        return Householder { v, beta: T::zero() };
+    }
+
+    // NOTE `v₀ - alpha` never suffers from cancellation because `alpha` has the opposite sign
+    let alpha = if v.buffer[0] < T::zero() {
+        norm
+    } else {
+        T::zero() - norm
+    };
+    v.buffer[0] = v.buffer[0] - alpha;
+
+    let mut vtv = T::zero();
+    for i in 0..N::to_usize() {
+        vtv = vtv + v.buffer[i] * v.buffer[i];
+    }
+
+    Householder {
+        v,
+        beta: T::from(2) / vtv,
+    }
+}
+
+impl<T, N> Householder<T, N>
+where
+    N: Unsigned + ops::Mul<U1>,
+    Prod<N, U1>: ArrayLength<T>,
+    T: ops::Add<T, Output = T>
+        + ops::Mul<T, Output = T>
+        + ops::Sub<T, Output = T>
+        + Copy
+        + Zero,
+{
+    /// Returns the Householder vector `v`
+    pub fn v(&self) -> &Vector<T, N> {
+        &self.v
+    }
+
+    /// Returns the scale factor `beta`
+    pub fn beta(&self) -> T {
+        self.beta
+    }
+
+    /// Reflects the columns of `m`, i.e. `m ← H m`
+    pub fn apply_left<C>(&self, m: &mut MatGen<T, N, C>)
+    where
+        N: ops::Mul<C>,
+        C: Unsigned,
+        Prod<N, C>: ArrayLength<T>,
+    {
+        let n = C::to_usize();
+        for c in 0..n {
+            // vᵀ m
+            let mut dot = T::zero();
+            for r in 0..N::to_usize() {
+                dot = dot + self.v.buffer[r] * m.buffer[r * n + c];
+            }
+
+            let dot = self.beta * dot;
+            for r in 0..N::to_usize() {
+                m.buffer[r * n + c] = m.buffer[r * n + c] - dot * self.v.buffer[r];
+            }
+        }
+    }
+
+    /// Reflects the rows of `m`, i.e. `m ← m H`
+    pub fn apply_right<R>(&self, m: &mut MatGen<T, R, N>)
+    where
+        R: Unsigned + ops::Mul<N>,
+        Prod<R, N>: ArrayLength<T>,
+    {
+        let n = N::to_usize();
+        for r in 0..R::to_usize() {
+            // m v
+            let mut dot = T::zero();
+            for c in 0..n {
+                dot = dot + m.buffer[r * n + c] * self.v.buffer[c];
+            }
+
+            let dot = self.beta * dot;
+            for c in 0..n {
+                m.buffer[r * n + c] = m.buffer[r * n + c] - dot * self.v.buffer[c];
+            }
+        }
+    }
+}
+
+/// Solves the linear system `A x = b`
+///
+/// This uses Gaussian elimination with partial pivoting. Returns `Error::Singular` if `A` is
+/// singular.
+pub fn solve<A, B, T, N>(a: A, b: B) -> Result<Vector<T, N>, Error>
+where
+    A: Matrix<Elem = T, NROWS = N, NCOLS = N>,
+    B: Matrix<Elem = T, NROWS = N, NCOLS = U1>,
+    N: Unsigned + ops::Mul<N> + ops::Mul<U1>,
+    Prod<N, N>: ArrayLength<T>,
+    Prod<N, U1>: ArrayLength<T>,
+    T: ops::Add<T, Output = T>
+        + ops::Div<T, Output = T>
+        + ops::Mul<T, Output = T>
+        + ops::Sub<T, Output = T>
+        + Copy
+        + PartialOrd
+        + Zero,
+{
+    let mut a = a.eval();
+    let mut x = b.eval();
+
+    if kernel::solve(a.as_mut_slice(), x.as_mut_slice(), N::to_usize()) {
+        Ok(x)
+    } else {
+        Err(Error::Singular)
+    }
+}
+
+/// Solves the linear systems `A X = B`, one per column of `B`
+///
+/// `A` is factorized once, with Gaussian elimination with partial pivoting, and the factorization
+/// is reused for all the columns of `B`. This is how a product like `A⁻¹ B` (or, transposing both
+/// sides, `B A⁻ᵀ`) should be computed, instead of forming `A⁻¹`. Returns `Error::Singular` if `A`
+/// is singular.
+///
+/// # Example
+///
+/// The Kalman gain `K = P Hᵀ S⁻¹`, where `S` is symmetric, is the transpose of the solution of
+/// `S Kᵀ = H P`
+///
+/// ```
+/// use mat::{linalg, mat};
+/// use mat::traits::{Matrix, Transpose};
+///
+/// // covariance of the state estimate
+/// let p = mat![
+///     [2., 0.5, 0.],
+///     [0.5, 1., 0.],
+///     [0., 0., 3.],
+/// ];
+/// // observation matrix
+/// let h = mat![
+///     [1., 0., 0.],
+///     [0., 0., 1.],
+/// ];
+/// // covariance of the innovation
+/// let s = mat![
+///     [2.5, 0.],
+///     [0., 4.],
+/// ];
+///
+/// let kt = linalg::solve_matrix(&s, &h * &p).unwrap();
+/// let k = (&kt).t();
+///
+/// assert_eq!(k.get(0, 0), 2. / 2.5);
+/// assert_eq!(k.get(1, 0), 0.5 / 2.5);
+/// assert_eq!(k.get(2, 1), 3. / 4.);
+/// ```
+pub fn solve_matrix<A, B, T, N, K>(a: A, b: B) -> Result<MatGen<T, N, K>, Error>
+where
+    A: Matrix<Elem = T, NROWS = N, NCOLS = N>,
+    B: Matrix<Elem = T, NROWS = N, NCOLS = K>,
+    N: Unsigned + ops::Mul<N> + ops::Mul<K>,
+    K: Unsigned,
+    Prod<N, N>: ArrayLength<T>,
+    Prod<N, K>: ArrayLength<T>,
+    T: ops::Add<T, Output = T>
+        + ops::Div<T, Output = T>
+        + ops::Mul<T, Output = T>
+        + ops::Sub<T, Output = T>
+        + Copy
+        + PartialOrd
+        + Zero,
+{
+    let mut a = a.eval();
+    let mut x = b.eval();
+
+    if kernel::solve_matrix(a.as_mut_slice(), x.as_mut_slice(), N::to_usize(), K::to_usize()) {
+        Ok(x)
+    } else {
+        Err(Error::Singular)
+    }
+}
+
+/// Orthonormalizes the columns of `a` using the modified Gram-Schmidt process
+///
+/// The `k`-th column of the result is a unit vector orthogonal to the previous columns that spans,
+/// together with them, the same space as the first `k + 1` columns of `a`. Returns
+/// `Error::RankDeficient` if the columns of `a` are linearly dependent.
+pub fn orthonormalize<A, T, M, N>(a: A) -> Result<MatGen<T, M, N>, Error>
+where
+    A: Matrix<Elem = T, NROWS = M, NCOLS = N>,
+    M: Unsigned + ops::Mul<N>,
+    N: Unsigned,
+    Prod<M, N>: ArrayLength<T>,
+    T: ops::Add<T, Output = T>
+        + ops::Div<T, Output = T>
+        + ops::Mul<T, Output = T>
+        + ops::Sub<T, Output = T>
+        + Copy
+        + PartialEq
+        + Sqrt
+        + Zero,
+{
+    let (m, n) = (M::to_usize(), N::to_usize());
+    let mut q = a.eval();
+
+    for k in 0..n {
+        let mut norm = T::zero();
+        for r in 0..m {
+            norm = norm + q.buffer[r * n + k] * q.buffer[r * n + k];
+        }
+        let norm = norm.sqrt();
+
+        if norm == T::zero() {
+            return Err(Error::RankDeficient);
+        }
+
+        for r in 0..m {
+            q.buffer[r * n + k] = q.buffer[r * n + k] / norm;
+        }
+
+        // remove the `k`-th direction from the remaining columns
+        for c in k + 1..n {
+            let mut dot = T::zero();
+            for r in 0..m {
+                dot = dot + q.buffer[r * n + k] * q.buffer[r * n + c];
+            }
+
+            for r in 0..m {
+                q.buffer[r * n + c] = q.buffer[r * n + c] - dot * q.buffer[r * n + k];
+            }
+        }
+    }
+
+    Ok(q)
+}
+
+/// Computes the QR factorization of `a`
+///
+/// Returns the `M` by `M` orthogonal matrix `Q` and the `M` by `N` upper triangular matrix `R` such
+/// that `a = Q R`. The factorization is computed with Givens rotations.
+pub fn qr<A, T, M, N>(a: A) -> (MatGen<T, M, M>, MatGen<T, M, N>)
+where
+    A: Matrix<Elem = T, NROWS = M, NCOLS = N>,
+    M: Unsigned + ops::Mul<M> + ops::Mul<N>,
+    N: Unsigned,
+    Prod<M, M>: ArrayLength<T>,
+    Prod<M, N>: ArrayLength<T>,
+    T: ops::Add<T, Output = T>
+        + ops::Div<T, Output = T>
+        + ops::Mul<T, Output = T>
+        + ops::Sub<T, Output = T>
+        + Copy
+        + From<u8>
+        + PartialEq
+        + Sqrt
+        + Zero,
+{
+    let (m, n) = (M::to_usize(), N::to_usize());
+    let mut q = MatGen::from_fn(|r, c| if r == c { T::from(1) } else { T::zero() });
+    let mut r = a.eval();
+
+    for c in 0..n {
+        for k in (c + 1..m).rev() {
+            let g = givens(r.buffer[(k - 1) * n + c], r.buffer[k * n + c]);
+            g.apply_left(&mut r, k - 1, k);
+            g.apply_right(&mut q, k - 1, k);
+            r.buffer[k * n + c] = T::zero();
+        }
+    }
+
+    (q, r)
+}
+
+/// Updates the QR factorization `A = Q R` to become the factorization of `A + u vᵀ`
+///
+/// `q` must be orthogonal and `r` upper triangular. The update uses `O(M (M + N))` Givens
+/// rotations instead of refactorizing `A + u vᵀ` from scratch.
+///
+/// # Example
+///
+/// ```
+/// use mat::{linalg, mat};
+/// use mat::traits::Matrix;
+///
+/// let a = mat![
+///     [1., 2.],
+///     [3., 4.],
+///     [5., 6.],
+/// ];
+/// let u = mat![[1.], [0.], [-1.]];
+/// let v = mat![[2.], [1.]];
+///
+/// let (mut q, mut r) = linalg::qr(&a);
+/// linalg::qr_update(&mut q, &mut r, &u, &v);
+///
+/// let qr = &q * &r;
+/// for i in 0..3 {
+///     for j in 0..2 {
+///         let expected: f64 = a.get(i, j) + u.get(i, 0) * v.get(j, 0);
+///         assert!((qr.get(i, j) - expected).abs() < 1e-12);
+///     }
+/// }
+/// assert!(r.get(1, 0) == 0. && r.get(2, 0) == 0. && r.get(2, 1) == 0.);
+/// ```
+pub fn qr_update<U, V, T, M, N>(q: &mut MatGen<T, M, M>, r: &mut MatGen<T, M, N>, u: U, v: V)
+where
+    U: Matrix<Elem = T, NROWS = M, NCOLS = U1>,
+    V: Matrix<Elem = T, NROWS = N, NCOLS = U1>,
+    M: Unsigned + ops::Mul<M> + ops::Mul<N> + ops::Mul<U1>,
+    N: Unsigned,
+    Prod<M, M>: ArrayLength<T>,
+    Prod<M, N>: ArrayLength<T>,
+    Prod<M, U1>: ArrayLength<T>,
+    T: ops::Add<T, Output = T>
+        + ops::Div<T, Output = T>
+        + ops::Mul<T, Output = T>
+        + ops::Sub<T, Output = T>
+        + Copy
+        + From<u8>
+        + PartialEq
+        + Sqrt
+        + Zero,
+{
+    let (m, n) = (M::to_usize(), N::to_usize());
+
+    // A + u vᵀ = Q (R + w vᵀ)
+    let mut w = ((&*q).t() * u).eval();
+
+    // reduce `w` to a multiple of `e₀`; this turns `R` into an upper Hessenberg matrix
+    for k in (1..m).rev() {
+        let g = givens(w.buffer[k - 1], w.buffer[k]);
+        g.apply_left(&mut w, k - 1, k);
+        g.apply_left(r, k - 1, k);
+        g.apply_right(q, k - 1, k);
+    }
+
+    for c in 0..n {
+        r.buffer[c] = r.buffer[c] + w.buffer[0] * v.get(c, 0);
+    }
+
+    // restore the triangular structure of `R`
+    for k in 0..n.min(m.saturating_sub(1)) {
+        let g = givens(r.buffer[k * n + k], r.buffer[(k + 1) * n + k]);
+        g.apply_left(r, k, k + 1);
+        g.apply_right(q, k, k + 1);
+        r.buffer[(k + 1) * n + k] = T::zero();
+    }
+}
+
+/// Returns the `M` by `N` Vandermonde matrix of the points `x`
+///
+/// The element at row `r` and column `c` is `x[r]` raised to the power `c`, so the product of the
+/// matrix and the coefficients of a polynomial, in order of increasing degree, is the polynomial
+/// evaluated at each point.
+pub fn vandermonde<N, T, M>(x: &Vector<T, M>) -> MatGen<T, M, N>
+where
+    M: Unsigned + ops::Mul<N> + ops::Mul<U1>,
+    N: Unsigned,
+    Prod<M, N>: ArrayLength<T>,
+    Prod<M, U1>: ArrayLength<T>,
+    T: ops::Mul<T, Output = T> + Copy + From<u8>,
+{
+    MatGen::from_fn(|r, c| (0..c).fold(T::from(1), |acc, _| acc * x.buffer[r]))
+}
+
+/// Fits a polynomial of degree `DEG` to the points `(x[i], y[i])` in the least squares sense
+///
+/// Returns the `DEG + 1` coefficients of the polynomial in order of increasing degree. The
+/// overdetermined system `V c = y`, where `V` is the Vandermonde matrix of `x`, is solved with a
+/// QR factorization of `V`. There must be at least `DEG + 1` points, which is checked at compile
+/// time, and `x` must have at least `DEG + 1` distinct values. `Error::RankDeficient` is returned
+/// if a diagonal element of `R` is zero; note that with floating point elements rounding errors
+/// usually turn a rank deficient `V` into huge, meaningless coefficients instead.
+///
+/// # Example
+///
+/// ```
+/// extern crate mat;
+/// extern crate typenum;
+///
+/// use mat::{linalg, Vector};
+/// use typenum::{U2, U5};
+///
+/// # fn main() {
+/// // raw readings of a sensor and the reference values
+/// let raw = Vector::<f64, U5>::from_fn(|r, _| [0., 1., 2., 3., 4.][r]);
+/// let reference = Vector::<f64, U5>::from_fn(|r, _| [1., 1., 2., 4., 7.][r]);
+///
+/// // calibration curve
+/// let c = linalg::polyfit::<U2, _, _>(&raw, &reference).unwrap();
+/// assert!((c[0] - 1.).abs() < 1e-12);
+/// assert!((c[1] + 0.5).abs() < 1e-12);
+/// assert!((c[2] - 0.5).abs() < 1e-12);
+/// # }
+/// ```
+pub fn polyfit<DEG, T, M>(
+    x: &Vector<T, M>,
+    y: &Vector<T, M>,
+) -> Result<Vector<T, Add1<DEG>>, Error>
+where
+    DEG: ops::Add<B1>,
+    Add1<DEG>: Unsigned + ops::Mul<U1> + IsLessOrEqual<M, Output = True>,
+    M: Unsigned + ops::Mul<M> + ops::Mul<Add1<DEG>> + ops::Mul<U1>,
+    Prod<M, M>: ArrayLength<T>,
+    Prod<M, Add1<DEG>>: ArrayLength<T>,
+    Prod<M, U1>: ArrayLength<T>,
+    Prod<Add1<DEG>, U1>: ArrayLength<T>,
+    T: ops::Add<T, Output = T>
+        + ops::Div<T, Output = T>
+        + ops::Mul<T, Output = T>
+        + ops::Sub<T, Output = T>
+        + Copy
+        + From<u8>
+        + PartialEq
+        + Sqrt
+        + Zero,
+{
+    let (m, n) = (M::to_usize(), Add1::<DEG>::to_usize());
+    let (q, r) = qr(&vandermonde::<Add1<DEG>, T, M>(x));
+
+    // the first `N` elements of `Qᵀ y`; the rest are the residual
+    let mut c: Vector<T, Add1<DEG>> = MatGen::from_fn(|i, _| {
+        (0..m).fold(T::zero(), |acc, k| acc + q.buffer[k * m + i] * y.buffer[k])
+    });
+
+    // back substitution with the upper triangle of `R`
+    for i in (0..n).rev() {
+        let d = r.buffer[i * n + i];
+        if d == T::zero() {
+            return Err(Error::RankDeficient);
+        }
+
+        let mut sum = c.buffer[i];
+        for k in i + 1..n {
+            sum = sum - r.buffer[i * n + k] * c.buffer[k];
+        }
+        c.buffer[i] = sum / d;
+    }
+
+    Ok(c)
+}
+
+impl<T, N> MatGen<T, N, N>
+where
+    N: Unsigned + ops::Mul<N>,
+    Prod<N, N>: ArrayLength<T>,
+    T: Clone,
+{
+    /// Returns the sign and the natural logarithm of the absolute value of the determinant
+    ///
+    /// The determinant is the product of the diagonal of the LU factorization, computed with
+    /// partial pivoting, but it's never formed: the logarithms of the diagonal elements are summed
+    /// instead, which doesn't overflow or underflow even when the determinant would. The sign is
+    /// `1` or `-1`; the determinant of a covariance matrix is always positive. Returns
+    /// `Error::Singular` if the matrix is singular.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate mat;
+    /// extern crate typenum;
+    ///
+    /// use mat::MatGen;
+    /// use mat::traits::Ln;
+    /// use typenum::{U2, U64};
+    ///
+    /// # fn main() {
+    /// let a = MatGen::<f64, U2, U2>::from_fn(|r, c| [[1., 2.], [3., 4.]][r][c]);
+    /// let (sign, ln) = a.log_det().unwrap();
+    /// assert_eq!(sign, -1.);
+    /// assert!((ln - 2_f64.ln()).abs() < 1e-9);
+    ///
+    /// // the determinant, 1e-320, underflows `f32` and is subnormal in `f64`
+    /// let p = MatGen::<f32, U64, U64>::from_fn(|r, c| if r == c { 1e-5 } else { 0. });
+    /// let (sign, ln) = p.log_det().unwrap();
+    /// assert_eq!(sign, 1.);
+    /// assert!((ln / (64. * Ln::ln(1e-5_f32)) - 1.).abs() < 1e-5);
+    /// # }
+    /// ```
+    pub fn log_det(&self) -> Result<(T, T), Error>
+    where
+        T: ops::Add<T, Output = T>
+            + ops::Div<T, Output = T>
+            + ops::Mul<T, Output = T>
+            + ops::Sub<T, Output = T>
+            + Copy
+            + From<u8>
+            + Ln
+            + PartialOrd
+            + Zero,
+    {
+        let n = N::to_usize();
+        let mut lu = self.clone();
+        let swaps = kernel::eliminate(lu.as_mut_slice(), &mut [], n, 0).ok_or(Error::Singular)?;
+
+        let (one, minus_one) = (T::from(1), T::zero() - T::from(1));
+        let mut sign = if swaps % 2 == 0 { one } else { minus_one };
+        let mut ln = T::zero();
+        for i in 0..n {
+            let d = lu.buffer[i * n + i];
+            if d < T::zero() {
+                sign = T::zero() - sign;
+            }
+            ln = ln + abs(d).ln();
+        }
+
+        Ok((sign, ln))
+    }
+}
+
+pub(crate) fn abs<T>(x: T) -> T
+where
+    T: ops::Sub<T, Output = T> + PartialOrd + Zero,
+{
+    if x < T::zero() {
+        T::zero() - x
+    } else {
+        x
+    }
+}
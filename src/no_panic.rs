@@ -0,0 +1,41 @@
+//! Link time check of the panic-free API
+//!
+//! With the `no-panic` Cargo feature the bodies of the checked accessors (`Matrix::get_checked`,
+//! the `row_checked` methods and the `set_checked` methods of every matrix type) are wrapped in a
+//! guard whose destructor, which only runs if the body unwinds, calls a function that doesn't
+//! exist. If the compiler can't prove that a body never panics the guard stays in the binary and
+//! linking fails with an error that names this check.
+//!
+//! The proof relies on inlining and dead code elimination, and on unwinding to observe panics, so
+//! the check is only meaningful in builds with optimizations and `panic = "unwind"`, e.g. `cargo
+//! test --release --features no-panic` on the host; doc tests also need `RUSTDOCFLAGS="-C
+//! opt-level=3"`. Panics in the `Clone`, arithmetic or comparison implementations of the element
+//! type are reported as well.
+
+/// Evaluates `$body`; with the `no-panic` feature linking fails unless `$body` provably doesn't
+/// panic
+macro_rules! no_panic {
+    ($body:expr) => {{
+        #[cfg(feature = "no-panic")]
+        let guard = ::no_panic::Guard;
+        let ret = $body;
+        #[cfg(feature = "no-panic")]
+        ::core::mem::forget(guard);
+        ret
+    }};
+}
+
+#[cfg(feature = "no-panic")]
+pub struct Guard;
+
+#[cfg(feature = "no-panic")]
+impl Drop for Guard {
+    fn drop(&mut self) {
+        extern "C" {
+            #[link_name = "\n\nmat: the panic-free API may panic; see the `no-panic` feature\n\n"]
+            fn may_panic() -> !;
+        }
+
+        unsafe { may_panic() }
+    }
+}
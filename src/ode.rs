@@ -0,0 +1,86 @@
+//! Ordinary differential equations
+//!
+//! # Example
+//!
+//! Propagate an harmonic oscillator, `x'' = -x`, for a quarter of a period
+//!
+//! ```
+//! use mat::{mat, ode};
+//!
+//! let a = mat![
+//!     [0., 1.],
+//!     [-1., 0.],
+//! ];
+//!
+//! let x0 = mat![[1.], [0.]];
+//!
+//! let steps = 100;
+//! let dt = std::f64::consts::FRAC_PI_2 / steps as f64;
+//! let x = ode::integrate_rk4(&a, &x0, dt, steps);
+//!
+//! assert!(x[0].abs() < 1e-9);
+//! assert!((x[1] + 1.).abs() < 1e-9);
+//! ```
+
+use core::ops;
+
+use generic_array::ArrayLength;
+use typenum::{Prod, U1, Unsigned};
+
+use {MatGen, Product, Vector};
+use traits::{Matrix, Zero};
+
+/// Integrates the linear system `x' = A x` from `x0` using `steps` fixed steps of size `dt`
+///
+/// This uses the classic fourth order Runge-Kutta method.
+pub fn integrate_rk4<A, X, T, N>(a: A, x0: X, dt: T, steps: usize) -> Vector<T, N>
+where
+    A: Matrix<Elem = T, NROWS = N, NCOLS = N>,
+    X: Matrix<Elem = T, NROWS = N, NCOLS = U1>,
+    N: Unsigned + ops::Mul<U1>,
+    Prod<N, U1>: ArrayLength<T>,
+    T: ops::Add<T, Output = T>
+        + ops::Div<T, Output = T>
+        + ops::Mul<T, Output = T>
+        + Copy
+        + From<u8>
+        + Zero,
+{
+    integrate_rk4_with(|x| Product { l: a, r: x }.eval(), x0, dt, steps)
+}
+
+/// Integrates the system `x' = f(x)` from `x0` using `steps` fixed steps of size `dt`
+///
+/// This uses the classic fourth order Runge-Kutta method.
+pub fn integrate_rk4_with<F, X, T, N>(mut f: F, x0: X, dt: T, steps: usize) -> Vector<T, N>
+where
+    F: FnMut(&Vector<T, N>) -> Vector<T, N>,
+    X: Matrix<Elem = T, NROWS = N, NCOLS = U1>,
+    N: Unsigned + ops::Mul<U1>,
+    Prod<N, U1>: ArrayLength<T>,
+    T: ops::Add<T, Output = T>
+        + ops::Div<T, Output = T>
+        + ops::Mul<T, Output = T>
+        + Copy
+        + From<u8>
+        + Zero,
+{
+    let half_dt = dt / T::from(2);
+    let sixth_dt = dt / T::from(6);
+    let two = T::from(2);
+
+    let mut x = x0.eval();
+    for _ in 0..steps {
+        let k1 = f(&x);
+        let k2 = f(&MatGen::from_fn(|r, _| x.get(r, 0) + half_dt * k1.get(r, 0)));
+        let k3 = f(&MatGen::from_fn(|r, _| x.get(r, 0) + half_dt * k2.get(r, 0)));
+        let k4 = f(&MatGen::from_fn(|r, _| x.get(r, 0) + dt * k3.get(r, 0)));
+
+        x = MatGen::from_fn(|r, _| {
+            let k = k1.get(r, 0) + two * k2.get(r, 0) + two * k3.get(r, 0) + k4.get(r, 0);
+            x.get(r, 0) + sixth_dt * k
+        });
+    }
+
+    x
+}
@@ -0,0 +1,309 @@
+//! Nonlinear least squares
+//!
+//! # Examples
+//!
+//! Fit the line `y = a * x + b` to a set of points with a single Gauss-Newton step
+//!
+//! ```
+//! use mat::mat;
+//! use mat::optimize;
+//!
+//! // residuals `a * x + b - y` evaluated at `a = 0`, `b = 0`
+//! let residuals = mat![[-1.], [-3.], [-5.]];
+//!
+//! // Jacobian of the residuals with respect to `[a, b]`
+//! let jacobian = mat![
+//!     [0., 1.],
+//!     [1., 1.],
+//!     [2., 1.],
+//! ];
+//!
+//! let delta = optimize::gauss_newton_step(&jacobian, &residuals).unwrap();
+//!
+//! assert!((delta[0] - 2_f64).abs() < 1e-12);
+//! assert!((delta[1] - 1_f64).abs() < 1e-12);
+//! ```
+//!
+//! Fit the curve `y = a * exp(b * t)` with the Levenberg-Marquardt algorithm
+//!
+//! ```
+//! extern crate mat;
+//! extern crate typenum;
+//!
+//! use mat::{MatGen, Vector};
+//! use mat::optimize::{self, LmOptions};
+//! use typenum::{U2, U4};
+//!
+//! # fn main() {
+//! let t = [0., 1., 2., 3.];
+//! let y = [2., 3.2974425414, 5.4365636569, 8.9633781407];
+//!
+//! let report = optimize::lm(
+//!     |x: &Vector<f64, U2>| {
+//!         Vector::<_, U4>::from_fn(|i, _| {
+//!             x[0] * (x[1] * t[i]).exp() - y[i]
+//!         })
+//!     },
+//!     |x: &Vector<f64, U2>| {
+//!         MatGen::<_, U4, U2>::from_fn(|i, j| {
+//!             let e = (x[1] * t[i]).exp();
+//!             if j == 0 { e } else { x[0] * t[i] * e }
+//!         })
+//!     },
+//!     MatGen::from_fn(|i, _| [1., 0.][i]),
+//!     LmOptions::default(),
+//! )
+//! .unwrap();
+//!
+//! assert!((report.x[0] - 2.).abs() < 1e-6);
+//! assert!((report.x[1] - 0.5).abs() < 1e-6);
+//! # }
+//! ```
+
+use core::ops;
+
+use generic_array::ArrayLength;
+use typenum::{Prod, U1, Unsigned};
+
+use {Error, MatGen, Vector};
+use linalg;
+use traits::{Matrix, Transpose, Zero};
+
+/// Computes a Gauss-Newton step
+///
+/// Given the `M` by `N` `jacobian` of the residuals with respect to the parameters and the
+/// `residuals` evaluated at the current parameters, returns the step `delta` that should be
+/// *added* to the parameters. The step is computed by solving the normal equations
+/// `Jᵀ J delta = -Jᵀ r`.
+///
+/// Returns `Error::Singular` if `Jᵀ J` is singular.
+pub fn gauss_newton_step<J, R, T, M, N>(
+    jacobian: J,
+    residuals: R,
+) -> Result<Vector<T, N>, Error>
+where
+    J: Matrix<Elem = T, NROWS = M, NCOLS = N>,
+    R: Matrix<Elem = T, NROWS = M, NCOLS = U1>,
+    M: Unsigned,
+    N: Unsigned + ops::Mul<N> + ops::Mul<U1>,
+    Prod<N, N>: ArrayLength<T>,
+    Prod<N, U1>: ArrayLength<T>,
+    T: ops::Add<T, Output = T>
+        + ops::Div<T, Output = T>
+        + ops::Mul<T, Output = T>
+        + ops::Neg<Output = T>
+        + ops::Sub<T, Output = T>
+        + Copy
+        + PartialOrd
+        + Zero,
+{
+    let jtj = (jacobian.t() * jacobian).eval();
+    let jtr = (jacobian.t() * residuals).eval();
+
+    let delta = linalg::solve(&jtj, &jtr)?;
+
+    Ok(MatGen::from_fn(|r, c| -delta.get(r, c)))
+}
+
+/// Options of the Levenberg-Marquardt algorithm
+#[derive(Clone, Copy, Debug)]
+pub struct LmOptions<T> {
+    /// Initial damping factor
+    pub lambda: T,
+    /// Factor by which the damping is multiplied after a rejected step
+    pub increase: T,
+    /// Factor by which the damping is divided after an accepted step
+    pub decrease: T,
+    /// The algorithm stops once a step changes the cost by this amount or less, or once the norm of
+    /// the step or of the gradient, `Jᵀ r`, is this small or smaller
+    pub tolerance: T,
+    /// Maximum number of iterations
+    pub max_iterations: usize,
+}
+
+impl Default for LmOptions<f32> {
+    fn default() -> Self {
+        LmOptions {
+            lambda: 1e-3,
+            increase: 10.,
+            decrease: 10.,
+            tolerance: 1e-6,
+            max_iterations: 100,
+        }
+    }
+}
+
+impl Default for LmOptions<f64> {
+    fn default() -> Self {
+        LmOptions {
+            lambda: 1e-3,
+            increase: 10.,
+            decrease: 10.,
+            tolerance: 1e-12,
+            max_iterations: 100,
+        }
+    }
+}
+
+/// Outcome of the Levenberg-Marquardt algorithm
+#[derive(Clone, Debug)]
+pub struct LmReport<T, N>
+where
+    N: Unsigned + ops::Mul<U1>,
+    Prod<N, U1>: ArrayLength<T>,
+    T: Copy,
+{
+    /// The best parameters found
+    pub x: Vector<T, N>,
+    /// The cost, the sum of the squared residuals, at `x`
+    pub cost: T,
+    /// Number of iterations performed
+    pub iterations: usize,
+}
+
+/// Minimizes the sum of the squared `residuals` with the Levenberg-Marquardt algorithm
+///
+/// `residuals` evaluates the `M` residuals at the given `N` parameters and `jacobian` evaluates
+/// their `M` by `N` Jacobian. The search starts at `x0` and runs for at most
+/// `options.max_iterations` iterations; `Error::DidNotConverge` is returned if it hasn't converged
+/// by then.
+///
+/// Each iteration solves the damped normal equations `(Jᵀ J + lambda I) delta = -Jᵀ r`. The step
+/// is accepted, and the damping decreased, if it reduces the cost; otherwise the step is discarded
+/// and the damping increased.
+///
+/// The search has converged once the gradient `Jᵀ r` or the step vanishes, or once a step changes
+/// the cost by no more than `options.tolerance` in either direction; see `LmOptions::tolerance`.
+/// In particular a search that starts at a minimum converges in a single iteration.
+///
+/// # Example
+///
+/// ```
+/// extern crate mat;
+/// extern crate typenum;
+///
+/// use mat::{MatGen, Vector};
+/// use mat::optimize::{self, LmOptions};
+/// use typenum::{U1, U2};
+///
+/// # fn main() {
+/// // `x - 3`, which is already minimized at the start point
+/// let report = optimize::lm(
+///     |x: &Vector<f64, U1>| MatGen::<_, U2, U1>::from_fn(|i, _| x[0] - [3., 3.][i]),
+///     |_: &Vector<f64, U1>| MatGen::<_, U2, U1>::from_fn(|_, _| 1.),
+///     MatGen::from_fn(|_, _| 3.),
+///     LmOptions::default(),
+/// )
+/// .unwrap();
+///
+/// assert!(report.iterations <= 1);
+/// assert_eq!(report.x[0], 3.);
+/// # }
+/// ```
+pub fn lm<FR, FJ, T, M, N>(
+    mut residuals: FR,
+    mut jacobian: FJ,
+    x0: Vector<T, N>,
+    options: LmOptions<T>,
+) -> Result<LmReport<T, N>, Error>
+where
+    FR: FnMut(&Vector<T, N>) -> Vector<T, M>,
+    FJ: FnMut(&Vector<T, N>) -> MatGen<T, M, N>,
+    M: Unsigned + ops::Mul<U1> + ops::Mul<N>,
+    N: Unsigned + ops::Mul<N> + ops::Mul<U1>,
+    Prod<M, U1>: ArrayLength<T>,
+    Prod<M, N>: ArrayLength<T>,
+    Prod<N, N>: ArrayLength<T>,
+    Prod<N, U1>: ArrayLength<T>,
+    T: ops::Add<T, Output = T>
+        + ops::Div<T, Output = T>
+        + ops::Mul<T, Output = T>
+        + ops::Sub<T, Output = T>
+        + Copy
+        + PartialOrd
+        + Zero,
+{
+    let mut x = x0;
+    let mut r = residuals(&x);
+    let mut cost = sum_of_squares(&r);
+    let mut lambda = options.lambda;
+
+    let mut j = jacobian(&x);
+    let mut jtj = ((&j).t() * &j).eval();
+    let mut jtr = ((&j).t() * &r).eval();
+
+    let tolerance2 = options.tolerance * options.tolerance;
+
+    let mut iterations = 0;
+    // NOTE the start point may already be a minimum
+    let mut converged = sum_of_squares(&jtr) <= tolerance2;
+    while !converged && iterations < options.max_iterations {
+        iterations += 1;
+
+        let damped = MatGen::<T, N, N>::from_fn(|row, col| {
+            if row == col {
+                jtj.get(row, col) + lambda
+            } else {
+                jtj.get(row, col)
+            }
+        });
+
+        let delta = match linalg::solve(&damped, &jtr) {
+            Ok(delta) => delta,
+            Err(_) => {
+                lambda = lambda * options.increase;
+                continue;
+            }
+        };
+
+        let x_new = MatGen::from_fn(|row, _| x.get(row, 0) - delta.get(row, 0));
+        let r_new = residuals(&x_new);
+        let cost_new = sum_of_squares(&r_new);
+        let negligible_step = sum_of_squares(&delta) <= tolerance2;
+
+        if cost_new < cost {
+            let reduction = cost - cost_new;
+
+            x = x_new;
+            r = r_new;
+            cost = cost_new;
+            lambda = lambda / options.decrease;
+
+            if reduction <= options.tolerance || negligible_step {
+                converged = true;
+                break;
+            }
+
+            j = jacobian(&x);
+            jtj = ((&j).t() * &j).eval();
+            jtr = ((&j).t() * &r).eval();
+            converged = sum_of_squares(&jtr) <= tolerance2;
+        } else if cost_new - cost <= options.tolerance || negligible_step {
+            // no step can reduce the cost any further; keep the current parameters
+            converged = true;
+        } else {
+            lambda = lambda * options.increase;
+        }
+    }
+
+    if !converged {
+        return Err(Error::DidNotConverge);
+    }
+
+    Ok(LmReport { x, cost, iterations })
+}
+
+fn sum_of_squares<M>(m: M) -> M::Elem
+where
+    M: Matrix,
+    M::Elem: ops::Add<M::Elem, Output = M::Elem> + ops::Mul<M::Elem, Output = M::Elem> + Zero,
+{
+    let mut sum = M::Elem::zero();
+    for r in 0..m.nrows() {
+        for c in 0..m.ncols() {
+            let e = m.get(r, c);
+            sum = sum + e.clone() * e;
+        }
+    }
+    sum
+}
@@ -0,0 +1,178 @@
+use core::marker::PhantomData;
+use core::ops;
+
+use generic_array::{ArrayLength, GenericArray};
+use typenum::{Prod, Unsigned};
+
+use {array, MatGen, Product};
+use traits::{Matrix, UnsafeGet, Zero};
+
+/// Permutation matrix that only stores the permutation indices
+///
+/// Row `i` of `P * A` is row `indices[i]` of `A`.
+///
+/// # Example
+///
+/// ```
+/// extern crate mat;
+/// extern crate typenum;
+///
+/// use mat::{mat, Permutation};
+/// use mat::traits::Matrix;
+/// use typenum::U3;
+///
+/// # fn main() {
+/// let a = mat![
+///     [1, 2],
+///     [3, 4],
+///     [5, 6],
+/// ];
+///
+/// let mut p = Permutation::<i32, U3>::identity();
+/// p.swap(0, 2);
+///
+/// let b = p.permute_rows(&a);
+/// assert_eq!(b.get(0, 0), 5);
+/// assert_eq!(b.get(2, 1), 2);
+///
+/// // lazy product
+/// assert_eq!((&p * &a).get(0, 1), 6);
+/// # }
+/// ```
+pub struct Permutation<T, N>
+where
+    N: ArrayLength<usize>,
+{
+    indices: GenericArray<usize, N>,
+    ty: PhantomData<[T; 0]>,
+}
+
+impl<T, N> Clone for Permutation<T, N>
+where
+    N: ArrayLength<usize>,
+{
+    fn clone(&self) -> Self {
+        let indices = self.indices();
+        Permutation {
+            indices: array::generate(|i| indices[i]),
+            ty: PhantomData,
+        }
+    }
+}
+
+impl<T, N> Permutation<T, N>
+where
+    N: ArrayLength<usize>,
+{
+    /// Returns the identity permutation
+    pub fn identity() -> Self {
+        Permutation {
+            indices: array::generate(|i| i),
+            ty: PhantomData,
+        }
+    }
+
+    /// Creates a permutation from its indices
+    ///
+    /// Returns `None` if `indices` is not a permutation of `0..N`
+    pub fn from_indices(indices: GenericArray<usize, N>) -> Option<Self> {
+        let n = N::to_usize();
+        let slice = array::as_slice(&indices);
+        for (i, &index) in slice.iter().enumerate() {
+            if index >= n || slice[..i].contains(&index) {
+                return None;
+            }
+        }
+
+        Some(Permutation {
+            indices,
+            ty: PhantomData,
+        })
+    }
+
+    /// Returns the permutation indices
+    pub fn indices(&self) -> &[usize] {
+        array::as_slice(&self.indices)
+    }
+
+    /// Swaps the rows `i` and `j` of the permutation matrix
+    ///
+    /// # Panics
+    ///
+    /// This operation panics if `i` or `j` exceed the matrix dimensions
+    pub fn swap(&mut self, i: usize, j: usize) {
+        array::as_mut_slice(&mut self.indices).swap(i, j)
+    }
+
+    /// Returns the inverse permutation, which is also its transpose
+    pub fn inverse(&self) -> Self {
+        let mut inverse = Self::identity();
+        for (i, &index) in self.indices().iter().enumerate() {
+            array::as_mut_slice(&mut inverse.indices)[index] = i;
+        }
+        inverse
+    }
+
+    /// Permutes the rows of `m`, i.e. returns `P m`
+    pub fn permute_rows<M, C>(&self, m: M) -> MatGen<T, N, C>
+    where
+        M: Matrix<Elem = T, NROWS = N, NCOLS = C>,
+        N: Unsigned + ops::Mul<C>,
+        C: Unsigned,
+        Prod<N, C>: ArrayLength<T>,
+        T: Clone,
+    {
+        MatGen::from_fn(|r, c| unsafe { m.unsafe_get(self.indices()[r], c) })
+    }
+
+    /// Permutes the columns of `m`, i.e. returns `m Pᵀ`
+    ///
+    /// Column `j` of the result is column `indices[j]` of `m`.
+    pub fn permute_cols<M, R>(&self, m: M) -> MatGen<T, R, N>
+    where
+        M: Matrix<Elem = T, NROWS = R, NCOLS = N>,
+        R: Unsigned + ops::Mul<N>,
+        Prod<R, N>: ArrayLength<T>,
+        T: Clone,
+    {
+        MatGen::from_fn(|r, c| unsafe { m.unsafe_get(r, self.indices()[c]) })
+    }
+}
+
+impl<T, N> Matrix for &Permutation<T, N>
+where
+    N: ArrayLength<usize>,
+    T: Clone + From<u8> + Zero,
+{
+    type NROWS = N;
+    type NCOLS = N;
+}
+
+impl<T, N> UnsafeGet for &Permutation<T, N>
+where
+    N: ArrayLength<usize>,
+    T: Clone + From<u8> + Zero,
+{
+    type Elem = T;
+
+    unsafe fn unsafe_get(self, r: usize, c: usize) -> T {
+        if *self.indices().get_unchecked(r) == c {
+            T::from(1)
+        } else {
+            T::zero()
+        }
+    }
+}
+
+impl<'a, T, N, R> ops::Mul<R> for &'a Permutation<T, N>
+where
+    N: ArrayLength<usize>,
+    T: Clone + From<u8> + Zero,
+    R: Matrix<NROWS = N>,
+{
+    type Output = Product<&'a Permutation<T, N>, R>;
+
+    fn mul(self, rhs: R) -> Self::Output {
+        Product { l: self, r: rhs }
+    }
+}
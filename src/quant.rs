@@ -0,0 +1,501 @@
+//! Quantized matrix multiplication
+//!
+//! A quantized matrix stores each element `x` as a small integer `q` such that `x ≈ scale * (q -
+//! zero_point)`, where the scale and the zero point are shared by all the elements of the matrix
+//! (*per-tensor* quantization). `matmul` multiplies `u8` and / or `i8` matrices accumulating in
+//! `i32`, and rescales the result to the output quantization using only integer arithmetic. This
+//! is the convention used by TensorFlow Lite for Microcontrollers.
+//!
+//! Nonlinearities can be applied to quantized matrices without any floating point math with a
+//! lookup table: `MatGen::apply_lut` indexes a table with every 8-bit element and
+//! `MatGen::apply_lut_interp` linearly interpolates a coarser table of 16-bit elements.
+//!
+//! # Example
+//!
+//! ```
+//! extern crate mat;
+//! extern crate typenum;
+//!
+//! use mat::compare;
+//! use mat::quant::{self, QuantParams};
+//! use mat::MatGen;
+//! use typenum::{U2, U3};
+//!
+//! # fn main() {
+//! let x = MatGen::<f32, U2, U3>::from_fn(|r, c| [[0.5, -1., 1.5], [2., 0., -0.5]][r][c]);
+//! let w = MatGen::<f32, U3, U2>::from_fn(|r, c| [[0.1, -0.2], [0.3, 0.4], [-0.5, 0.6]][r][c]);
+//!
+//! // asymmetric `u8` activations, symmetric `i8` weights and `i8` outputs
+//! let x_params = QuantParams::new(2. / 127., 128);
+//! let w_params = QuantParams::new(0.6 / 127., 0);
+//! let y_params = QuantParams::new(0.05, 0);
+//!
+//! let xq = quant::quantize::<u8, _, _>(&x, x_params);
+//! let wq = quant::quantize::<i8, _, _>(&w, w_params);
+//! let yq = quant::matmul::<_, _, i8, _, _, _>(&xq, x_params, &wq, w_params, y_params);
+//!
+//! // within two output quantization steps of the floating point result
+//! let y = quant::dequantize(&yq, y_params);
+//! assert!(compare::compare(&y, &x * &w, 0.1).passed());
+//! # }
+//! ```
+
+use core::ops;
+
+use generic_array::ArrayLength;
+use typenum::{Prod, Unsigned};
+
+use {array, MatGen};
+
+/// An integer type used to store quantized values
+pub trait Quantized: Copy {
+    /// The smallest value of the type
+    const MIN: i32;
+    /// The largest value of the type
+    const MAX: i32;
+
+    /// Widens the value
+    fn to_i32(self) -> i32;
+
+    /// Narrows `x`, which must be in the range `MIN..=MAX`
+    fn from_i32(x: i32) -> Self;
+}
+
+macro_rules! quantized {
+    ($($T:ident,)+) => {
+        $(
+            impl Quantized for $T {
+                const MIN: i32 = $T::MIN as i32;
+                const MAX: i32 = $T::MAX as i32;
+
+                fn to_i32(self) -> i32 {
+                    self as i32
+                }
+
+                fn from_i32(x: i32) -> Self {
+                    x as $T
+                }
+            }
+        )+
+    }
+}
+
+quantized!(i8, u8,);
+
+/// A 16-bit type that can be looked up in an interpolated table
+///
+/// Implemented for `i16` and `u16`, and for the 16-bit fixed point types of the `fixed` crate when
+/// the `fixed` feature is enabled.
+pub trait Interpolated: Copy {
+    /// The smallest value of the type, as an integer
+    const MIN: i32;
+
+    /// Returns the underlying integer
+    fn to_bits(self) -> i32;
+
+    /// Converts the integer `x`, which must be in the range of the type, back
+    fn from_bits(x: i32) -> Self;
+}
+
+macro_rules! interpolated {
+    ($($T:ident,)+) => {
+        $(
+            impl Interpolated for $T {
+                const MIN: i32 = $T::MIN as i32;
+
+                fn to_bits(self) -> i32 {
+                    self as i32
+                }
+
+                fn from_bits(x: i32) -> Self {
+                    x as $T
+                }
+            }
+        )+
+    }
+}
+
+interpolated!(i16, u16,);
+
+/// The quantization parameters of a matrix
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct QuantParams {
+    /// The difference between the real values of two consecutive quantized values
+    pub scale: f32,
+    /// The quantized value that represents zero
+    pub zero_point: i32,
+}
+
+impl QuantParams {
+    /// Creates quantization parameters from a scale and a zero point
+    pub fn new(scale: f32, zero_point: i32) -> Self {
+        QuantParams { scale, zero_point }
+    }
+
+    /// Quantizes `x`, rounding to the nearest value and saturating at the bounds of `Q`
+    pub fn quantize<Q>(&self, x: f32) -> Q
+    where
+        Q: Quantized,
+    {
+        let q = x / self.scale;
+        let q = if q < 0. { q - 0.5 } else { q + 0.5 } as i32;
+        Q::from_i32(clamp::<Q>(q.saturating_add(self.zero_point)))
+    }
+
+    /// Returns the real value that `q` represents
+    pub fn dequantize<Q>(&self, q: Q) -> f32
+    where
+        Q: Quantized,
+    {
+        self.scale * (q.to_i32() - self.zero_point) as f32
+    }
+}
+
+/// A real multiplier represented as a fixed point number and a power of two
+///
+/// Rescaling with a `Multiplier` only uses integer arithmetic.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Multiplier {
+    // Q31 fixed point number in the range `[0.5, 1)`, or zero
+    multiplier: i32,
+    shift: i32,
+}
+
+impl Multiplier {
+    /// Approximates the non-negative multiplier `real`
+    ///
+    /// # Panics
+    ///
+    /// This operation panics if `real` is negative, infinite or NaN
+    pub fn new(real: f64) -> Self {
+        assert!(real >= 0. && real.is_finite());
+
+        if real == 0. {
+            return Multiplier {
+                multiplier: 0,
+                shift: 0,
+            };
+        }
+
+        // `real = q * 2^shift` where `q` is in `[0.5, 1)`
+        let (mut q, mut shift) = (real, 0);
+        while q >= 1. {
+            q /= 2.;
+            shift += 1;
+        }
+        while q < 0.5 {
+            q *= 2.;
+            shift -= 1;
+        }
+
+        let mut fixed = (q * (1_i64 << 31) as f64 + 0.5) as i64;
+        if fixed == 1 << 31 {
+            fixed /= 2;
+            shift += 1;
+        }
+
+        Multiplier {
+            multiplier: fixed as i32,
+            shift,
+        }
+    }
+
+    /// Returns `x` times the multiplier, rounded to the nearest integer
+    ///
+    /// Ties are rounded like TensorFlow Lite does, which is not symmetric: some negative ties are
+    /// rounded up. Results that don't fit in an `i32` saturate.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mat::quant::Multiplier;
+    ///
+    /// assert_eq!(Multiplier::new(0.25).apply(10), 3);
+    /// assert_eq!(Multiplier::new(1e10).apply(3), i32::MAX);
+    /// assert_eq!(Multiplier::new(1e10).apply(-3), i32::MIN);
+    /// ```
+    pub fn apply(&self, x: i32) -> i32 {
+        let (left, right) = if self.shift > 0 {
+            (self.shift, 0)
+        } else {
+            (0, -self.shift)
+        };
+
+        rounding_divide_by_pot(doubling_high_mul(x, self.multiplier, left), right.min(31))
+    }
+}
+
+impl<NROWS, NCOLS> MatGen<f32, NROWS, NCOLS>
+where
+    NROWS: Unsigned + ops::Mul<NCOLS>,
+    NCOLS: Unsigned,
+    Prod<NROWS, NCOLS>: ArrayLength<f32>,
+{
+    /// Quantizes the elements to `i8` with the given scale and zero point
+    ///
+    /// Each element is rounded to the nearest value, ties away from zero, and saturates at the
+    /// bounds of `i8`. Use the `quantize` function to quantize to other types.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate mat;
+    /// extern crate typenum;
+    ///
+    /// use mat::MatGen;
+    /// use typenum::{U1, U4};
+    ///
+    /// # fn main() {
+    /// let x = MatGen::<f32, U1, U4>::from_fn(|_, c| [-1., 0.26, 0.5, 20.][c]);
+    ///
+    /// let q = x.quantize(0.1, -3);
+    /// assert_eq!(q, MatGen::from_fn(|_, c| [-13, 0, 2, 127][c]));
+    ///
+    /// // the saturated element can't be recovered
+    /// let y = q.dequantize(0.1, -3);
+    /// assert!((y.get(1) - 0.3).abs() < 1e-6);
+    /// assert!((y.get(3) - 13.).abs() < 1e-5);
+    /// # }
+    /// ```
+    pub fn quantize(&self, scale: f32, zero_point: i32) -> MatGen<i8, NROWS, NCOLS>
+    where
+        Prod<NROWS, NCOLS>: ArrayLength<i8>,
+    {
+        quantize(self, QuantParams::new(scale, zero_point))
+    }
+}
+
+impl<T, NROWS, NCOLS> MatGen<T, NROWS, NCOLS>
+where
+    NROWS: Unsigned + ops::Mul<NCOLS>,
+    NCOLS: Unsigned,
+    Prod<NROWS, NCOLS>: ArrayLength<T>,
+    T: Clone,
+{
+    /// Returns the real values, `scale * (q - zero_point)`, that the elements `q` represent
+    pub fn dequantize(&self, scale: f32, zero_point: i32) -> MatGen<f32, NROWS, NCOLS>
+    where
+        Prod<NROWS, NCOLS>: ArrayLength<f32>,
+        T: Quantized,
+    {
+        dequantize(self, QuantParams::new(scale, zero_point))
+    }
+
+    /// Replaces every element `x` by its entry in `table`
+    ///
+    /// The table is indexed from the smallest value of `T`, i.e. `table[0]` is the entry of `0u8`
+    /// and of `-128i8`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate mat;
+    /// extern crate typenum;
+    ///
+    /// use mat::MatGen;
+    /// use typenum::U2;
+    ///
+    /// # fn main() {
+    /// // gamma correction, the table can be computed once, e.g. at build time
+    /// let mut gamma = [0; 256];
+    /// for (i, y) in gamma.iter_mut().enumerate() {
+    ///     *y = (255. * (i as f32 / 255.).powf(1. / 2.2) + 0.5) as u8;
+    /// }
+    ///
+    /// let pixels = MatGen::<u8, U2, U2>::from_fn(|r, c| [[0, 64], [128, 255]][r][c]);
+    /// let corrected = pixels.apply_lut(&gamma);
+    /// assert_eq!(corrected, MatGen::from_fn(|r, c| [[0, 136], [186, 255]][r][c]));
+    /// # }
+    /// ```
+    pub fn apply_lut(&self, table: &[T; 256]) -> Self
+    where
+        T: Quantized,
+    {
+        let buffer = self.as_slice();
+        MatGen::new(array::generate(|i| table[(buffer[i].to_i32() - T::MIN) as usize]))
+    }
+
+    /// Replaces every element `x` by the linear interpolation of `table` at `x`
+    ///
+    /// `table[i]` is the value at the `i * 256`-th element of the range of `T`, counting from its
+    /// smallest value; the last entry is the value just past the largest value of `T`. The
+    /// interpolation rounds to the nearest integer, and ties up.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate mat;
+    /// extern crate typenum;
+    ///
+    /// use mat::MatGen;
+    /// use mat::traits::Matrix;
+    /// use typenum::U3;
+    ///
+    /// # fn main() {
+    /// // Q15 `tanh`, tabulated once at startup
+    /// let mut tanh = [0; 257];
+    /// for (i, y) in tanh.iter_mut().enumerate() {
+    ///     let x = (i as f32 * 256. - 32768.) / 32768. * 4.;
+    ///     *y = (x.tanh() * 32767.) as i16;
+    /// }
+    ///
+    /// // inputs in Q2.13, i.e. from -4 to 4
+    /// let x = MatGen::<i16, U3, U3>::from_fn(|r, c| (r as i16 * 3 + c as i16 - 4) * 4096);
+    /// let y = x.apply_lut_interp(&tanh);
+    ///
+    /// for (r, c) in (0..3).flat_map(|r| (0..3).map(move |c| (r, c))) {
+    ///     let x = f32::from(x.get(r, c)) / 8192.;
+    ///     let y = f32::from(y.get(r, c)) / 32767.;
+    ///     assert!((y - x.tanh()).abs() < 1e-3);
+    /// }
+    /// # }
+    /// ```
+    pub fn apply_lut_interp(&self, table: &[T; 257]) -> Self
+    where
+        T: Interpolated,
+    {
+        let buffer = self.as_slice();
+        MatGen::new(array::generate(|i| {
+            let offset = buffer[i].to_bits() - T::MIN;
+            let (index, frac) = ((offset >> 8) as usize, offset & 0xff);
+
+            let (lo, hi) = (table[index].to_bits(), table[index + 1].to_bits());
+            T::from_bits(lo + (((hi - lo) * frac + 128) >> 8))
+        }))
+    }
+}
+
+/// Quantizes the elements of `m`
+pub fn quantize<Q, NROWS, NCOLS>(
+    m: &MatGen<f32, NROWS, NCOLS>,
+    params: QuantParams,
+) -> MatGen<Q, NROWS, NCOLS>
+where
+    NROWS: Unsigned + ops::Mul<NCOLS>,
+    NCOLS: Unsigned,
+    Prod<NROWS, NCOLS>: ArrayLength<f32> + ArrayLength<Q>,
+    Q: Quantized,
+{
+    let ncols = NCOLS::to_usize();
+    MatGen::from_fn(|r, c| params.quantize(m.buffer[r * ncols + c]))
+}
+
+/// Returns the real values that the elements of `m` represent
+pub fn dequantize<Q, NROWS, NCOLS>(
+    m: &MatGen<Q, NROWS, NCOLS>,
+    params: QuantParams,
+) -> MatGen<f32, NROWS, NCOLS>
+where
+    NROWS: Unsigned + ops::Mul<NCOLS>,
+    NCOLS: Unsigned,
+    Prod<NROWS, NCOLS>: ArrayLength<f32> + ArrayLength<Q>,
+    Q: Quantized,
+{
+    let ncols = NCOLS::to_usize();
+    MatGen::from_fn(|r, c| params.dequantize(m.buffer[r * ncols + c]))
+}
+
+/// Returns the `i32` accumulators `Σ (a[r, k] - a_zero_point) (b[k, c] - b_zero_point)`
+///
+/// The real value of the product is the accumulator times `a_scale * b_scale`, which is also the
+/// usual quantization of a bias that is added to the accumulators.
+pub fn accumulate<QA, QB, M, K, N>(
+    a: &MatGen<QA, M, K>,
+    a_zero_point: i32,
+    b: &MatGen<QB, K, N>,
+    b_zero_point: i32,
+) -> MatGen<i32, M, N>
+where
+    M: Unsigned + ops::Mul<K> + ops::Mul<N>,
+    K: Unsigned + ops::Mul<N>,
+    N: Unsigned,
+    Prod<M, K>: ArrayLength<QA>,
+    Prod<K, N>: ArrayLength<QB>,
+    Prod<M, N>: ArrayLength<i32>,
+    QA: Quantized,
+    QB: Quantized,
+{
+    let (k, n) = (K::to_usize(), N::to_usize());
+    MatGen::from_fn(|r, c| {
+        let mut acc = 0_i32;
+        for i in 0..k {
+            let a = a.buffer[r * k + i].to_i32() - a_zero_point;
+            let b = b.buffer[i * n + c].to_i32() - b_zero_point;
+            acc = acc.wrapping_add(a * b);
+        }
+        acc
+    })
+}
+
+/// Multiplies the quantized matrices `a` and `b` and quantizes the product with `out_params`
+///
+/// The products are accumulated in `i32` and rescaled by the fixed point `Multiplier` of
+/// `a_params.scale * b_params.scale / out_params.scale`; the result saturates at the bounds of
+/// `QO`.
+///
+/// # Panics
+///
+/// This operation panics if that ratio is negative, infinite or NaN, e.g. if `out_params.scale` is
+/// zero
+pub fn matmul<QA, QB, QO, M, K, N>(
+    a: &MatGen<QA, M, K>,
+    a_params: QuantParams,
+    b: &MatGen<QB, K, N>,
+    b_params: QuantParams,
+    out_params: QuantParams,
+) -> MatGen<QO, M, N>
+where
+    M: Unsigned + ops::Mul<K> + ops::Mul<N>,
+    K: Unsigned + ops::Mul<N>,
+    N: Unsigned,
+    Prod<M, K>: ArrayLength<QA>,
+    Prod<K, N>: ArrayLength<QB>,
+    Prod<M, N>: ArrayLength<i32> + ArrayLength<QO>,
+    QA: Quantized,
+    QB: Quantized,
+    QO: Quantized,
+{
+    let real = f64::from(a_params.scale) * f64::from(b_params.scale) / f64::from(out_params.scale);
+    let multiplier = Multiplier::new(real);
+
+    let acc = accumulate(a, a_params.zero_point, b, b_params.zero_point);
+    let n = N::to_usize();
+    MatGen::from_fn(|r, c| {
+        let q = multiplier
+            .apply(acc.buffer[r * n + c])
+            .saturating_add(out_params.zero_point);
+        QO::from_i32(clamp::<QO>(q))
+    })
+}
+
+fn clamp<Q>(x: i32) -> i32
+where
+    Q: Quantized,
+{
+    if x < Q::MIN {
+        Q::MIN
+    } else if x > Q::MAX {
+        Q::MAX
+    } else {
+        x
+    }
+}
+
+// `round(a * 2^shift * b / 2^31)`, saturating at the bounds of `i32`
+fn doubling_high_mul(a: i32, b: i32, shift: i32) -> i32 {
+    // NOTE `a * 2^shift` overflows `i32` for large shifts, so the result saturates instead. `a * b`
+    // has at most 63 significant bits; any shift of 64 or more saturates a non-zero product
+    let ab = (i128::from(a) * i128::from(b)) << shift.min(64);
+    let nudge = if ab >= 0 { 1 << 30 } else { 1 - (1 << 30) };
+    ((ab + nudge) / (1 << 31))
+        .max(i128::from(i32::MIN))
+        .min(i128::from(i32::MAX)) as i32
+}
+
+// `x / 2^exponent`, rounding half away from zero
+fn rounding_divide_by_pot(x: i32, exponent: i32) -> i32 {
+    let mask = ((1_i64 << exponent) - 1) as i32;
+    let remainder = x & mask;
+    let threshold = (mask >> 1) + if x < 0 { 1 } else { 0 };
+    (x >> exponent) + if remainder > threshold { 1 } else { 0 }
+}
@@ -0,0 +1,249 @@
+//! Fully unrolled operations on 2x2, 3x3 and 4x4 matrices
+
+use core::ops;
+
+use traits::Zero;
+use {Error, Mat2, Mat3, Mat4};
+
+// Expands to the `N` by `N` product of the row major buffers `$a` and `$b` as an array of rows,
+// with every multiplication written out
+macro_rules! unrolled_mul {
+    ($a:expr, $b:expr, $n:expr, [$($r:expr),+], $cols:tt) => {
+        [$(unrolled_mul!(@row $a, $b, $n, $r, $cols, $cols)),+]
+    };
+    (@row $a:expr, $b:expr, $n:expr, $r:expr, [$($c:expr),+], $ks:tt) => {
+        [$(unrolled_mul!(@dot $a, $b, $n, $r, $c, $ks)),+]
+    };
+    (@dot $a:expr, $b:expr, $n:expr, $r:expr, $c:expr, [$k0:expr $(, $k:expr)*]) => {
+        $a[$r * $n + $k0] * $b[$k0 * $n + $c] $(+ $a[$r * $n + $k] * $b[$k * $n + $c])*
+    };
+}
+
+/// Fully unrolled operations
+///
+/// # Example
+///
+/// ```
+/// let a = mat::mat2([[4., 2.], [3., 2.]]);
+/// assert_eq!(a.determinant(), 2.);
+///
+/// let inv = a.inverse().unwrap();
+/// assert_eq!(inv, mat::mat2([[1., -1.], [-1.5, 2.]]));
+/// assert_eq!(a.mul_unrolled(&inv), mat::Mat2::identity());
+///
+/// assert!(mat::mat2([[1., 2.], [2., 4.]]).inverse().is_err());
+///
+/// // only `inverse` needs division and negation, so unsigned integers can be multiplied
+/// let b = mat::mat2([[1_u32, 2], [3, 4]]);
+/// assert_eq!(b.mul_unrolled(&b), mat::mat2([[7, 10], [15, 22]]));
+/// ```
+impl<T> Mat2<T>
+where
+    T: ops::Add<T, Output = T> + ops::Mul<T, Output = T> + ops::Sub<T, Output = T> + Copy,
+{
+    /// Returns the matrix product `self * rhs`
+    ///
+    /// This computes the same result as evaluating `self * rhs` without any loops.
+    pub fn mul_unrolled(&self, rhs: &Self) -> Self {
+        ::mat2(unrolled_mul!(self.buffer, rhs.buffer, 2, [0, 1], [0, 1]))
+    }
+
+    /// Returns the determinant of the matrix
+    pub fn determinant(&self) -> T {
+        let m = &self.buffer;
+        m[0] * m[3] - m[1] * m[2]
+    }
+
+    /// Returns the inverse of the matrix, or `Error::Singular` if the matrix is singular
+    pub fn inverse(&self) -> Result<Self, Error>
+    where
+        T: ops::Div<T, Output = T> + ops::Neg<Output = T> + PartialEq + Zero,
+    {
+        let m = &self.buffer;
+        let det = self.determinant();
+        if det == T::zero() {
+            return Err(Error::Singular);
+        }
+
+        Ok(::mat2([
+            [m[3] / det, -m[1] / det],
+            [-m[2] / det, m[0] / det],
+        ]))
+    }
+}
+
+/// Fully unrolled operations
+///
+/// # Example
+///
+/// ```
+/// let a = mat::mat3([
+///     [2., 0., 0.],
+///     [0., 4., 0.],
+///     [1., 0., 1.],
+/// ]);
+/// assert_eq!(a.determinant(), 8.);
+///
+/// let inv = a.inverse().unwrap();
+/// assert_eq!(inv.mul_unrolled(&a), mat::Mat3::identity());
+/// assert_eq!(inv.mul_unrolled(&a), (&inv * &a).eval());
+/// ```
+impl<T> Mat3<T>
+where
+    T: ops::Add<T, Output = T> + ops::Mul<T, Output = T> + ops::Sub<T, Output = T> + Copy,
+{
+    /// Returns the matrix product `self * rhs`
+    ///
+    /// This computes the same result as evaluating `self * rhs` without any loops.
+    pub fn mul_unrolled(&self, rhs: &Self) -> Self {
+        ::mat3(unrolled_mul!(self.buffer, rhs.buffer, 3, [0, 1, 2], [0, 1, 2]))
+    }
+
+    /// Returns the determinant of the matrix
+    pub fn determinant(&self) -> T {
+        let m = &self.buffer;
+        m[0] * (m[4] * m[8] - m[5] * m[7]) + m[1] * (m[5] * m[6] - m[3] * m[8])
+            + m[2] * (m[3] * m[7] - m[4] * m[6])
+    }
+
+    /// Returns the inverse of the matrix, or `Error::Singular` if the matrix is singular
+    pub fn inverse(&self) -> Result<Self, Error>
+    where
+        T: ops::Div<T, Output = T> + PartialEq + Zero,
+    {
+        let m = &self.buffer;
+
+        // cofactors of the first row
+        let c0 = m[4] * m[8] - m[5] * m[7];
+        let c1 = m[5] * m[6] - m[3] * m[8];
+        let c2 = m[3] * m[7] - m[4] * m[6];
+
+        let det = m[0] * c0 + m[1] * c1 + m[2] * c2;
+        if det == T::zero() {
+            return Err(Error::Singular);
+        }
+
+        Ok(::mat3([
+            [
+                c0 / det,
+                (m[2] * m[7] - m[1] * m[8]) / det,
+                (m[1] * m[5] - m[2] * m[4]) / det,
+            ],
+            [
+                c1 / det,
+                (m[0] * m[8] - m[2] * m[6]) / det,
+                (m[2] * m[3] - m[0] * m[5]) / det,
+            ],
+            [
+                c2 / det,
+                (m[1] * m[6] - m[0] * m[7]) / det,
+                (m[0] * m[4] - m[1] * m[3]) / det,
+            ],
+        ]))
+    }
+}
+
+/// Fully unrolled operations
+///
+/// # Example
+///
+/// ```
+/// let a = mat::mat4([
+///     [1., 0., 2., 0.],
+///     [0., 2., 0., 0.],
+///     [0., 0., 1., 0.],
+///     [4., 0., 0., 1.],
+/// ]);
+/// assert_eq!(a.determinant(), 2.);
+///
+/// let inv = a.inverse().unwrap();
+/// assert_eq!(a.mul_unrolled(&inv), mat::Mat4::identity());
+/// ```
+impl<T> Mat4<T>
+where
+    T: ops::Add<T, Output = T> + ops::Mul<T, Output = T> + ops::Sub<T, Output = T> + Copy,
+{
+    /// Returns the matrix product `self * rhs`
+    ///
+    /// This computes the same result as evaluating `self * rhs` without any loops.
+    pub fn mul_unrolled(&self, rhs: &Self) -> Self {
+        ::mat4(unrolled_mul!(
+            self.buffer,
+            rhs.buffer,
+            4,
+            [0, 1, 2, 3],
+            [0, 1, 2, 3]
+        ))
+    }
+
+    /// Returns the determinant of the matrix
+    pub fn determinant(&self) -> T {
+        let (s, c) = self.minors();
+        s[0] * c[5] - s[1] * c[4] + s[2] * c[3] + s[3] * c[2] - s[4] * c[1] + s[5] * c[0]
+    }
+
+    /// Returns the inverse of the matrix, or `Error::Singular` if the matrix is singular
+    pub fn inverse(&self) -> Result<Self, Error>
+    where
+        T: ops::Div<T, Output = T> + PartialEq + Zero,
+    {
+        let m = &self.buffer;
+        let (s, c) = self.minors();
+
+        let det = s[0] * c[5] - s[1] * c[4] + s[2] * c[3] + s[3] * c[2] - s[4] * c[1]
+            + s[5] * c[0];
+        if det == T::zero() {
+            return Err(Error::Singular);
+        }
+
+        Ok(::mat4([
+            [
+                (m[5] * c[5] - m[6] * c[4] + m[7] * c[3]) / det,
+                (m[2] * c[4] - m[1] * c[5] - m[3] * c[3]) / det,
+                (m[13] * s[5] - m[14] * s[4] + m[15] * s[3]) / det,
+                (m[10] * s[4] - m[9] * s[5] - m[11] * s[3]) / det,
+            ],
+            [
+                (m[6] * c[2] - m[4] * c[5] - m[7] * c[1]) / det,
+                (m[0] * c[5] - m[2] * c[2] + m[3] * c[1]) / det,
+                (m[14] * s[2] - m[12] * s[5] - m[15] * s[1]) / det,
+                (m[8] * s[5] - m[10] * s[2] + m[11] * s[1]) / det,
+            ],
+            [
+                (m[4] * c[4] - m[5] * c[2] + m[7] * c[0]) / det,
+                (m[1] * c[2] - m[0] * c[4] - m[3] * c[0]) / det,
+                (m[12] * s[4] - m[13] * s[2] + m[15] * s[0]) / det,
+                (m[9] * s[2] - m[8] * s[4] - m[11] * s[0]) / det,
+            ],
+            [
+                (m[5] * c[1] - m[4] * c[3] - m[6] * c[0]) / det,
+                (m[0] * c[3] - m[1] * c[1] + m[2] * c[0]) / det,
+                (m[13] * s[1] - m[12] * s[3] - m[14] * s[0]) / det,
+                (m[8] * s[3] - m[9] * s[1] + m[10] * s[0]) / det,
+            ],
+        ]))
+    }
+
+    // Returns the 2x2 minors of the first two rows, `s`, and of the last two rows, `c`
+    fn minors(&self) -> ([T; 6], [T; 6]) {
+        let m = &self.buffer;
+        (
+            [
+                m[0] * m[5] - m[4] * m[1],
+                m[0] * m[6] - m[4] * m[2],
+                m[0] * m[7] - m[4] * m[3],
+                m[1] * m[6] - m[5] * m[2],
+                m[1] * m[7] - m[5] * m[3],
+                m[2] * m[7] - m[6] * m[3],
+            ],
+            [
+                m[8] * m[13] - m[12] * m[9],
+                m[8] * m[14] - m[12] * m[10],
+                m[8] * m[15] - m[12] * m[11],
+                m[9] * m[14] - m[13] * m[10],
+                m[9] * m[15] - m[13] * m[11],
+                m[10] * m[15] - m[14] * m[11],
+            ],
+        )
+    }
+}
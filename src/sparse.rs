@@ -0,0 +1,253 @@
+use core::marker::PhantomData;
+use core::ops;
+
+use generic_array::{ArrayLength, GenericArray};
+use typenum::{Prod, Unsigned};
+
+use traits::{Matrix, UnsafeGet, Zero};
+use {array, MatGen, Product};
+
+/// Sparse matrix with room for up to `NNZ` non-zero elements
+///
+/// The non-zero elements are stored in row major order along with their positions so reading an
+/// element is a binary search, and multiplying by a dense matrix with `mul_dense` only visits the
+/// non-zero elements.
+///
+/// # Example
+///
+/// ```
+/// extern crate mat;
+/// extern crate typenum;
+///
+/// use mat::{MatGen, SparseMat};
+/// use mat::traits::Matrix;
+/// use typenum::{U1, U4, U64, U100};
+///
+/// # fn main() {
+/// // each of the 64 measurements observes one or two of the 100 states
+/// let mut h = SparseMat::<f32, U64, U100, U100>::new();
+/// for i in 0..64 {
+///     h.set(i, i, 1.).unwrap();
+/// }
+/// h.set(0, 99, -1.).unwrap();
+/// assert_eq!(h.nnz(), 65);
+///
+/// assert_eq!((&h).get(0, 99), -1.);
+/// assert_eq!((&h).get(1, 99), 0.);
+///
+/// let x = MatGen::<f32, U100, U1>::from_fn(|r, _| r as f32);
+/// let y = h.mul_dense(&x);
+/// assert_eq!(y[0], -99.);
+/// assert_eq!(y[63], 63.);
+///
+/// // the capacity is fixed
+/// let mut s = SparseMat::<i32, U4, U4, U1>::new();
+/// assert_eq!(s.set(0, 0, 1), Ok(()));
+/// assert_eq!(s.set(0, 0, 2), Ok(()));
+/// assert_eq!(s.set(1, 1, 3), Err(3));
+/// # }
+/// ```
+pub struct SparseMat<T, NROWS, NCOLS, NNZ>
+where
+    NROWS: Unsigned,
+    NCOLS: Unsigned,
+    NNZ: ArrayLength<T> + ArrayLength<usize>,
+    T: Clone,
+{
+    // NOTE the position of the element at `(r, c)` is `r * NCOLS + c`; `positions[..len]` is sorted
+    positions: GenericArray<usize, NNZ>,
+    values: GenericArray<T, NNZ>,
+    len: usize,
+    dims: PhantomData<(NROWS, NCOLS)>,
+}
+
+impl<T, NROWS, NCOLS, NNZ> Clone for SparseMat<T, NROWS, NCOLS, NNZ>
+where
+    NROWS: Unsigned,
+    NCOLS: Unsigned,
+    NNZ: ArrayLength<T> + ArrayLength<usize>,
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        let positions = array::as_slice(&self.positions);
+        let values = array::as_slice(&self.values);
+        SparseMat {
+            positions: array::generate(|i| positions[i]),
+            values: array::generate(|i| values[i].clone()),
+            len: self.len,
+            dims: PhantomData,
+        }
+    }
+}
+
+impl<T, NROWS, NCOLS, NNZ> SparseMat<T, NROWS, NCOLS, NNZ>
+where
+    NROWS: Unsigned,
+    NCOLS: Unsigned,
+    NNZ: ArrayLength<T> + ArrayLength<usize>,
+    T: Clone,
+{
+    /// Creates a matrix filled with zeros
+    pub fn new() -> Self
+    where
+        T: Zero,
+    {
+        SparseMat {
+            positions: array::generate(|_| 0),
+            values: array::generate(|_| T::zero()),
+            len: 0,
+            dims: PhantomData,
+        }
+    }
+
+    /// Returns the number of stored elements
+    pub fn nnz(&self) -> usize {
+        self.len
+    }
+
+    /// Returns an iterator over the stored elements as `(row, column, value)` tuples, in row major
+    /// order
+    pub fn iter<'a>(&'a self) -> impl Iterator<Item = (usize, usize, &'a T)> + 'a {
+        let ncols = NCOLS::to_usize();
+        self.positions()
+            .iter()
+            .zip(array::as_slice(&self.values))
+            .map(move |(&pos, value)| (pos / ncols, pos % ncols, value))
+    }
+
+    /// Returns the sorted positions of the stored elements
+    fn positions(&self) -> &[usize] {
+        &array::as_slice(&self.positions)[..self.len]
+    }
+
+    /// Sets the element at row `r` and column `c` to `value`
+    ///
+    /// Returns `value` back if the element was not stored and the matrix is full.
+    ///
+    /// # Panics
+    ///
+    /// This operation panics if `r` or `c` exceed the matrix dimensions
+    pub fn set(&mut self, r: usize, c: usize, value: T) -> Result<(), T> {
+        assert!(r < NROWS::to_usize() && c < NCOLS::to_usize());
+
+        self.insert(r * NCOLS::to_usize() + c, value)
+    }
+
+    /// Sets the element at row `r` and column `c` to `value`
+    ///
+    /// Returns `value` back if `r` or `c` exceed the matrix dimensions, or if the element was not
+    /// stored and the matrix is full.
+    pub fn set_checked(&mut self, r: usize, c: usize, value: T) -> Result<(), T> {
+        no_panic!(if r < NROWS::to_usize() && c < NCOLS::to_usize() {
+            self.insert(r * NCOLS::to_usize() + c, value)
+        } else {
+            Err(value)
+        })
+    }
+
+    /// Stores `value` at position `pos`, in row major order
+    fn insert(&mut self, pos: usize, value: T) -> Result<(), T> {
+        let len = self.len;
+        let positions = array::as_mut_slice(&mut self.positions);
+        let values = array::as_mut_slice(&mut self.values);
+        match positions.get(..len).map(|stored| stored.binary_search(&pos)) {
+            Some(Ok(i)) => match values.get_mut(i) {
+                Some(elem) => {
+                    *elem = value;
+                    Ok(())
+                }
+                None => Err(value),
+            },
+            Some(Err(i)) if len < positions.len() && len < values.len() => {
+                // make room for the new element
+                positions[i..len + 1].rotate_right(1);
+                values[i..len + 1].rotate_right(1);
+                positions[i] = pos;
+                values[i] = value;
+                self.len += 1;
+                Ok(())
+            }
+            // full
+            _ => Err(value),
+        }
+    }
+
+    /// Multiplies this matrix by the dense matrix `m`
+    ///
+    /// This costs `K` multiplications per stored element.
+    pub fn mul_dense<M, K>(&self, m: M) -> MatGen<T, NROWS, K>
+    where
+        M: Matrix<Elem = T, NROWS = NCOLS, NCOLS = K>,
+        NROWS: ops::Mul<K>,
+        K: Unsigned,
+        Prod<NROWS, K>: ArrayLength<T>,
+        T: ops::Add<T, Output = T> + ops::Mul<T, Output = T> + Zero,
+    {
+        let k = K::to_usize();
+        let mut out: MatGen<T, NROWS, K> = MatGen::zeros();
+        for (r, c, value) in self.iter() {
+            for j in 0..k {
+                let i = r * k + j;
+                let elem = unsafe { m.unsafe_get(c, j) };
+                out.buffer[i] = out.buffer[i].clone() + value.clone() * elem;
+            }
+        }
+        out
+    }
+}
+
+impl<T, NROWS, NCOLS, NNZ> Default for SparseMat<T, NROWS, NCOLS, NNZ>
+where
+    NROWS: Unsigned,
+    NCOLS: Unsigned,
+    NNZ: ArrayLength<T> + ArrayLength<usize>,
+    T: Clone + Zero,
+{
+    fn default() -> Self {
+        SparseMat::new()
+    }
+}
+
+impl<T, NROWS, NCOLS, NNZ> Matrix for &SparseMat<T, NROWS, NCOLS, NNZ>
+where
+    NROWS: Unsigned,
+    NCOLS: Unsigned,
+    NNZ: ArrayLength<T> + ArrayLength<usize>,
+    T: Clone + Zero,
+{
+    type NROWS = NROWS;
+    type NCOLS = NCOLS;
+}
+
+impl<T, NROWS, NCOLS, NNZ> UnsafeGet for &SparseMat<T, NROWS, NCOLS, NNZ>
+where
+    NROWS: Unsigned,
+    NCOLS: Unsigned,
+    NNZ: ArrayLength<T> + ArrayLength<usize>,
+    T: Clone + Zero,
+{
+    type Elem = T;
+
+    unsafe fn unsafe_get(self, r: usize, c: usize) -> T {
+        let pos = r * NCOLS::to_usize() + c;
+        match self.positions().binary_search(&pos) {
+            Ok(i) => array::as_slice(&self.values).get_unchecked(i).clone(),
+            Err(_) => T::zero(),
+        }
+    }
+}
+
+impl<'a, T, NROWS, NCOLS, NNZ, R> ops::Mul<R> for &'a SparseMat<T, NROWS, NCOLS, NNZ>
+where
+    NROWS: Unsigned,
+    NCOLS: Unsigned,
+    NNZ: ArrayLength<T> + ArrayLength<usize>,
+    T: Clone + Zero,
+    R: Matrix<NROWS = NCOLS>,
+{
+    type Output = Product<&'a SparseMat<T, NROWS, NCOLS, NNZ>, R>;
+
+    fn mul(self, rhs: R) -> Self::Output {
+        Product { l: self, r: rhs }
+    }
+}
@@ -0,0 +1,445 @@
+//! Statistics
+//!
+//! # Example
+//!
+//! ```
+//! use mat::{mat, stats};
+//! use mat::traits::Matrix;
+//!
+//! // one observation per row
+//! let samples = mat![
+//!     [1., 2.],
+//!     [3., 6.],
+//!     [5., 10.],
+//! ];
+//!
+//! let (mean, cov) = stats::covariance(&samples);
+//!
+//! assert_eq!((mean[0], mean[1]), (3., 6.));
+//! assert_eq!(cov.get(0, 0), 4.);
+//! assert_eq!(cov.get(0, 1), 8.);
+//! assert_eq!(cov.get(1, 0), 8.);
+//! assert_eq!(cov.get(1, 1), 16.);
+//! ```
+
+use core::ops;
+
+use generic_array::ArrayLength;
+use typenum::{Prod, U1, Unsigned};
+
+use {MatGen, RowVector, Vector};
+#[cfg(all(feature = "rand_core", any(feature = "libm", feature = "fast-math")))]
+use traits::{Ln, Trig};
+use traits::{Matrix, Sqrt, Zero};
+
+/// Computes the mean and the sample covariance of the observations in `samples`
+///
+/// Each of the `M` rows of `samples` is an observation of `N` variables. Returns the `N` element
+/// mean vector and the `N` by `N` sample covariance matrix, which is normalized by `M - 1`.
+///
+/// # Panics
+///
+/// This operation panics if `samples` has less than two rows
+pub fn covariance<S, T, M, N>(samples: S) -> (Vector<T, N>, MatGen<T, N, N>)
+where
+    S: Matrix<Elem = T, NROWS = M, NCOLS = N>,
+    M: Unsigned,
+    N: Unsigned + ops::Mul<N> + ops::Mul<U1>,
+    Prod<N, N>: ArrayLength<T>,
+    Prod<N, U1>: ArrayLength<T>,
+    T: ops::Add<T, Output = T>
+        + ops::Div<T, Output = T>
+        + ops::Mul<T, Output = T>
+        + ops::Sub<T, Output = T>
+        + Copy
+        + From<u8>
+        + Zero,
+{
+    let (m, n) = (M::to_usize(), N::to_usize());
+    assert!(m >= 2);
+
+    let count: T = count(m);
+
+    let mut mean: Vector<T, N> = MatGen::zeros();
+    for r in 0..m {
+        for c in 0..n {
+            mean.buffer[c] = mean.buffer[c] + samples.get(r, c);
+        }
+    }
+    for c in 0..n {
+        mean.buffer[c] = mean.buffer[c] / count;
+    }
+
+    let mut cov: MatGen<T, N, N> = MatGen::zeros();
+    for r in 0..m {
+        for i in 0..n {
+            let di = samples.get(r, i) - mean.buffer[i];
+            for j in i..n {
+                let dj = samples.get(r, j) - mean.buffer[j];
+                cov.buffer[i * n + j] = cov.buffer[i * n + j] + di * dj;
+            }
+        }
+    }
+
+    let dof = count - T::from(1);
+    for i in 0..n {
+        for j in i..n {
+            let cij = cov.buffer[i * n + j] / dof;
+            cov.buffer[i * n + j] = cij;
+            cov.buffer[j * n + i] = cij;
+        }
+    }
+
+    (mean, cov)
+}
+
+/// How a sum of squared deviations from the mean is normalized
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Correction {
+    /// Divide by the number of samples, `n`: the variance of the samples themselves
+    Population,
+    /// Divide by `n - 1` (Bessel's correction): an unbiased estimate of the variance of the
+    /// population the samples were drawn from
+    Bessel,
+}
+
+impl Correction {
+    fn dof<T>(self, n: usize) -> T
+    where
+        T: ops::Add<T, Output = T> + From<u8> + Zero,
+    {
+        match self {
+            Correction::Population => {
+                assert!(n >= 1);
+                count(n)
+            }
+            Correction::Bessel => {
+                assert!(n >= 2);
+                count(n - 1)
+            }
+        }
+    }
+}
+
+/// Computes the cross-covariance of the observations in `x` and `y`
+///
+/// Row `r` of `x` and row `r` of `y` are observations made at the same time of `N` and `P`
+/// variables, respectively. The element at row `i` and column `j` of the `N` by `P` result is the
+/// covariance of the `i`-th variable of `x` and the `j`-th variable of `y`.
+///
+/// # Panics
+///
+/// This operation panics if there are less than two observations with `Correction::Bessel`, or none
+/// with `Correction::Population`
+///
+/// # Example
+///
+/// ```
+/// extern crate mat;
+/// extern crate typenum;
+///
+/// use mat::stats::{self, Correction};
+/// use mat::MatGen;
+/// use typenum::{U1, U2, U4};
+///
+/// # fn main() {
+/// let x = MatGen::<f64, U4, U2>::from_fn(|r, c| [[1., 0.], [2., 1.], [3., 0.], [4., 1.]][r][c]);
+/// let y = MatGen::<f64, U4, U1>::from_fn(|r, _| [2., 4., 6., 8.][r]);
+///
+/// let cov = stats::cross_covariance(&x, &y, Correction::Population);
+/// // a 2 by 1 matrix, i.e. a vector
+/// assert_eq!(cov.get(0), 2.5);
+/// assert_eq!(cov.get(1), 0.5);
+///
+/// let cov = stats::cross_covariance(&x, &y, Correction::Bessel);
+/// assert_eq!(cov.get(0), 10. / 3.);
+/// # }
+/// ```
+pub fn cross_covariance<X, Y, T, M, N, P>(x: X, y: Y, correction: Correction) -> MatGen<T, N, P>
+where
+    X: Matrix<Elem = T, NROWS = M, NCOLS = N>,
+    Y: Matrix<Elem = T, NROWS = M, NCOLS = P>,
+    M: Unsigned,
+    N: Unsigned + ops::Mul<P> + ops::Mul<U1>,
+    P: Unsigned + ops::Mul<U1>,
+    Prod<N, P>: ArrayLength<T>,
+    Prod<N, U1>: ArrayLength<T>,
+    Prod<P, U1>: ArrayLength<T>,
+    T: ops::Add<T, Output = T>
+        + ops::Div<T, Output = T>
+        + ops::Mul<T, Output = T>
+        + ops::Sub<T, Output = T>
+        + Copy
+        + From<u8>
+        + Zero,
+{
+    let m = M::to_usize();
+    let dof: T = correction.dof(m);
+
+    let mx: Vector<T, N> = MatGen::from_fn(|i, _| mean(m, |r| x.get(r, i)));
+    let my: Vector<T, P> = MatGen::from_fn(|j, _| mean(m, |r| y.get(r, j)));
+
+    MatGen::from_fn(|i, j| {
+        let mut sum = T::zero();
+        for r in 0..m {
+            sum = sum + (x.get(r, i) - mx.buffer[i]) * (y.get(r, j) - my.buffer[j]);
+        }
+        sum / dof
+    })
+}
+
+/// Axis-wise statistics
+impl<T, NROWS, NCOLS> MatGen<T, NROWS, NCOLS>
+where
+    NROWS: Unsigned + ops::Mul<NCOLS> + ops::Mul<U1>,
+    NCOLS: Unsigned,
+    U1: ops::Mul<NCOLS>,
+    Prod<NROWS, NCOLS>: ArrayLength<T>,
+    Prod<NROWS, U1>: ArrayLength<T>,
+    Prod<U1, NCOLS>: ArrayLength<T>,
+    T: ops::Add<T, Output = T>
+        + ops::Div<T, Output = T>
+        + ops::Mul<T, Output = T>
+        + ops::Sub<T, Output = T>
+        + Copy
+        + From<u8>
+        + Zero,
+{
+    /// Returns the mean of each row
+    ///
+    /// # Panics
+    ///
+    /// This operation panics if the matrix has no columns
+    pub fn row_mean(&self) -> Vector<T, NROWS> {
+        let ncols = NCOLS::to_usize();
+        assert!(ncols >= 1);
+        MatGen::from_fn(|r, _| mean(ncols, |c| self.buffer[r * ncols + c]))
+    }
+
+    /// Returns the mean of each column
+    ///
+    /// # Panics
+    ///
+    /// This operation panics if the matrix has no rows
+    pub fn col_mean(&self) -> RowVector<T, NCOLS> {
+        let (nrows, ncols) = (NROWS::to_usize(), NCOLS::to_usize());
+        assert!(nrows >= 1);
+        MatGen::from_fn(|_, c| mean(nrows, |r| self.buffer[r * ncols + c]))
+    }
+
+    /// Returns the variance of each row
+    ///
+    /// # Panics
+    ///
+    /// This operation panics if the rows are too short for the `correction`: two elements for
+    /// `Correction::Bessel` and one for `Correction::Population`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate mat;
+    /// extern crate typenum;
+    ///
+    /// use mat::stats::Correction;
+    /// use mat::MatGen;
+    /// use typenum::{U2, U4};
+    ///
+    /// # fn main() {
+    /// // one signal per row
+    /// let signals = [[1., 2., 3., 4.], [0., 0., 2., 2.]];
+    /// let signals = MatGen::<f32, U2, U4>::from_fn(|r, c| signals[r][c]);
+    ///
+    /// assert_eq!(signals.row_mean().get(1), 1.);
+    /// assert_eq!(signals.row_variance(Correction::Population).get(0), 1.25);
+    /// assert_eq!(signals.row_variance(Correction::Bessel).get(1), 4. / 3.);
+    /// assert_eq!(signals.row_std(Correction::Population).get(1), 1.);
+    ///
+    /// // the columns are pairs of samples
+    /// assert_eq!(signals.col_std(Correction::Population).get(0), 0.5);
+    /// # }
+    /// ```
+    pub fn row_variance(&self, correction: Correction) -> Vector<T, NROWS> {
+        let ncols = NCOLS::to_usize();
+        let dof: T = correction.dof(ncols);
+        MatGen::from_fn(|r, _| sum_sq_dev(ncols, |c| self.buffer[r * ncols + c]) / dof)
+    }
+
+    /// Returns the variance of each column
+    ///
+    /// # Panics
+    ///
+    /// This operation panics if the columns are too short for the `correction`: two elements for
+    /// `Correction::Bessel` and one for `Correction::Population`
+    pub fn col_variance(&self, correction: Correction) -> RowVector<T, NCOLS> {
+        let (nrows, ncols) = (NROWS::to_usize(), NCOLS::to_usize());
+        let dof: T = correction.dof(nrows);
+        MatGen::from_fn(|_, c| sum_sq_dev(nrows, |r| self.buffer[r * ncols + c]) / dof)
+    }
+
+    /// Returns the standard deviation of each row
+    ///
+    /// # Panics
+    ///
+    /// See `row_variance`
+    pub fn row_std(&self, correction: Correction) -> Vector<T, NROWS>
+    where
+        T: Sqrt,
+    {
+        let var = self.row_variance(correction);
+        MatGen::from_fn(|r, _| var.buffer[r].sqrt())
+    }
+
+    /// Returns the standard deviation of each column
+    ///
+    /// # Panics
+    ///
+    /// See `col_variance`
+    pub fn col_std(&self, correction: Correction) -> RowVector<T, NCOLS>
+    where
+        T: Sqrt,
+    {
+        let var = self.col_variance(correction);
+        MatGen::from_fn(|_, c| var.buffer[c].sqrt())
+    }
+}
+
+/// Element types that can draw samples from the standard normal distribution
+///
+/// Implemented for `f32` with the `libm` or the `fast-math` feature, and for `f64` with the `libm`
+/// feature.
+#[cfg(feature = "rand_core")]
+pub trait StandardNormal: Sized {
+    /// Draws a sample from the standard normal distribution using the Box-Muller transform
+    fn sample<R>(rng: &mut R) -> Self
+    where
+        R: ::rand_core::Rng + ?Sized;
+}
+
+#[cfg(feature = "rand_core")]
+macro_rules! standard_normal {
+    ($($(#[$attr:meta])* $T:ident: $next:ident, $bits:expr, $shift:expr;)+) => {
+        $(
+            $(#[$attr])*
+            impl StandardNormal for $T {
+                fn sample<R>(rng: &mut R) -> $T
+                where
+                    R: ::rand_core::Rng + ?Sized,
+                {
+                    let scale = 1. / (1_u64 << $bits) as $T;
+                    // `u1` is in `(0, 1]` so its logarithm is finite
+                    let u1 = ((rng.$next() >> $shift) + 1) as $T * scale;
+                    let u2 = (rng.$next() >> $shift) as $T * scale;
+
+                    let two: $T = 2.;
+                    Sqrt::sqrt(-two * Ln::ln(u1)) * Trig::cos(two * ::core::$T::consts::PI * u2)
+                }
+            }
+        )+
+    };
+}
+
+#[cfg(feature = "rand_core")]
+standard_normal! {
+    #[cfg(any(feature = "libm", feature = "fast-math"))]
+    f32: next_u32, 24, 8;
+    #[cfg(feature = "libm")]
+    f64: next_u64, 53, 11;
+}
+
+/// Draws a sample from the multivariate normal distribution with the given `mean` and the
+/// covariance `L Lᵀ`
+///
+/// `chol` is the lower triangular Cholesky factor `L` of the covariance; its elements above the
+/// diagonal are ignored. The sample is `mean + L z` where `z` is a vector of independent standard
+/// normal samples drawn from `rng`.
+///
+/// # Example
+///
+/// ```
+/// extern crate mat;
+/// extern crate rand_core;
+/// extern crate rand_xorshift;
+/// extern crate typenum;
+///
+/// use mat::{stats, MatGen, Vector};
+/// use rand_core::SeedableRng;
+/// use rand_xorshift::XorShiftRng;
+/// use typenum::U2;
+///
+/// # fn main() {
+/// let mut rng = XorShiftRng::from_seed([42; 16]);
+///
+/// // covariance [[4, 2], [2, 2]] = L Lᵀ
+/// let mean = Vector::<f64, U2>::from_fn(|r, _| [1., -1.][r]);
+/// let l = MatGen::<f64, U2, U2>::from_fn(|r, c| [[2., 0.], [1., 1.]][r][c]);
+///
+/// let (mut sum, mut sum_sq, mut sum_xy) = ([0.; 2], [0.; 2], 0.);
+/// let n = 10_000;
+/// for _ in 0..n {
+///     let x = stats::sample_gaussian(&mean, &l, &mut rng);
+///     for i in 0..2 {
+///         sum[i] += x[i];
+///         sum_sq[i] += (x[i] - mean[i]) * (x[i] - mean[i]);
+///     }
+///     sum_xy += (x[0] - mean[0]) * (x[1] - mean[1]);
+/// }
+///
+/// let n = f64::from(n);
+/// assert!((sum[0] / n - 1.).abs() < 0.1 && (sum[1] / n + 1.).abs() < 0.1);
+/// assert!((sum_sq[0] / n - 4.).abs() < 0.2 && (sum_sq[1] / n - 2.).abs() < 0.2);
+/// assert!((sum_xy / n - 2.).abs() < 0.2);
+/// # }
+/// ```
+#[cfg(feature = "rand_core")]
+pub fn sample_gaussian<L, R, T, N>(mean: &Vector<T, N>, chol: L, rng: &mut R) -> Vector<T, N>
+where
+    L: Matrix<Elem = T, NROWS = N, NCOLS = N>,
+    R: ::rand_core::Rng + ?Sized,
+    N: Unsigned + ops::Mul<U1>,
+    Prod<N, U1>: ArrayLength<T>,
+    T: ops::Add<T, Output = T> + ops::Mul<T, Output = T> + Copy + StandardNormal + Zero,
+{
+    let mut z: Vector<T, N> = MatGen::zeros();
+    for x in z.as_mut_slice() {
+        *x = T::sample(rng);
+    }
+
+    MatGen::from_fn(|r, _| {
+        (0..r + 1).fold(mean.buffer[r], |acc, k| acc + chol.get(r, k) * z.buffer[k])
+    })
+}
+
+// `n` as a `T`; NOTE `n` may not fit in a `u8`
+fn count<T>(n: usize) -> T
+where
+    T: ops::Add<T, Output = T> + From<u8> + Zero,
+{
+    (0..n).fold(T::zero(), |acc, _| acc + T::from(1))
+}
+
+fn mean<T, F>(n: usize, f: F) -> T
+where
+    T: ops::Add<T, Output = T> + ops::Div<T, Output = T> + Copy + From<u8> + Zero,
+    F: Fn(usize) -> T,
+{
+    (0..n).fold(T::zero(), |acc, i| acc + f(i)) / count(n)
+}
+
+// sum of the squared deviations from the mean
+fn sum_sq_dev<T, F>(n: usize, f: F) -> T
+where
+    T: ops::Add<T, Output = T>
+        + ops::Div<T, Output = T>
+        + ops::Mul<T, Output = T>
+        + ops::Sub<T, Output = T>
+        + Copy
+        + From<u8>
+        + Zero,
+    F: Fn(usize) -> T,
+{
+    let mean = mean(n, &f);
+    (0..n).fold(T::zero(), |acc, i| {
+        let d = f(i) - mean;
+        acc + d * d
+    })
+}
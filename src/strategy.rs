@@ -0,0 +1,135 @@
+//! Interoperability with `proptest`
+//!
+//! `MatGen` implements `Arbitrary` so `any::<MatGen<T, NROWS, NCOLS>>()` generates matrices of
+//! arbitrary elements. `matrix` generates matrices whose elements are drawn from a given strategy,
+//! e.g. a range. `f32_with_specials` and `f64_with_specials` occasionally replace the values of a
+//! strategy with special values (NaN, infinities, extremes, signed zeros) to exercise the edge
+//! cases of numeric code.
+//!
+//! Generated matrices shrink element-wise.
+//!
+//! # Example
+//!
+//! ```
+//! extern crate mat;
+//! extern crate proptest;
+//! extern crate typenum;
+//!
+//! use mat::MatGen;
+//! use mat::strategy;
+//! use mat::traits::{Matrix, Transpose};
+//! use proptest::prelude::*;
+//! use proptest::test_runner::TestRunner;
+//! use typenum::{U2, U3};
+//!
+//! # fn main() {
+//! let mut runner = TestRunner::default();
+//!
+//! runner
+//!     .run(&any::<MatGen<i32, U2, U3>>(), |m| {
+//!         prop_assert!(m == m.t().t());
+//!         Ok(())
+//!     })
+//!     .unwrap();
+//!
+//! let elements = strategy::f32_with_specials(-1_f32..1.);
+//! runner
+//!     .run(&strategy::matrix::<_, U2, U3>(elements), |m| {
+//!         // `m mᵀ` is symmetric, even with NaNs and infinities
+//!         let p = (&m * m.t()).eval();
+//!         let (a, b) = (p.get(0, 1), p.get(1, 0));
+//!         prop_assert!(a == b || a.is_nan() && b.is_nan());
+//!         Ok(())
+//!     })
+//!     .unwrap();
+//! # }
+//! ```
+
+use core::{f32, f64, ops};
+use std::vec::Vec;
+
+use generic_array::ArrayLength;
+use proptest::arbitrary::{any_with, Arbitrary};
+use proptest::collection::{self, VecStrategy};
+use proptest::strategy::{Just, Map, Strategy};
+use typenum::{Prod, Unsigned};
+
+use {array, MatGen};
+
+/// The strategy returned by `matrix`
+pub type MatrixStrategy<S, NROWS, NCOLS> = Map<
+    VecStrategy<S>,
+    fn(Vec<<S as Strategy>::Value>) -> MatGen<<S as Strategy>::Value, NROWS, NCOLS>,
+>;
+
+/// Generates matrices whose elements are drawn from `elements`
+pub fn matrix<S, NROWS, NCOLS>(elements: S) -> MatrixStrategy<S, NROWS, NCOLS>
+where
+    NROWS: Unsigned + ops::Mul<NCOLS>,
+    NCOLS: Unsigned,
+    Prod<NROWS, NCOLS>: ArrayLength<S::Value>,
+    S: Strategy,
+    S::Value: Clone,
+{
+    let n = NROWS::to_usize() * NCOLS::to_usize();
+    collection::vec(elements, n).prop_map(from_vec)
+}
+
+fn from_vec<T, NROWS, NCOLS>(elements: Vec<T>) -> MatGen<T, NROWS, NCOLS>
+where
+    NROWS: Unsigned + ops::Mul<NCOLS>,
+    NCOLS: Unsigned,
+    Prod<NROWS, NCOLS>: ArrayLength<T>,
+    T: Clone,
+{
+    // NOTE `matrix` generates vectors of exactly `NROWS * NCOLS` elements
+    MatGen::new(array::generate(|i| elements[i].clone()))
+}
+
+impl<T, NROWS, NCOLS> Arbitrary for MatGen<T, NROWS, NCOLS>
+where
+    NROWS: Unsigned + ops::Mul<NCOLS>,
+    NCOLS: Unsigned,
+    Prod<NROWS, NCOLS>: ArrayLength<T>,
+    T: Arbitrary + Clone,
+{
+    type Parameters = T::Parameters;
+    type Strategy = MatrixStrategy<T::Strategy, NROWS, NCOLS>;
+
+    fn arbitrary_with(parameters: T::Parameters) -> Self::Strategy {
+        matrix(any_with::<T>(parameters))
+    }
+}
+
+macro_rules! with_specials {
+    ($($fn:ident, $ty:ident;)+) => {
+        $(
+            /// Replaces about one in ten of the values of `values` with a special value: NaN,
+            /// positive or negative infinity, the largest and smallest finite values, the smallest
+            /// positive normal value, or positive or negative zero
+            pub fn $fn<S>(values: S) -> impl Strategy<Value = $ty>
+            where
+                S: Strategy<Value = $ty>,
+            {
+                prop_oneof![
+                    9 => values,
+                    1 => prop_oneof![
+                        Just($ty::NAN),
+                        Just($ty::INFINITY),
+                        Just($ty::NEG_INFINITY),
+                        Just($ty::MAX),
+                        Just($ty::MIN),
+                        Just($ty::MIN_POSITIVE),
+                        Just(0.),
+                        Just(-0.),
+                    ],
+                ]
+            }
+        )+
+    }
+}
+
+with_specials! {
+    f32_with_specials, f32;
+    f64_with_specials, f64;
+}
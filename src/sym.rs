@@ -0,0 +1,211 @@
+use core::ops;
+
+use generic_array::{ArrayLength, GenericArray};
+use typenum::{Add1, B1, Prod, Quot, U2, Unsigned};
+
+use {array, Product};
+use traits::{Matrix, UnsafeGet};
+
+/// Number of elements in the upper triangle, diagonal included, of an `N x N` matrix
+pub type PackedLen<N> = Quot<Prod<N, Add1<N>>, U2>;
+
+/// Symmetric matrix that only stores its upper triangle
+///
+/// The `N (N + 1) / 2` elements on and above the diagonal are stored in row major order; elements
+/// below the diagonal are read from their mirror image above it so the matrix is symmetric by
+/// construction.
+///
+/// # Example
+///
+/// ```
+/// extern crate mat;
+/// extern crate typenum;
+///
+/// use std::mem;
+///
+/// use mat::{MatGen, SymMat};
+/// use mat::traits::Matrix;
+/// use typenum::U4;
+///
+/// # fn main() {
+/// // covariance matrix
+/// let mut p = SymMat::<f32, U4>::from_fn(|r, c| if r == c { 1. } else { 0.1 * (r + c) as f32 });
+/// assert_eq!(mem::size_of_val(&p), 10 * mem::size_of::<f32>());
+///
+/// p.set(3, 1, 0.5);
+/// assert_eq!((&p).get(1, 3), 0.5);
+/// assert_eq!((&p).get(3, 1), 0.5);
+///
+/// let x = MatGen::<f32, U4, U4>::identity();
+/// assert_eq!((&p * &x).eval(), (&p).eval());
+/// # }
+/// ```
+pub struct SymMat<T, N>
+where
+    N: Unsigned + ops::Add<B1> + ops::Mul<Add1<N>>,
+    Prod<N, Add1<N>>: ops::Div<U2>,
+    PackedLen<N>: ArrayLength<T>,
+    T: Clone,
+{
+    upper: GenericArray<T, PackedLen<N>>,
+}
+
+impl<T, N> Clone for SymMat<T, N>
+where
+    N: Unsigned + ops::Add<B1> + ops::Mul<Add1<N>>,
+    Prod<N, Add1<N>>: ops::Div<U2>,
+    PackedLen<N>: ArrayLength<T>,
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        let upper = self.packed();
+        SymMat::new(array::generate(|i| upper[i].clone()))
+    }
+}
+
+impl<T, N> SymMat<T, N>
+where
+    N: Unsigned + ops::Add<B1> + ops::Mul<Add1<N>>,
+    Prod<N, Add1<N>>: ops::Div<U2>,
+    PackedLen<N>: ArrayLength<T>,
+    T: Clone,
+{
+    /// Creates a symmetric matrix from its packed upper triangle, in row major order
+    pub fn new(upper: GenericArray<T, PackedLen<N>>) -> Self {
+        SymMat { upper }
+    }
+
+    /// Creates a symmetric matrix where the elements at `(r, c)` and `(c, r)` are `f(r, c)`
+    ///
+    /// `f` is only called with `r <= c`.
+    pub fn from_fn<F>(f: F) -> Self
+    where
+        F: Fn(usize, usize) -> T,
+    {
+        let n = N::to_usize();
+        SymMat::new(array::generate(|i| {
+            let (r, c) = upper_position(n, i);
+            f(r, c)
+        }))
+    }
+
+    /// Creates a symmetric matrix from the upper triangle of `m`; the lower triangle is ignored
+    pub fn from_upper<M>(m: M) -> Self
+    where
+        M: Matrix<Elem = T, NROWS = N, NCOLS = N>,
+    {
+        SymMat::from_fn(|r, c| unsafe { m.unsafe_get(r, c) })
+    }
+
+    /// Returns the packed upper triangle
+    pub fn packed(&self) -> &[T] {
+        array::as_slice(&self.upper)
+    }
+
+    /// Sets the elements at `(r, c)` and `(c, r)` to `value`
+    ///
+    /// # Panics
+    ///
+    /// This operation panics if `r` or `c` exceed the matrix dimensions
+    pub fn set(&mut self, r: usize, c: usize, value: T) {
+        let n = N::to_usize();
+        assert!(r < n && c < n);
+
+        array::as_mut_slice(&mut self.upper)[upper_index(n, r, c)] = value;
+    }
+
+    /// Sets the elements at `(r, c)` and `(c, r)` to `value`
+    ///
+    /// Returns `value` back if `r` or `c` exceed the matrix dimensions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate mat;
+    /// extern crate typenum;
+    ///
+    /// use mat::SymMat;
+    /// use mat::traits::Matrix;
+    /// use typenum::U2;
+    ///
+    /// # fn main() {
+    /// let mut p = SymMat::<i32, U2>::from_fn(|_, _| 0);
+    ///
+    /// assert_eq!(p.set_checked(1, 0, 3), Ok(()));
+    /// assert_eq!(p.set_checked(0, 2, 4), Err(4));
+    /// assert_eq!((&p).get(0, 1), 3);
+    /// # }
+    /// ```
+    pub fn set_checked(&mut self, r: usize, c: usize, value: T) -> Result<(), T> {
+        let n = N::to_usize();
+        no_panic!(if r < n && c < n {
+            match array::as_mut_slice(&mut self.upper).get_mut(upper_index(n, r, c)) {
+                Some(elem) => {
+                    *elem = value;
+                    Ok(())
+                }
+                None => Err(value),
+            }
+        } else {
+            Err(value)
+        })
+    }
+}
+
+/// Index of the element at `(r, c)`, or `(c, r)` if `r > c`, in the packed upper triangle of an `n
+/// x n` matrix
+pub(crate) fn upper_index(n: usize, r: usize, c: usize) -> usize {
+    let (r, c) = if r <= c { (r, c) } else { (c, r) };
+    r * (2 * n + 1 - r) / 2 + c - r
+}
+
+/// Row and column of the element at index `i` of the packed upper triangle of an `n x n` matrix
+pub(crate) fn upper_position(n: usize, i: usize) -> (usize, usize) {
+    // row `r` starts at index `start` and has `n - r` elements
+    let (mut r, mut start) = (0, 0);
+    while i >= start + n - r {
+        start += n - r;
+        r += 1;
+    }
+    (r, r + i - start)
+}
+
+impl<T, N> Matrix for &SymMat<T, N>
+where
+    N: Unsigned + ops::Add<B1> + ops::Mul<Add1<N>>,
+    Prod<N, Add1<N>>: ops::Div<U2>,
+    PackedLen<N>: ArrayLength<T>,
+    T: Clone,
+{
+    type NROWS = N;
+    type NCOLS = N;
+}
+
+impl<T, N> UnsafeGet for &SymMat<T, N>
+where
+    N: Unsigned + ops::Add<B1> + ops::Mul<Add1<N>>,
+    Prod<N, Add1<N>>: ops::Div<U2>,
+    PackedLen<N>: ArrayLength<T>,
+    T: Clone,
+{
+    type Elem = T;
+
+    unsafe fn unsafe_get(self, r: usize, c: usize) -> T {
+        self.packed().get_unchecked(upper_index(N::to_usize(), r, c)).clone()
+    }
+}
+
+impl<'a, T, N, R> ops::Mul<R> for &'a SymMat<T, N>
+where
+    N: Unsigned + ops::Add<B1> + ops::Mul<Add1<N>>,
+    Prod<N, Add1<N>>: ops::Div<U2>,
+    PackedLen<N>: ArrayLength<T>,
+    T: Clone,
+    R: Matrix<NROWS = N>,
+{
+    type Output = Product<&'a SymMat<T, N>, R>;
+
+    fn mul(self, rhs: R) -> Self::Output {
+        Product { l: self, r: rhs }
+    }
+}
@@ -0,0 +1,519 @@
+//! Text formatting and parsing of matrices
+//!
+//! `Display` prints one row per line, each row enclosed in brackets, with every element padded to
+//! the same width so the columns line up. The precision and the width of the formatter apply to
+//! each element; the width is a minimum, the elements are padded further if any of them is wider.
+//!
+//! ```
+//! extern crate mat;
+//!
+//! use mat::mat;
+//!
+//! # fn main() {
+//! let m = mat![
+//!     [1, -20],
+//!     [300, 4],
+//! ];
+//! assert_eq!(format!("{}", m), "[  1 -20]\n[300   4]");
+//! assert_eq!(format!("{:5}", m), "[    1   -20]\n[  300     4]");
+//!
+//! let m = mat![
+//!     [1., -2.5],
+//!     [10.5, 4.],
+//! ];
+//! assert_eq!(format!("{:.1}", m), "[ 1.0 -2.5]\n[10.5  4.0]");
+//! # }
+//! ```
+//!
+//! `Debug` prints an array of rows. The alternate form, `{:#?}`, prints one row per line.
+//! Expression trees are formatted by `Display` like the matrix they evaluate to, while their
+//! `Debug` implementation prints their structure, like `Matrix::tree`; `eval` them to see their
+//! elements in the `Debug` format.
+//!
+//! ```
+//! extern crate mat;
+//!
+//! use mat::mat;
+//! use mat::traits::{Matrix, Transpose};
+//!
+//! # fn main() {
+//! let a = mat![
+//!     [1, 2],
+//!     [3, 4],
+//! ];
+//! assert_eq!(format!("{:?}", a), "[[1, 2], [3, 4]]");
+//! assert_eq!(format!("{:#?}", a), "[\n    [1, 2],\n    [3, 4],\n]");
+//!
+//! assert_eq!(format!("{}", &a * a.t() + &a), "[ 6 13]\n[14 29]");
+//! assert_eq!(format!("{:?}", &a * a.t() + &a), "Add(Mul(2x2, T(2x2)), 2x2)");
+//! assert_eq!(format!("{:?}", (&a * a.t() + &a).eval()), "[[6, 13], [14, 29]]");
+//! # }
+//! ```
+//!
+//! `MatGen` implements `FromStr` for the `Display` format: one bracketed row after the other, with
+//! the elements of a row separated by whitespace. The number of rows and columns must match the
+//! dimensions of the matrix type.
+//!
+//! ```
+//! extern crate mat;
+//! extern crate typenum;
+//!
+//! use mat::{MatGen, ParseError};
+//! use mat::traits::Matrix;
+//! use typenum::{U2, U3};
+//!
+//! # fn main() {
+//! let m: MatGen<f32, U2, U3> = "[1 2.5 3]\n[-4 5 6e3]".parse().unwrap();
+//! assert_eq!(m.get(1, 2), 6000.);
+//!
+//! let m2: MatGen<f32, U2, U3> = format!("{}", m).parse().unwrap();
+//! assert_eq!(format!("{:?}", m2), format!("{:?}", m));
+//!
+//! assert_eq!(
+//!     "[1 2 3] [4 5]".parse::<MatGen<f32, U2, U3>>().err(),
+//!     Some(ParseError::ColumnCount { row: 1 }),
+//! );
+//! assert_eq!(
+//!     "[1 2 3] [4 five 6]".parse::<MatGen<f32, U2, U3>>().err(),
+//!     Some(ParseError::Element { row: 1, col: 1 }),
+//! );
+//! # }
+//! ```
+
+use core::str::FromStr;
+use core::{cmp, fmt, ops};
+
+use generic_array::ArrayLength;
+use typenum::{Prod, Unsigned};
+
+use traits::{Matrix, UnsafeGet};
+use {array, Affine, Conjugate, DiagProduct, Kronecker, Mat, MatGen, Outer, Product, Sum};
+use {Transpose, Tree, TriangularProduct};
+
+/// Error returned when parsing a matrix from text fails
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// Row `row` is not enclosed in brackets
+    Bracket {
+        /// Row index
+        row: usize,
+    },
+    /// Row `row` doesn't have as many elements as the matrix has columns
+    ColumnCount {
+        /// Row index
+        row: usize,
+    },
+    /// The element at row `row` and column `col` is not a valid element
+    Element {
+        /// Row index
+        row: usize,
+        /// Column index
+        col: usize,
+    },
+    /// The text doesn't have as many rows as the matrix
+    RowCount,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParseError::Bracket { row } => write!(f, "row {} is not enclosed in brackets", row),
+            ParseError::ColumnCount { row } => write!(f, "wrong number of columns in row {}", row),
+            ParseError::Element { row, col } => {
+                write!(f, "invalid element at row {} and column {}", row, col)
+            }
+            ParseError::RowCount => f.write_str("wrong number of rows"),
+        }
+    }
+}
+
+impl<T, NROWS, NCOLS> FromStr for MatGen<T, NROWS, NCOLS>
+where
+    NROWS: Unsigned + ops::Mul<NCOLS>,
+    NCOLS: Unsigned,
+    Prod<NROWS, NCOLS>: ArrayLength<T>,
+    T: Clone + FromStr,
+{
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let (nrows, ncols) = (NROWS::to_usize(), NCOLS::to_usize());
+
+        let mut elems = array::Builder::new();
+        let mut rest = s.trim();
+        let mut r = 0;
+        while !rest.is_empty() {
+            let end = match rest.find(']') {
+                Some(end) if rest.starts_with('[') => end,
+                _ => return Err(ParseError::Bracket { row: r }),
+            };
+
+            if r == nrows {
+                return Err(ParseError::RowCount);
+            }
+
+            parse_row(&mut elems, r, ncols, rest[1..end].split_whitespace())?;
+
+            rest = rest[end + 1..].trim();
+            r += 1;
+        }
+
+        // every row has `ncols` elements so the array is full iff there are `nrows` rows
+        elems.finish().map(MatGen::new).ok_or(ParseError::RowCount)
+    }
+}
+
+impl<T, BUFFER, NROWS, NCOLS> Mat<T, BUFFER, NROWS, NCOLS>
+where
+    BUFFER: AsRef<[T]>,
+    NROWS: Unsigned,
+    NCOLS: Unsigned,
+    T: Clone + fmt::Display,
+{
+    /// Writes the matrix as comma separated values, one row per line
+    ///
+    /// See `MatGen::write_csv`
+    pub fn write_csv<W>(&self, w: &mut W, precision: Option<usize>) -> fmt::Result
+    where
+        W: fmt::Write,
+    {
+        let slice: &[T] = self.buffer.as_ref();
+        write_csv(w, slice, NROWS::to_usize(), NCOLS::to_usize(), precision)
+    }
+}
+
+impl<T, NROWS, NCOLS> MatGen<T, NROWS, NCOLS>
+where
+    NROWS: Unsigned + ops::Mul<NCOLS>,
+    NCOLS: Unsigned,
+    Prod<NROWS, NCOLS>: ArrayLength<T>,
+    T: Clone + fmt::Display,
+{
+    /// Writes the matrix as comma separated values, one row per line
+    ///
+    /// If `precision` is specified the elements are formatted with that many digits after the
+    /// decimal point. Every row, including the last one, ends with a newline.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate mat;
+    /// extern crate typenum;
+    ///
+    /// use mat::MatGen;
+    /// use mat::traits::Matrix;
+    /// use typenum::{U2, U3};
+    ///
+    /// # fn main() {
+    /// let m = MatGen::<f32, U2, U3>::from_fn(|r, c| (3 * r + c) as f32 / 4.);
+    ///
+    /// let mut csv = String::new();
+    /// m.write_csv(&mut csv, Some(2)).unwrap();
+    /// assert_eq!(csv, "0.00,0.25,0.50\n0.75,1.00,1.25\n");
+    ///
+    /// let m2 = MatGen::<f32, U2, U3>::from_csv(&csv).unwrap();
+    /// assert_eq!(m2.get(1, 2), 1.25);
+    /// # }
+    /// ```
+    pub fn write_csv<W>(&self, w: &mut W, precision: Option<usize>) -> fmt::Result
+    where
+        W: fmt::Write,
+    {
+        write_csv(w, self.as_slice(), NROWS::to_usize(), NCOLS::to_usize(), precision)
+    }
+}
+
+impl<T, NROWS, NCOLS> MatGen<T, NROWS, NCOLS>
+where
+    NROWS: Unsigned + ops::Mul<NCOLS>,
+    NCOLS: Unsigned,
+    Prod<NROWS, NCOLS>: ArrayLength<T>,
+    T: Clone + FromStr,
+{
+    /// Parses a matrix from comma separated values, one row per line
+    ///
+    /// Whitespace around the elements and blank lines are ignored. The number of rows and columns
+    /// must match the dimensions of the matrix type. As `write_csv` writes them, the rows of a
+    /// matrix without columns are empty lines, which are counted instead.
+    pub fn from_csv(s: &str) -> Result<Self, ParseError> {
+        let (nrows, ncols) = (NROWS::to_usize(), NCOLS::to_usize());
+
+        if ncols == 0 {
+            let mut lines = 0;
+            for line in s.lines() {
+                if !line.trim().is_empty() {
+                    return Err(ParseError::ColumnCount { row: lines });
+                }
+                lines += 1;
+            }
+
+            if lines != nrows {
+                return Err(ParseError::RowCount);
+            }
+
+            // NOTE every line is blank so the empty array is finished below
+        }
+
+        let mut elems = array::Builder::new();
+        for (r, line) in s.lines().filter(|line| !line.trim().is_empty()).enumerate() {
+            if r == nrows {
+                return Err(ParseError::RowCount);
+            }
+
+            parse_row(&mut elems, r, ncols, line.split(',').map(str::trim))?;
+        }
+
+        // every row has `ncols` elements so the array is full iff there are `nrows` rows
+        elems.finish().map(MatGen::new).ok_or(ParseError::RowCount)
+    }
+}
+
+/// Parses the `ncols` elements of row `r` from `tokens` and appends them to `elems`
+fn parse_row<'a, T, N, I>(
+    elems: &mut array::Builder<T, N>,
+    r: usize,
+    ncols: usize,
+    tokens: I,
+) -> Result<(), ParseError>
+where
+    I: Iterator<Item = &'a str>,
+    N: ArrayLength<T>,
+    T: FromStr,
+{
+    let mut c = 0;
+    for token in tokens {
+        if c == ncols {
+            return Err(ParseError::ColumnCount { row: r });
+        }
+
+        let elem = token.parse().map_err(|_| ParseError::Element { row: r, col: c })?;
+        // NOTE the callers stop before row `nrows` so there's always room for this row
+        elems.push(elem).map_err(|_| ParseError::RowCount)?;
+        c += 1;
+    }
+
+    if c != ncols {
+        return Err(ParseError::ColumnCount { row: r });
+    }
+
+    Ok(())
+}
+
+fn write_csv<W, T>(
+    w: &mut W,
+    buffer: &[T],
+    nrows: usize,
+    ncols: usize,
+    precision: Option<usize>,
+) -> fmt::Result
+where
+    W: fmt::Write,
+    T: fmt::Display,
+{
+    for r in 0..nrows {
+        let row = &buffer[r * ncols..(r + 1) * ncols];
+        for (c, elem) in row.iter().enumerate() {
+            if c != 0 {
+                w.write_str(",")?;
+            }
+
+            write_elem(w, elem, precision)?;
+        }
+        w.write_str("\n")?;
+    }
+
+    Ok(())
+}
+
+macro_rules! fmt {
+    ($($Node:ident<$($P:ident),+>,)+) => {
+        $(
+            /// Formats the structure of the expression tree; see `Matrix::tree`
+            impl<$($P),+> fmt::Debug for $Node<$($P),+>
+            where
+                $Node<$($P),+>: Matrix,
+            {
+                fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                    self.fmt_tree(f)
+                }
+            }
+
+            /// Formats the matrix the expression evaluates to
+            impl<$($P),+> fmt::Display for $Node<$($P),+>
+            where
+                $Node<$($P),+>: Matrix,
+                <$Node<$($P),+> as UnsafeGet>::Elem: fmt::Display,
+            {
+                fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                    display(f, *self)
+                }
+            }
+        )+
+    }
+}
+
+fmt!(
+    Affine<W, X, B>,
+    Conjugate<M>,
+    DiagProduct<D, M>,
+    Kronecker<L, R>,
+    Outer<L, R>,
+    Product<L, R>,
+    Sum<L, R>,
+    Transpose<M>,
+    TriangularProduct<L, R>,
+);
+
+impl<T, BUFFER, NROWS, NCOLS> fmt::Display for Mat<T, BUFFER, NROWS, NCOLS>
+where
+    BUFFER: AsRef<[T]>,
+    NROWS: Unsigned,
+    NCOLS: Unsigned,
+    T: Clone + fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        display(f, self)
+    }
+}
+
+impl<T, NROWS, NCOLS> fmt::Display for MatGen<T, NROWS, NCOLS>
+where
+    NROWS: Unsigned + ops::Mul<NCOLS>,
+    NCOLS: Unsigned,
+    Prod<NROWS, NCOLS>: ArrayLength<T>,
+    T: Clone + fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        display(f, self)
+    }
+}
+
+impl<M> fmt::Debug for Tree<M>
+where
+    M: Matrix,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.m.fmt_tree(f)
+    }
+}
+
+/// Formats the operation `name` applied to the operands `l` and `r`
+pub fn fmt_node<L, R>(f: &mut fmt::Formatter, name: &str, l: L, r: R) -> fmt::Result
+where
+    L: Matrix,
+    R: Matrix,
+{
+    write!(f, "{}(", name)?;
+    l.fmt_tree(f)?;
+    f.write_str(", ")?;
+    r.fmt_tree(f)?;
+    f.write_str(")")
+}
+
+/// Formats `m` as an array of rows, one row per line in the alternate form
+pub fn debug<M>(f: &mut fmt::Formatter, m: M) -> fmt::Result
+where
+    M: Matrix,
+    M::Elem: fmt::Debug,
+{
+    let (nrows, ncols) = m.size();
+
+    f.write_str("[")?;
+    for r in 0..nrows {
+        if f.alternate() {
+            f.write_str("\n    ")?;
+        } else if r != 0 {
+            f.write_str(", ")?;
+        }
+
+        f.write_str("[")?;
+        for c in 0..ncols {
+            if c != 0 {
+                f.write_str(", ")?;
+            }
+
+            write!(f, "{:?}", unsafe { m.unsafe_get(r, c) })?;
+        }
+        f.write_str("]")?;
+
+        if f.alternate() {
+            f.write_str(",")?;
+        }
+    }
+
+    if f.alternate() && nrows != 0 {
+        f.write_str("\n")?;
+    }
+    f.write_str("]")
+}
+
+fn display<M>(f: &mut fmt::Formatter, m: M) -> fmt::Result
+where
+    M: Matrix,
+    M::Elem: fmt::Display,
+{
+    let precision = f.precision();
+    let (nrows, ncols) = m.size();
+
+    // NOTE expression trees are evaluated twice: once to find the widest element and once to print
+    let mut width = f.width().unwrap_or(0);
+    for r in 0..nrows {
+        for c in 0..ncols {
+            width = cmp::max(width, len(&unsafe { m.unsafe_get(r, c) }, precision)?);
+        }
+    }
+
+    for r in 0..nrows {
+        if r != 0 {
+            f.write_str("\n")?;
+        }
+
+        f.write_str("[")?;
+        for c in 0..ncols {
+            if c != 0 {
+                f.write_str(" ")?;
+            }
+
+            let elem = unsafe { m.unsafe_get(r, c) };
+            for _ in len(&elem, precision)?..width {
+                f.write_str(" ")?;
+            }
+            write_elem(f, &elem, precision)?;
+        }
+        f.write_str("]")?;
+    }
+
+    Ok(())
+}
+
+/// Returns the number of characters `elem` is formatted into
+fn len<T>(elem: &T, precision: Option<usize>) -> Result<usize, fmt::Error>
+where
+    T: fmt::Display,
+{
+    let mut counter = Counter(0);
+    write_elem(&mut counter, elem, precision)?;
+    Ok(counter.0)
+}
+
+fn write_elem<W, T>(w: &mut W, elem: &T, precision: Option<usize>) -> fmt::Result
+where
+    W: fmt::Write,
+    T: fmt::Display,
+{
+    if let Some(precision) = precision {
+        write!(w, "{:.*}", precision, elem)
+    } else {
+        write!(w, "{}", elem)
+    }
+}
+
+/// A `fmt::Write` sink that counts characters
+struct Counter(usize);
+
+impl fmt::Write for Counter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0 += s.chars().count();
+        Ok(())
+    }
+}
@@ -1,6 +1,12 @@
 //! Traits
 
-use typenum::Unsigned;
+use core::{fmt, ops};
+
+use generic_array::ArrayLength;
+use typenum::{IsEqual, IsLess, Min, Prod, True, U1, Unsigned};
+
+use linalg;
+use MatGen;
 
 /// The transpose operation
 pub trait Transpose: Copy {
@@ -17,6 +23,13 @@ pub trait Matrix: UnsafeGet {
     /// Number of columns
     type NCOLS: Unsigned;
 
+    /// Number of rows, as a constant
+    const NROWS: usize = Self::NROWS::USIZE;
+    /// Number of columns, as a constant
+    const NCOLS: usize = Self::NCOLS::USIZE;
+    /// Number of rows and columns, as a constant
+    const SHAPE: (usize, usize) = (Self::NROWS::USIZE, Self::NCOLS::USIZE);
+
     /// Returns the element at row `r` and column `c`
     ///
     /// # Panics
@@ -28,6 +41,98 @@ pub trait Matrix: UnsafeGet {
         unsafe { self.unsafe_get(r, c) }
     }
 
+    /// Returns the element at row `R` and column `C`
+    ///
+    /// The indices are type level integers that are checked against the dimensions at compile
+    /// time, so no runtime bounds check is performed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate mat;
+    /// extern crate typenum;
+    ///
+    /// use mat::traits::Matrix;
+    /// use typenum::{U0, U1};
+    ///
+    /// # fn main() {
+    /// let mut m = mat::mat2([[1, 2], [3, 4]]);
+    /// assert_eq!((&m).get_at::<U1, U0>(), 3);
+    ///
+    /// m.set_at::<U0, U1>(5);
+    /// assert_eq!(m, mat::mat2([[1, 5], [3, 4]]));
+    /// # }
+    /// ```
+    ///
+    /// Out of bounds indices don't compile
+    ///
+    /// ``` compile_fail
+    /// extern crate mat;
+    /// extern crate typenum;
+    ///
+    /// use mat::traits::Matrix;
+    /// use typenum::{U0, U2};
+    ///
+    /// # fn main() {
+    /// let m = mat::mat2([[1, 2], [3, 4]]);
+    /// (&m).get_at::<U2, U0>();
+    /// # }
+    /// ```
+    fn get_at<R, C>(self) -> Self::Elem
+    where
+        R: IsLess<Self::NROWS, Output = True> + Unsigned,
+        C: IsLess<Self::NCOLS, Output = True> + Unsigned,
+    {
+        unsafe { self.unsafe_get(R::to_usize(), C::to_usize()) }
+    }
+
+    /// Returns the element at row `r` and column `c`, or `None` if `r` or `c` exceed the matrix
+    /// dimensions
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mat::traits::{Matrix, Transpose};
+    ///
+    /// let m = mat::mat2([[1, 2], [3, 4]]);
+    ///
+    /// assert_eq!((&m).get_checked(0, 1), Some(2));
+    /// assert_eq!((&m).t().get_checked(0, 1), Some(3));
+    /// assert_eq!((&m * &m).get_checked(2, 0), None);
+    /// ```
+    fn get_checked(self, r: usize, c: usize) -> Option<Self::Elem> {
+        no_panic!(if r < self.nrows() && c < self.ncols() {
+            Some(unsafe { self.unsafe_get(r, c) })
+        } else {
+            None
+        })
+    }
+
+    /// Returns row `r` as a new row vector, or `None` if `r` exceeds the number of rows
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mat::traits::{Matrix, Transpose};
+    ///
+    /// let m = mat::mat2([[1, 2], [3, 4]]);
+    ///
+    /// assert_eq!((&m).row_checked(1).unwrap(), mat::RowVector::from_fn(|_, c| [3, 4][c]));
+    /// assert_eq!((&m).t().row_checked(0).unwrap(), mat::RowVector::from_fn(|_, c| [1, 3][c]));
+    /// assert!((&m).row_checked(2).is_none());
+    /// ```
+    fn row_checked(self, r: usize) -> Option<::RowVector<Self::Elem, Self::NCOLS>>
+    where
+        U1: ops::Mul<Self::NCOLS>,
+        Prod<U1, Self::NCOLS>: ArrayLength<Self::Elem>,
+    {
+        no_panic!(if r < self.nrows() {
+            Some(MatGen::from_fn(|_, c| unsafe { self.unsafe_get(r, c) }))
+        } else {
+            None
+        })
+    }
+
     /// Returns the size of the matrix
     fn size(self) -> (usize, usize) {
         (Self::NROWS::to_usize(), Self::NCOLS::to_usize())
@@ -42,25 +147,776 @@ pub trait Matrix: UnsafeGet {
     fn ncols(self) -> usize {
         self.size().1
     }
+
+    /// Returns the inner product of two vectors
+    ///
+    /// Both operands must be column vectors or both must be row vectors.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mat::traits::Matrix;
+    ///
+    /// let a = mat::vec3(1, 2, 3);
+    /// let b = mat::vec3(4, 5, 6);
+    ///
+    /// assert_eq!(a.dot(&b), 32);
+    ///
+    /// // accumulate `i8` elements in an `i32` to avoid overflow
+    /// let a = mat::vec2(100_i8, 100);
+    /// let dot: i32 = a.dot_wide(&a);
+    /// assert_eq!(dot, 20_000);
+    /// ```
+    fn dot<R>(self, rhs: R) -> Self::Elem
+    where
+        Self::NROWS: Min<Self::NCOLS, Output = U1>,
+        R: Matrix<Elem = Self::Elem, NROWS = Self::NROWS, NCOLS = Self::NCOLS>,
+        Self::Elem: ops::Add<Self::Elem, Output = Self::Elem>
+            + ops::Mul<Self::Elem, Output = Self::Elem>
+            + Zero,
+    {
+        let mut sum = Self::Elem::zero();
+        for r in 0..self.nrows() {
+            for c in 0..self.ncols() {
+                sum = sum + unsafe { self.unsafe_get(r, c) * rhs.unsafe_get(r, c) };
+            }
+        }
+        sum
+    }
+
+    /// Returns the inner product of two vectors computed in the wider type `A`
+    ///
+    /// Each element is converted to `A` before the multiplication so, for example, the inner
+    /// product of `i16` vectors can be accumulated in an `i32` without overflowing.
+    fn dot_wide<A, R>(self, rhs: R) -> A
+    where
+        Self::NROWS: Min<Self::NCOLS, Output = U1>,
+        R: Matrix<Elem = Self::Elem, NROWS = Self::NROWS, NCOLS = Self::NCOLS>,
+        A: ops::Add<A, Output = A> + ops::Mul<A, Output = A> + From<Self::Elem> + Zero,
+    {
+        let mut sum = A::zero();
+        for r in 0..self.nrows() {
+            for c in 0..self.ncols() {
+                let (a, b) = unsafe { (self.unsafe_get(r, c), rhs.unsafe_get(r, c)) };
+                sum = sum + A::from(a) * A::from(b);
+            }
+        }
+        sum
+    }
+
+    /// Returns the matrix product `self * rhs` evaluated in the wider type `A`
+    ///
+    /// Like `dot_wide`, each element is converted to `A` before the multiplication and the sums are
+    /// accumulated in `A`. Use this with narrow element types (e.g. `i8` or `half::f16`) to avoid
+    /// overflow and rounding errors building up in long sums.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate mat;
+    ///
+    /// use mat::mat;
+    /// use mat::traits::Matrix;
+    ///
+    /// # fn main() {
+    /// let a = mat![[100_i8, 100], [-100, 100]];
+    /// let b = mat![[100_i8], [-100]];
+    ///
+    /// let c = a.mul_wide::<i32, _>(&b);
+    /// assert_eq!((c[0], c[1]), (0, -20_000));
+    /// # }
+    /// ```
+    fn mul_wide<A, R>(self, rhs: R) -> MatGen<A, Self::NROWS, R::NCOLS>
+    where
+        R: Matrix<Elem = Self::Elem, NROWS = Self::NCOLS>,
+        A: ops::Add<A, Output = A> + ops::Mul<A, Output = A> + Copy + From<Self::Elem> + Zero,
+        Self::NROWS: ops::Mul<R::NCOLS>,
+        Prod<Self::NROWS, R::NCOLS>: ArrayLength<A>,
+    {
+        MatGen::from_fn(|r, c| {
+            let mut sum = A::zero();
+            for i in 0..self.ncols() {
+                let (a, b) = unsafe { (self.unsafe_get(r, i), rhs.unsafe_get(i, c)) };
+                sum = sum + A::from(a) * A::from(b);
+            }
+            sum
+        })
+    }
+
+    /// Multiplies every vector of `xs` by this matrix, storing the products in `ys`
+    ///
+    /// The rows of the matrix are the outer loop so each row is reused across all the vectors while
+    /// it's hot in cache, or in registers for small matrices.
+    ///
+    /// # Panics
+    ///
+    /// This operation panics if `xs` and `ys` have different lengths
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mat::traits::Matrix;
+    ///
+    /// // rotate a point cloud 90 degrees about the Z axis
+    /// let rot = mat::mat3([
+    ///     [0, -1, 0],
+    ///     [1, 0, 0],
+    ///     [0, 0, 1],
+    /// ]);
+    /// let points = [mat::vec3(1, 0, 0), mat::vec3(0, 2, 0), mat::vec3(1, 1, 3)];
+    ///
+    /// let mut rotated = [mat::vec3(0, 0, 0); 3];
+    /// (&rot).mul_batch(&points, &mut rotated);
+    /// assert_eq!(rotated, [mat::vec3(0, 1, 0), mat::vec3(-2, 0, 0), mat::vec3(-1, 1, 3)]);
+    /// ```
+    fn mul_batch(
+        self,
+        xs: &[MatGen<Self::Elem, Self::NCOLS, U1>],
+        ys: &mut [MatGen<Self::Elem, Self::NROWS, U1>],
+    ) where
+        Self::NROWS: ops::Mul<U1>,
+        Self::NCOLS: ops::Mul<U1>,
+        Prod<Self::NROWS, U1>: ArrayLength<Self::Elem>,
+        Prod<Self::NCOLS, U1>: ArrayLength<Self::Elem>,
+        Self::Elem: ops::Add<Self::Elem, Output = Self::Elem>
+            + ops::Mul<Self::Elem, Output = Self::Elem>
+            + Zero,
+    {
+        assert_eq!(xs.len(), ys.len());
+
+        for r in 0..self.nrows() {
+            for (x, y) in xs.iter().zip(ys.iter_mut()) {
+                let mut sum = Self::Elem::zero();
+                for (c, x) in x.as_slice().iter().enumerate() {
+                    sum = sum + unsafe { self.unsafe_get(r, c) } * x.clone();
+                }
+                y.buffer[r] = sum;
+            }
+        }
+    }
+
+    /// Returns the symmetric matrix `self * selfᵀ`
+    ///
+    /// Only the upper triangle is computed; it's mirrored into the lower triangle, which saves
+    /// almost half of the multiplications of evaluating `self * self.t()`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate mat;
+    ///
+    /// use mat::mat;
+    /// use mat::traits::{Matrix, Transpose};
+    ///
+    /// # fn main() {
+    /// let a = mat![
+    ///     [1, 2, 3],
+    ///     [4, 5, 6],
+    /// ];
+    ///
+    /// assert_eq!(a.self_outer(), &a * a.t());
+    /// assert_eq!(a.self_inner(), a.t() * &a);
+    /// # }
+    /// ```
+    fn self_outer(self) -> MatGen<Self::Elem, Self::NROWS, Self::NROWS>
+    where
+        Self::NROWS: ops::Mul<Self::NROWS>,
+        Prod<Self::NROWS, Self::NROWS>: ArrayLength<Self::Elem>,
+        Self::Elem: ops::Add<Self::Elem, Output = Self::Elem>
+            + ops::Mul<Self::Elem, Output = Self::Elem>
+            + Zero,
+    {
+        symmetric(|r, c| {
+            let mut sum = Self::Elem::zero();
+            for i in 0..self.ncols() {
+                sum = sum + unsafe { self.unsafe_get(r, i) * self.unsafe_get(c, i) };
+            }
+            sum
+        })
+    }
+
+    /// Returns the symmetric matrix `selfᵀ * self`
+    ///
+    /// Like `self_outer`, only the upper triangle is computed.
+    fn self_inner(self) -> MatGen<Self::Elem, Self::NCOLS, Self::NCOLS>
+    where
+        Self::NCOLS: ops::Mul<Self::NCOLS>,
+        Prod<Self::NCOLS, Self::NCOLS>: ArrayLength<Self::Elem>,
+        Self::Elem: ops::Add<Self::Elem, Output = Self::Elem>
+            + ops::Mul<Self::Elem, Output = Self::Elem>
+            + Zero,
+    {
+        symmetric(|r, c| {
+            let mut sum = Self::Elem::zero();
+            for i in 0..self.nrows() {
+                sum = sum + unsafe { self.unsafe_get(i, r) * self.unsafe_get(i, c) };
+            }
+            sum
+        })
+    }
+
+    /// Returns `true` if the matrix is symmetric, i.e. if every element is within `tol` of its
+    /// transposed element
+    ///
+    /// NaN elements are never within tolerance, and the same holds for the other predicates.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mat::traits::Matrix;
+    ///
+    /// let p = mat::mat3([[4., 1., 0.], [1., 3., 2.], [0., 2.0000001, 5.]]);
+    ///
+    /// assert!((&p).is_symmetric(1e-6));
+    /// assert!(!(&p).is_symmetric(0.));
+    /// assert!((&p).is_finite());
+    ///
+    /// let i = mat::mat2([[1., 1e-9], [0., 1.]]);
+    /// assert!((&i).is_identity(1e-6));
+    /// assert!((&i).is_upper_triangular(0.));
+    /// assert!(!(&i).is_diagonal(0.));
+    /// ```
+    fn is_symmetric(self, tol: Self::Elem) -> bool
+    where
+        Self::NROWS: IsEqual<Self::NCOLS, Output = True>,
+        Self::Elem: ops::Sub<Self::Elem, Output = Self::Elem> + PartialOrd + Zero,
+    {
+        let n = self.nrows();
+        for r in 0..n {
+            for c in r + 1..n {
+                if !within(unsafe { self.unsafe_get(r, c) - self.unsafe_get(c, r) }, &tol) {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    /// Returns `true` if every element outside the diagonal is within `tol` of zero
+    fn is_diagonal(self, tol: Self::Elem) -> bool
+    where
+        Self::Elem: ops::Sub<Self::Elem, Output = Self::Elem> + PartialOrd + Zero,
+    {
+        all(self, |r, c, elem| r == c || within(elem, &tol))
+    }
+
+    /// Returns `true` if the matrix is within `tol`, element-wise, of the identity matrix
+    fn is_identity(self, tol: Self::Elem) -> bool
+    where
+        Self::NROWS: IsEqual<Self::NCOLS, Output = True>,
+        Self::Elem: ops::Sub<Self::Elem, Output = Self::Elem> + One + PartialOrd + Zero,
+    {
+        all(self, |r, c, elem| {
+            if r == c {
+                within(elem - Self::Elem::one(), &tol)
+            } else {
+                within(elem, &tol)
+            }
+        })
+    }
+
+    /// Returns `true` if every element below the diagonal is within `tol` of zero
+    fn is_upper_triangular(self, tol: Self::Elem) -> bool
+    where
+        Self::Elem: ops::Sub<Self::Elem, Output = Self::Elem> + PartialOrd + Zero,
+    {
+        all(self, |r, c, elem| r <= c || within(elem, &tol))
+    }
+
+    /// Returns `true` if no element is infinite or NaN
+    fn is_finite(self) -> bool
+    where
+        Self::Elem: ops::Sub<Self::Elem, Output = Self::Elem> + PartialEq + Zero,
+    {
+        // NOTE `x - x` is NaN if `x` is infinite or NaN, and zero otherwise
+        all(self, |_, _, elem| elem.clone() - elem == Self::Elem::zero())
+    }
+
+    /// Returns the squared Euclidean norm of a vector
+    fn norm_squared(self) -> Self::Elem
+    where
+        Self::NROWS: Min<Self::NCOLS, Output = U1>,
+        Self::Elem: ops::Add<Self::Elem, Output = Self::Elem>
+            + ops::Mul<Self::Elem, Output = Self::Elem>
+            + Zero,
+    {
+        self.dot(self)
+    }
+
+    /// Returns the Euclidean norm of a vector
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mat::traits::Matrix;
+    ///
+    /// let v = mat::vec2(3_f64, 4.);
+    /// assert_eq!(v.norm(), 5.);
+    ///
+    /// let u = v.normalize();
+    /// assert_eq!((u[0], u[1]), (0.6, 0.8));
+    /// ```
+    fn norm(self) -> Self::Elem
+    where
+        Self::NROWS: Min<Self::NCOLS, Output = U1>,
+        Self::Elem: ops::Add<Self::Elem, Output = Self::Elem>
+            + ops::Mul<Self::Elem, Output = Self::Elem>
+            + Sqrt
+            + Zero,
+    {
+        self.norm_squared().sqrt()
+    }
+
+    /// Returns the unit vector that has the same direction as this vector
+    ///
+    /// The result is undefined (e.g. `NaN` elements) if the norm of the vector is zero
+    fn normalize(self) -> MatGen<Self::Elem, Self::NROWS, Self::NCOLS>
+    where
+        Self::NROWS: Min<Self::NCOLS, Output = U1> + ops::Mul<Self::NCOLS>,
+        Prod<Self::NROWS, Self::NCOLS>: ArrayLength<Self::Elem>,
+        Self::Elem: ops::Add<Self::Elem, Output = Self::Elem>
+            + ops::Div<Self::Elem, Output = Self::Elem>
+            + ops::Mul<Self::Elem, Output = Self::Elem>
+            + Sqrt
+            + Zero,
+    {
+        let norm = self.norm();
+        MatGen::from_fn(|r, c| unsafe { self.unsafe_get(r, c) / norm.clone() })
+    }
+
+    /// Returns the element-wise complex conjugate of the matrix, lazily
+    fn conj(self) -> super::Conjugate<Self>
+    where
+        Self::Elem: Conj,
+    {
+        super::Conjugate { m: self }
+    }
+
+    /// Returns the conjugate transpose (adjoint) of the matrix, lazily
+    fn adjoint(self) -> super::Transpose<super::Conjugate<Self>>
+    where
+        Self::Elem: Conj,
+    {
+        super::Transpose {
+            m: super::Conjugate { m: self },
+        }
+    }
+
+    /// Evaluates the expression tree into a new matrix
+    fn eval(self) -> MatGen<Self::Elem, Self::NROWS, Self::NCOLS>
+    where
+        Self::NROWS: ops::Mul<Self::NCOLS>,
+        Prod<Self::NROWS, Self::NCOLS>: ArrayLength<Self::Elem>,
+    {
+        MatGen::from_fn(|r, c| unsafe { self.unsafe_get(r, c) })
+    }
+
+    /// Returns a wrapper whose `Debug` implementation prints the structure of the expression tree
+    ///
+    /// Operations are printed as `Add`, `Mul`, `T` (transpose), `Conj`, `Outer`, `Kron` and
+    /// `Affine`; the operands that are not operations are printed as their dimensions. The `Debug`
+    /// implementations of the expression nodes print the same structure; this wrapper also works
+    /// for leaves like `&MatGen`, whose `Debug` prints their elements.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate mat;
+    /// extern crate typenum;
+    ///
+    /// use mat::MatGen;
+    /// use mat::traits::{Matrix, Transpose};
+    /// use typenum::{U2, U3};
+    ///
+    /// # fn main() {
+    /// let a = MatGen::<i32, U2, U3>::from_fn(|r, c| (r + c) as i32);
+    /// let b = MatGen::<i32, U3, U2>::from_fn(|r, c| (r * c) as i32);
+    ///
+    /// let e = &a * &b + (&b).t() * (&a).t();
+    /// assert_eq!(format!("{:?}", e.tree()), "Add(Mul(2x3, 3x2), Mul(T(3x2), T(2x3)))");
+    /// assert_eq!(format!("{:?}", (&a).tree()), "2x3");
+    /// # }
+    /// ```
+    fn tree(self) -> super::Tree<Self> {
+        super::Tree { m: self }
+    }
+
+    #[doc(hidden)]
+    fn fmt_tree(self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}x{}", self.nrows(), self.ncols())
+    }
+
+    /// The elements in row major order, if this matrix stores them contiguously
+    #[doc(hidden)]
+    fn as_row_major(&self) -> Option<&[Self::Elem]> {
+        None
+    }
+}
+
+// Returns `true` if `f(r, c, m[r, c])` holds for every element of `m`
+fn all<M, F>(m: M, f: F) -> bool
+where
+    M: Matrix,
+    F: Fn(usize, usize, M::Elem) -> bool,
+{
+    let (nrows, ncols) = m.size();
+    for r in 0..nrows {
+        for c in 0..ncols {
+            if !f(r, c, unsafe { m.unsafe_get(r, c) }) {
+                return false;
+            }
+        }
+    }
+    true
+}
+
+// Returns `true` if `x` is within `tol` of zero
+fn within<T>(x: T, tol: &T) -> bool
+where
+    T: ops::Sub<T, Output = T> + PartialOrd + Zero,
+{
+    linalg::abs(x) <= *tol
+}
+
+// Returns the symmetric matrix whose upper triangle is `f(r, c)`
+fn symmetric<T, N, F>(f: F) -> MatGen<T, N, N>
+where
+    N: Unsigned + ops::Mul<N>,
+    Prod<N, N>: ArrayLength<T>,
+    T: Clone + Zero,
+    F: Fn(usize, usize) -> T,
+{
+    let n = N::to_usize();
+    let mut m: MatGen<T, N, N> = MatGen::zeros();
+    for r in 0..n {
+        for c in r..n {
+            let elem = f(r, c);
+            m.buffer[c * n + r] = elem.clone();
+            m.buffer[r * n + c] = elem;
+        }
+    }
+    m
 }
 
 /// Unsafe indexing
 // NOTE(`: Copy`) this bound is a lint against expression trees that take ownership of `Mat`
 pub trait UnsafeGet: Copy {
     /// The matrix element type
-    // NOTE(`: Copy`) let's narrow down the problem to matrices that contain only primitive types
-    type Elem: Copy;
+    ///
+    /// Elements are returned by value so they are cloned out of the matrix; for `Copy` types the
+    /// clone is a plain copy.
+    type Elem: Clone;
 
-    /// Returns the element at row `r` and column `c` with performing bounds checks
+    /// Returns the element at row `r` and column `c` without performing bounds checks
+    ///
+    /// # Safety
+    ///
+    /// `r` must be less than the number of rows and `c` less than the number of columns.
     unsafe fn unsafe_get(self, r: usize, c: usize) -> Self::Elem;
 }
 
+/// Types that have a square root
+///
+/// With the default `libm` feature this is implemented for `f32` and `f64`. On targets without a
+/// floating point unit the `libm` feature can be disabled and this trait implemented for a custom
+/// element type, e.g. a fixed point number with a fast approximate square root. The `fast-math`
+/// feature implements it for `f32` using the approximations of the `micromath` crate.
+pub trait Sqrt {
+    /// Returns the square root of `self`
+    fn sqrt(self) -> Self;
+}
+
+#[cfg(all(feature = "libm", not(feature = "fast-math")))]
+impl Sqrt for f32 {
+    fn sqrt(self) -> f32 {
+        ::libm::sqrtf(self)
+    }
+}
+
+#[cfg(feature = "fast-math")]
+impl Sqrt for f32 {
+    fn sqrt(self) -> f32 {
+        ::micromath::F32Ext::sqrt(self)
+    }
+}
+
+#[cfg(feature = "libm")]
+impl Sqrt for f64 {
+    fn sqrt(self) -> f64 {
+        ::libm::sqrt(self)
+    }
+}
+
+/// Trigonometric functions
+///
+/// With the default `libm` feature this is implemented for `f32` and `f64`. The `fast-math` feature
+/// implements it for `f32` using the approximations of the `micromath` crate, which are faster but
+/// only accurate to a few decimal places.
+pub trait Trig {
+    /// Returns the sine of `self` (in radians)
+    fn sin(self) -> Self;
+
+    /// Returns the cosine of `self` (in radians)
+    fn cos(self) -> Self;
+
+    /// Returns the tangent of `self` (in radians)
+    fn tan(self) -> Self;
+
+    /// Returns the arcsine of `self` in the range `[-π/2, π/2]`
+    fn asin(self) -> Self;
+
+    /// Returns the arccosine of `self` in the range `[0, π]`
+    fn acos(self) -> Self;
+
+    /// Returns the four quadrant arctangent of `self` (`y`) and `x` in the range `[-π, π]`
+    fn atan2(self, x: Self) -> Self;
+}
+
+#[cfg(all(feature = "libm", not(feature = "fast-math")))]
+impl Trig for f32 {
+    fn sin(self) -> f32 {
+        ::libm::sinf(self)
+    }
+
+    fn cos(self) -> f32 {
+        ::libm::cosf(self)
+    }
+
+    fn tan(self) -> f32 {
+        ::libm::tanf(self)
+    }
+
+    fn asin(self) -> f32 {
+        ::libm::asinf(self)
+    }
+
+    fn acos(self) -> f32 {
+        ::libm::acosf(self)
+    }
+
+    fn atan2(self, x: f32) -> f32 {
+        ::libm::atan2f(self, x)
+    }
+}
+
+#[cfg(feature = "fast-math")]
+impl Trig for f32 {
+    fn sin(self) -> f32 {
+        ::micromath::F32Ext::sin(self)
+    }
+
+    fn cos(self) -> f32 {
+        ::micromath::F32Ext::cos(self)
+    }
+
+    fn tan(self) -> f32 {
+        ::micromath::F32Ext::tan(self)
+    }
+
+    fn asin(self) -> f32 {
+        ::micromath::F32Ext::asin(self)
+    }
+
+    fn acos(self) -> f32 {
+        ::micromath::F32Ext::acos(self)
+    }
+
+    fn atan2(self, x: f32) -> f32 {
+        ::micromath::F32Ext::atan2(self, x)
+    }
+}
+
+#[cfg(feature = "libm")]
+impl Trig for f64 {
+    fn sin(self) -> f64 {
+        ::libm::sin(self)
+    }
+
+    fn cos(self) -> f64 {
+        ::libm::cos(self)
+    }
+
+    fn tan(self) -> f64 {
+        ::libm::tan(self)
+    }
+
+    fn asin(self) -> f64 {
+        ::libm::asin(self)
+    }
+
+    fn acos(self) -> f64 {
+        ::libm::acos(self)
+    }
+
+    fn atan2(self, x: f64) -> f64 {
+        ::libm::atan2(self, x)
+    }
+}
+
+/// The exponential function
+///
+/// With the default `libm` feature this is implemented for `f32` and `f64`. The `fast-math` feature
+/// implements it for `f32` using the approximation of the `micromath` crate.
+pub trait Exp {
+    /// Returns `e^self`
+    fn exp(self) -> Self;
+}
+
+#[cfg(all(feature = "libm", not(feature = "fast-math")))]
+impl Exp for f32 {
+    fn exp(self) -> f32 {
+        ::libm::expf(self)
+    }
+}
+
+#[cfg(feature = "fast-math")]
+impl Exp for f32 {
+    fn exp(self) -> f32 {
+        ::micromath::F32Ext::exp(self)
+    }
+}
+
+#[cfg(feature = "libm")]
+impl Exp for f64 {
+    fn exp(self) -> f64 {
+        ::libm::exp(self)
+    }
+}
+
+/// The natural logarithm
+///
+/// With the default `libm` feature this is implemented for `f32` and `f64`. The `fast-math` feature
+/// implements it for `f32` using the approximation of the `micromath` crate.
+pub trait Ln {
+    /// Returns the natural logarithm of `self`
+    fn ln(self) -> Self;
+}
+
+#[cfg(all(feature = "libm", not(feature = "fast-math")))]
+impl Ln for f32 {
+    fn ln(self) -> f32 {
+        ::libm::logf(self)
+    }
+}
+
+#[cfg(feature = "fast-math")]
+impl Ln for f32 {
+    fn ln(self) -> f32 {
+        ::micromath::F32Ext::ln(self)
+    }
+}
+
+#[cfg(feature = "libm")]
+impl Ln for f64 {
+    fn ln(self) -> f64 {
+        ::libm::log(self)
+    }
+}
+
+/// Types that have a complex conjugate
+///
+/// Real numbers are their own conjugate.
+pub trait Conj: Clone {
+    /// Returns the complex conjugate of `self`
+    fn conj(self) -> Self;
+}
+
+macro_rules! conj {
+    ($($ty:ty),+) => {
+        $(
+            impl Conj for $ty {
+                fn conj(self) -> Self {
+                    self
+                }
+            }
+        )+
+    }
+}
+
+conj!(f32, f64, i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+/// Types that have a "one" value
+///
+/// With the `num-traits` feature this trait is implemented for every type that implements
+/// `num_traits::One` instead of for a fixed list of primitive types.
+pub trait One {
+    /// Returns the value of this type that represents the number one
+    fn one() -> Self;
+}
+
+#[cfg(feature = "num-traits")]
+impl<T> One for T
+where
+    T: ::num_traits::One,
+{
+    fn one() -> T {
+        ::num_traits::One::one()
+    }
+}
+
+#[cfg(not(feature = "num-traits"))]
+macro_rules! one {
+    ($($ty:ty),+) => {
+        $(
+            impl One for $ty {
+                fn one() -> Self {
+                    1
+                }
+            }
+        )+
+    }
+}
+
+#[cfg(not(feature = "num-traits"))]
+one!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+#[cfg(not(feature = "num-traits"))]
+impl One for f32 {
+    fn one() -> f32 {
+        1.
+    }
+}
+
+#[cfg(not(feature = "num-traits"))]
+impl One for f64 {
+    fn one() -> f64 {
+        1.
+    }
+}
+
 /// Types that have a "zero" value
+///
+/// With the `num-traits` feature this trait is implemented for every type that implements
+/// `num_traits::Zero` (e.g. the primitive types, `num_complex::Complex` or the fixed point types of
+/// the `fixed` crate) instead of for a fixed list of primitive types.
 pub trait Zero {
     /// Returns the value of this type that represents the number zero
     fn zero() -> Self;
+
+    /// Stores the product of the `m` by `k` matrix `a` and the `k` by `n` matrix `b` in `c` using
+    /// the `gemm::DefaultBackend` of this type
+    ///
+    /// Returns `false`, and leaves `c` untouched, if this type doesn't have a default backend.
+    #[doc(hidden)]
+    fn gemm(_a: &[Self], _b: &[Self], _c: &mut [Self], _m: usize, _k: usize, _n: usize) -> bool
+    where
+        Self: Sized,
+    {
+        false
+    }
+}
+
+#[cfg(feature = "num-traits")]
+impl<T> Zero for T
+where
+    T: ::num_traits::Zero,
+{
+    fn zero() -> T {
+        ::num_traits::Zero::zero()
+    }
 }
 
+#[cfg(not(feature = "num-traits"))]
 macro_rules! zero {
     ($($ty:ty),+) => {
         $(
@@ -68,21 +924,43 @@ macro_rules! zero {
                 fn zero() -> Self {
                     0
                 }
+
+                fn gemm(
+                    a: &[$ty],
+                    b: &[$ty],
+                    c: &mut [$ty],
+                    m: usize,
+                    k: usize,
+                    n: usize,
+                ) -> bool {
+                    ::gemm::default_gemm(a, b, c, m, k, n)
+                }
             }
         )+
     }
 }
 
+#[cfg(not(feature = "num-traits"))]
 zero!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
 
+#[cfg(not(feature = "num-traits"))]
 impl Zero for f32 {
     fn zero() -> f32 {
         0.
     }
+
+    fn gemm(a: &[f32], b: &[f32], c: &mut [f32], m: usize, k: usize, n: usize) -> bool {
+        ::gemm::default_gemm(a, b, c, m, k, n)
+    }
 }
 
+#[cfg(not(feature = "num-traits"))]
 impl Zero for f64 {
     fn zero() -> f64 {
         0.
     }
+
+    fn gemm(a: &[f64], b: &[f64], c: &mut [f64], m: usize, k: usize, n: usize) -> bool {
+        ::gemm::default_gemm(a, b, c, m, k, n)
+    }
 }
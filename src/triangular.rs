@@ -0,0 +1,396 @@
+use core::{fmt, ops};
+use core::ops::Range;
+
+use generic_array::{ArrayLength, GenericArray};
+use typenum::{Add1, B1, Prod, U2, Unsigned};
+
+use sym::{upper_index, upper_position, PackedLen};
+use traits::{Matrix, UnsafeGet, Zero};
+use {array, text, Error, MatGen, Product, Sum};
+
+/// The product of a triangular matrix and a matrix
+///
+/// Each element only sums over the non-zero elements of its row of the triangular matrix.
+#[derive(Clone, Copy)]
+pub struct TriangularProduct<L, R> {
+    l: L,
+    r: R,
+}
+
+macro_rules! triangular {
+    ($(#[$attr:meta])* $Tri:ident, $index:ident, $position:ident, $contains:ident, $terms:ident,
+     $row:ident) => {
+        $(#[$attr])*
+        pub struct $Tri<T, N>
+        where
+            N: Unsigned + ops::Add<B1> + ops::Mul<Add1<N>>,
+            Prod<N, Add1<N>>: ops::Div<U2>,
+            PackedLen<N>: ArrayLength<T>,
+            T: Clone,
+        {
+            packed: GenericArray<T, PackedLen<N>>,
+        }
+
+        impl<T, N> Clone for $Tri<T, N>
+        where
+            N: Unsigned + ops::Add<B1> + ops::Mul<Add1<N>>,
+            Prod<N, Add1<N>>: ops::Div<U2>,
+            PackedLen<N>: ArrayLength<T>,
+            T: Clone,
+        {
+            fn clone(&self) -> Self {
+                let packed = self.packed();
+                $Tri::new(array::generate(|i| packed[i].clone()))
+            }
+        }
+
+        impl<T, N> $Tri<T, N>
+        where
+            N: Unsigned + ops::Add<B1> + ops::Mul<Add1<N>>,
+            Prod<N, Add1<N>>: ops::Div<U2>,
+            PackedLen<N>: ArrayLength<T>,
+            T: Clone,
+        {
+            /// Creates a triangular matrix from its packed triangle, in row major order
+            pub fn new(packed: GenericArray<T, PackedLen<N>>) -> Self {
+                $Tri { packed }
+            }
+
+            /// Creates a triangular matrix where the element at row `r` and column `c` is `f(r,
+            /// c)`
+            ///
+            /// `f` is only called for the elements of the triangle.
+            pub fn from_fn<F>(f: F) -> Self
+            where
+                F: Fn(usize, usize) -> T,
+            {
+                let n = N::to_usize();
+                $Tri::new(array::generate(|i| {
+                    let (r, c) = $position(n, i);
+                    f(r, c)
+                }))
+            }
+
+            /// Creates a triangular matrix from the triangle of `m`; the other elements of `m`
+            /// are ignored
+            pub fn from_matrix<M>(m: M) -> Self
+            where
+                M: Matrix<Elem = T, NROWS = N, NCOLS = N>,
+            {
+                $Tri::from_fn(|r, c| unsafe { m.unsafe_get(r, c) })
+            }
+
+            /// Returns the packed triangle
+            pub fn packed(&self) -> &[T] {
+                array::as_slice(&self.packed)
+            }
+
+            /// Sets the element at row `r` and column `c` to `value`
+            ///
+            /// # Panics
+            ///
+            /// This operation panics if `r` or `c` exceed the matrix dimensions, or if the
+            /// element is outside the triangle
+            pub fn set(&mut self, r: usize, c: usize, value: T) {
+                let n = N::to_usize();
+                assert!(r < n && c < n && $contains(r, c));
+
+                array::as_mut_slice(&mut self.packed)[$index(n, r, c)] = value;
+            }
+
+            /// Sets the element at row `r` and column `c` to `value`
+            ///
+            /// Returns `value` back if `r` or `c` exceed the matrix dimensions, or if the
+            /// element is outside the triangle.
+            pub fn set_checked(&mut self, r: usize, c: usize, value: T) -> Result<(), T> {
+                let n = N::to_usize();
+                no_panic!(if r < n && c < n && $contains(r, c) {
+                    match array::as_mut_slice(&mut self.packed).get_mut($index(n, r, c)) {
+                        Some(elem) => {
+                            *elem = value;
+                            Ok(())
+                        }
+                        None => Err(value),
+                    }
+                } else {
+                    Err(value)
+                })
+            }
+
+            /// Solves the linear system `A X = B` by substitution, in `O(N²)` operations per
+            /// column of `B`
+            ///
+            /// Returns `Error::Singular` if `A` is singular, i.e. if an element of its diagonal
+            /// is zero.
+            pub fn solve<B, C>(&self, b: B) -> Result<MatGen<T, N, C>, Error>
+            where
+                B: Matrix<Elem = T, NROWS = N, NCOLS = C>,
+                N: ops::Mul<C>,
+                C: Unsigned,
+                Prod<N, C>: ArrayLength<T>,
+                T: ops::Div<T, Output = T>
+                    + ops::Mul<T, Output = T>
+                    + ops::Sub<T, Output = T>
+                    + PartialEq
+                    + Zero,
+            {
+                let n = N::to_usize();
+                let ncols = C::to_usize();
+                let mut x = b.eval();
+
+                for i in 0..n {
+                    let r = $row(n, i);
+                    let d = self.packed()[$index(n, r, r)].clone();
+                    if d == T::zero() {
+                        return Err(Error::Singular);
+                    }
+
+                    for c in 0..ncols {
+                        let mut sum = x.buffer[r * ncols + c].clone();
+                        for k in $terms(n, r).filter(|&k| k != r) {
+                            sum = sum
+                                - self.packed()[$index(n, r, k)].clone()
+                                    * x.buffer[k * ncols + c].clone();
+                        }
+                        x.buffer[r * ncols + c] = sum / d.clone();
+                    }
+                }
+
+                Ok(x)
+            }
+        }
+
+        impl<'a, T, N> Matrix for &'a $Tri<T, N>
+        where
+            N: Unsigned + ops::Add<B1> + ops::Mul<Add1<N>>,
+            Prod<N, Add1<N>>: ops::Div<U2>,
+            PackedLen<N>: ArrayLength<T>,
+            T: Clone + Zero,
+        {
+            type NROWS = N;
+            type NCOLS = N;
+        }
+
+        impl<'a, T, N> UnsafeGet for &'a $Tri<T, N>
+        where
+            N: Unsigned + ops::Add<B1> + ops::Mul<Add1<N>>,
+            Prod<N, Add1<N>>: ops::Div<U2>,
+            PackedLen<N>: ArrayLength<T>,
+            T: Clone + Zero,
+        {
+            type Elem = T;
+
+            unsafe fn unsafe_get(self, r: usize, c: usize) -> T {
+                if $contains(r, c) {
+                    self.packed().get_unchecked($index(N::to_usize(), r, c)).clone()
+                } else {
+                    T::zero()
+                }
+            }
+        }
+
+        impl<'a, T, N, R> ops::Mul<R> for &'a $Tri<T, N>
+        where
+            N: Unsigned + ops::Add<B1> + ops::Mul<Add1<N>>,
+            Prod<N, Add1<N>>: ops::Div<U2>,
+            PackedLen<N>: ArrayLength<T>,
+            T: Clone + Zero,
+            R: Matrix<NROWS = N>,
+        {
+            type Output = TriangularProduct<&'a $Tri<T, N>, R>;
+
+            fn mul(self, rhs: R) -> Self::Output {
+                TriangularProduct { l: self, r: rhs }
+            }
+        }
+
+        impl<'a, T, N, R> Matrix for TriangularProduct<&'a $Tri<T, N>, R>
+        where
+            N: Unsigned + ops::Add<B1> + ops::Mul<Add1<N>>,
+            Prod<N, Add1<N>>: ops::Div<U2>,
+            PackedLen<N>: ArrayLength<T>,
+            R: Matrix<Elem = T, NROWS = N>,
+            T: ops::Add<T, Output = T> + ops::Mul<T, Output = T> + Clone + Zero,
+        {
+            type NROWS = N;
+            type NCOLS = R::NCOLS;
+
+            fn fmt_tree(self, f: &mut fmt::Formatter) -> fmt::Result {
+                text::fmt_node(f, "Mul", self.l, self.r)
+            }
+        }
+
+        impl<'a, T, N, R> UnsafeGet for TriangularProduct<&'a $Tri<T, N>, R>
+        where
+            N: Unsigned + ops::Add<B1> + ops::Mul<Add1<N>>,
+            Prod<N, Add1<N>>: ops::Div<U2>,
+            PackedLen<N>: ArrayLength<T>,
+            R: Matrix<Elem = T, NROWS = N>,
+            T: ops::Add<T, Output = T> + ops::Mul<T, Output = T> + Clone + Zero,
+        {
+            type Elem = T;
+
+            unsafe fn unsafe_get(self, r: usize, c: usize) -> T {
+                let n = N::to_usize();
+                let mut sum = T::zero();
+                for k in $terms(n, r) {
+                    sum = sum
+                        + self.l.packed.get_unchecked($index(n, r, k)).clone()
+                            * self.r.unsafe_get(k, c);
+                }
+                sum
+            }
+        }
+    };
+}
+
+triangular!(
+    /// Upper triangular matrix that only stores the elements on and above its diagonal
+    ///
+    /// # Example
+    ///
+    /// Solving a least squares problem with the QR factorization
+    ///
+    /// ```
+    /// extern crate mat;
+    /// extern crate typenum;
+    ///
+    /// use mat::{linalg, MatGen, UpperTriangular};
+    /// use mat::traits::{Matrix, Transpose};
+    /// use typenum::{U1, U2, U3};
+    ///
+    /// # fn main() {
+    /// // fit `y = a + b x` to the points (0, 1), (1, 3) and (2, 5)
+    /// let a = MatGen::<f64, U3, U2>::from_fn(|r, c| if c == 0 { 1. } else { r as f64 });
+    /// let y = MatGen::<f64, U3, U1>::from_fn(|r, _| [1., 3., 5.][r]);
+    ///
+    /// let (q, r) = linalg::qr(&a);
+    /// let qty = (q.t() * &y).eval();
+    ///
+    /// // the top 2 x 2 block of `R`
+    /// let r = UpperTriangular::<f64, U2>::from_fn(|i, j| r.get(i, j));
+    /// let rhs = MatGen::<f64, U2, U1>::from_fn(|i, _| qty[i]);
+    /// let x = r.solve(&rhs).unwrap();
+    ///
+    /// assert!((x[0] - 1.).abs() < 1e-12);
+    /// assert!((x[1] - 2.).abs() < 1e-12);
+    ///
+    /// // `R x = rhs`
+    /// assert!(((&r * &x).get(1, 0) - rhs[1]).abs() < 1e-12);
+    /// # }
+    /// ```
+    UpperTriangular,
+    upper_index,
+    upper_position,
+    upper_contains,
+    upper_terms,
+    upper_row
+);
+
+triangular!(
+    /// Lower triangular matrix that only stores the elements on and below its diagonal
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate mat;
+    /// extern crate typenum;
+    ///
+    /// use std::mem;
+    ///
+    /// use mat::{LowerTriangular, MatGen};
+    /// use mat::traits::Matrix;
+    /// use typenum::{U1, U3};
+    ///
+    /// # fn main() {
+    /// let l = LowerTriangular::<i32, U3>::from_fn(|r, c| (r + c + 1) as i32);
+    /// assert_eq!(mem::size_of_val(&l), 6 * mem::size_of::<i32>());
+    /// assert_eq!((&l).get(2, 1), 4);
+    /// assert_eq!((&l).get(1, 2), 0);
+    ///
+    /// let x = MatGen::<i32, U3, U1>::from_fn(|r, _| r as i32 + 1);
+    /// let b = (&l * &x).eval();
+    /// assert_eq!(b, &(&l).eval() * &x);
+    ///
+    /// // forward substitution
+    /// assert_eq!(l.solve(&b), Ok(x));
+    /// # }
+    /// ```
+    LowerTriangular,
+    lower_index,
+    lower_position,
+    lower_contains,
+    lower_terms,
+    lower_row
+);
+
+fn upper_contains(r: usize, c: usize) -> bool {
+    r <= c
+}
+
+/// Columns of the non-zero elements of row `r`
+fn upper_terms(n: usize, r: usize) -> Range<usize> {
+    r..n
+}
+
+/// Back substitution solves for the last row first
+fn upper_row(n: usize, i: usize) -> usize {
+    n - 1 - i
+}
+
+/// Index of the element at `(r, c)` in the packed lower triangle
+fn lower_index(_: usize, r: usize, c: usize) -> usize {
+    r * (r + 1) / 2 + c
+}
+
+/// Row and column of the element at index `i` of the packed lower triangle
+fn lower_position(_: usize, i: usize) -> (usize, usize) {
+    // row `r` starts at index `start` and has `r + 1` elements
+    let (mut r, mut start) = (0, 0);
+    while i > start + r {
+        start += r + 1;
+        r += 1;
+    }
+    (r, i - start)
+}
+
+fn lower_contains(r: usize, c: usize) -> bool {
+    r >= c
+}
+
+/// Columns of the non-zero elements of row `r`
+fn lower_terms(_: usize, r: usize) -> Range<usize> {
+    0..r + 1
+}
+
+/// Forward substitution solves for the first row first
+fn lower_row(_: usize, i: usize) -> usize {
+    i
+}
+
+impl<L, R, RHS> ops::Mul<RHS> for TriangularProduct<L, R>
+where
+    TriangularProduct<L, R>: Matrix,
+    RHS: Matrix<NROWS = <TriangularProduct<L, R> as Matrix>::NCOLS>,
+{
+    type Output = Product<TriangularProduct<L, R>, RHS>;
+
+    fn mul(self, rhs: RHS) -> Self::Output {
+        Product { l: self, r: rhs }
+    }
+}
+
+impl<L, R, RHS> ops::Add<RHS> for TriangularProduct<L, R>
+where
+    TriangularProduct<L, R>: Matrix,
+    RHS: Matrix<
+        NROWS = <TriangularProduct<L, R> as Matrix>::NROWS,
+        NCOLS = <TriangularProduct<L, R> as Matrix>::NCOLS,
+    >,
+{
+    type Output = Sum<TriangularProduct<L, R>, RHS>;
+
+    fn add(self, rhs: RHS) -> Self::Output {
+        Sum { l: self, r: rhs }
+    }
+}
@@ -0,0 +1,238 @@
+//! Window functions
+//!
+//! `hann`, `hamming` and `blackman` return the symmetric window of length `N` as a column vector;
+//! multiply it element-wise with a block of samples before a correlation or a spectral estimate to
+//! reduce spectral leakage. These evaluate cosines through the `Trig` trait, which requires the
+//! `libm` or the `fast-math` feature for floating point elements. The `_tabulated` variants return
+//! precomputed `f32` windows for the common lengths 16, 32 and 64 and need no math functions at
+//! all.
+//!
+//! # Example
+//!
+//! ```
+//! extern crate mat;
+//! extern crate typenum;
+//!
+//! use mat::window;
+//! use typenum::U32;
+//!
+//! # fn main() {
+//! let w = window::hann::<f32, U32>();
+//!
+//! // symmetric, zero at both ends and close to one at the center
+//! assert_eq!(w.get(0), 0.);
+//! assert!((w.get(31) - w.get(0)).abs() < 1e-6);
+//! assert!(w.get(15) > 0.99);
+//!
+//! // NOTE the `fast-math` feature computes the window with an approximate cosine
+//! let tol = if cfg!(feature = "fast-math") { 1e-3 } else { 1e-6 };
+//! let t = window::hann_tabulated::<U32>();
+//! for i in 0..32 {
+//!     assert!((w.get(i) - t.get(i)).abs() < tol);
+//! }
+//! # }
+//! ```
+
+use core::ops;
+
+use generic_array::ArrayLength;
+use typenum::{Prod, U1, U16, U32, U64, Unsigned};
+
+use traits::Trig;
+use {MatGen, Vector};
+
+/// Returns the Hann window, `0.5 - 0.5 cos(2π i / (N - 1))`
+pub fn hann<T, N>() -> Vector<T, N>
+where
+    N: Unsigned + ops::Mul<U1>,
+    Prod<N, U1>: ArrayLength<T>,
+    T: ops::Add<T, Output = T>
+        + ops::Div<T, Output = T>
+        + ops::Mul<T, Output = T>
+        + ops::Sub<T, Output = T>
+        + Clone
+        + From<u16>
+        + Trig,
+{
+    cosine_sum(&[25, 25])
+}
+
+/// Returns the Hamming window, `0.54 - 0.46 cos(2π i / (N - 1))`
+pub fn hamming<T, N>() -> Vector<T, N>
+where
+    N: Unsigned + ops::Mul<U1>,
+    Prod<N, U1>: ArrayLength<T>,
+    T: ops::Add<T, Output = T>
+        + ops::Div<T, Output = T>
+        + ops::Mul<T, Output = T>
+        + ops::Sub<T, Output = T>
+        + Clone
+        + From<u16>
+        + Trig,
+{
+    cosine_sum(&[27, 23])
+}
+
+/// Returns the Blackman window, `0.42 - 0.5 cos(2π i / (N - 1)) + 0.08 cos(4π i / (N - 1))`
+pub fn blackman<T, N>() -> Vector<T, N>
+where
+    N: Unsigned + ops::Mul<U1>,
+    Prod<N, U1>: ArrayLength<T>,
+    T: ops::Add<T, Output = T>
+        + ops::Div<T, Output = T>
+        + ops::Mul<T, Output = T>
+        + ops::Sub<T, Output = T>
+        + Clone
+        + From<u16>
+        + Trig,
+{
+    cosine_sum(&[21, 25, 4])
+}
+
+/// A window length for which the windows have been precomputed
+pub trait Tabulated: Unsigned {
+    #[doc(hidden)]
+    const HANN: &'static [f32];
+    #[doc(hidden)]
+    const HAMMING: &'static [f32];
+    #[doc(hidden)]
+    const BLACKMAN: &'static [f32];
+}
+
+/// Returns the precomputed Hann window
+pub fn hann_tabulated<N>() -> Vector<f32, N>
+where
+    N: Tabulated + ops::Mul<U1>,
+    Prod<N, U1>: ArrayLength<f32>,
+{
+    MatGen::from_fn(|r, _| N::HANN[r])
+}
+
+/// Returns the precomputed Hamming window
+pub fn hamming_tabulated<N>() -> Vector<f32, N>
+where
+    N: Tabulated + ops::Mul<U1>,
+    Prod<N, U1>: ArrayLength<f32>,
+{
+    MatGen::from_fn(|r, _| N::HAMMING[r])
+}
+
+/// Returns the precomputed Blackman window
+pub fn blackman_tabulated<N>() -> Vector<f32, N>
+where
+    N: Tabulated + ops::Mul<U1>,
+    Prod<N, U1>: ArrayLength<f32>,
+{
+    MatGen::from_fn(|r, _| N::BLACKMAN[r])
+}
+
+// `Σ (-1)^k a[k] / 50 cos(2π k i / (N - 1))`; all the classic coefficients are multiples of 1/50
+fn cosine_sum<T, N>(a: &[u16]) -> Vector<T, N>
+where
+    N: Unsigned + ops::Mul<U1>,
+    Prod<N, U1>: ArrayLength<T>,
+    T: ops::Add<T, Output = T>
+        + ops::Div<T, Output = T>
+        + ops::Mul<T, Output = T>
+        + ops::Sub<T, Output = T>
+        + Clone
+        + From<u16>
+        + Trig,
+{
+    let n = N::to_usize();
+    // NOTE by convention a window of length one is a single one; the formula would divide by zero
+    if n == 1 {
+        return MatGen::from_fn(|_, _| T::from(1));
+    }
+
+    let pi = (T::from(0) - T::from(1)).acos();
+    let step = T::from(2) * pi / T::from(n.saturating_sub(1) as u16);
+
+    MatGen::from_fn(|i, _| {
+        let x = step.clone() * T::from(i as u16);
+
+        let mut w = T::from(0);
+        for (k, a) in a.iter().enumerate() {
+            let term = T::from(*a) * (x.clone() * T::from(k as u16)).cos();
+            w = if k % 2 == 0 { w + term } else { w - term };
+        }
+        w / T::from(50)
+    })
+}
+
+impl Tabulated for U16 {
+    const HANN: &'static [f32] = &[
+        0., 0.04322727, 0.1654347, 0.3454915, 0.5522642, 0.75, 0.9045085, 0.9890738, 0.9890738,
+        0.9045085, 0.75, 0.5522642, 0.3454915, 0.1654347, 0.04322727, 0.,
+    ];
+
+    const HAMMING: &'static [f32] = &[
+        0.08, 0.11976909, 0.23219992, 0.39785218, 0.5880831, 0.77, 0.9121478, 0.9899479, 0.9899479,
+        0.9121478, 0.77, 0.5880831, 0.39785218, 0.23219992, 0.11976909, 0.08,
+    ];
+
+    const BLACKMAN: &'static [f32] = &[
+        0., 0.01675772, 0.07707242, 0.20077014, 0.39401242, 0.63, 0.8492299, 0.9821574, 0.9821574,
+        0.8492299, 0.63, 0.39401242, 0.20077014, 0.07707242, 0.01675772, 0.,
+    ];
+}
+
+impl Tabulated for U32 {
+    const HANN: &'static [f32] = &[
+        0., 0.010235029, 0.040521093, 0.08961828, 0.15551654, 0.235518, 0.32634738, 0.4242861,
+        0.5253246, 0.6253263, 0.7201971, 0.806053, 0.87937903, 0.9371733, 0.9770696, 0.9974347,
+        0.9974347, 0.9770696, 0.9371733, 0.87937903, 0.806053, 0.7201971, 0.6253263, 0.5253246,
+        0.4242861, 0.32634738, 0.235518, 0.15551654, 0.08961828, 0.040521093, 0.010235029, 0.,
+    ];
+
+    const HAMMING: &'static [f32] = &[
+        0.08, 0.08941623, 0.11727941, 0.16244882, 0.22307521, 0.29667655, 0.38023958, 0.47034323,
+        0.56329864, 0.65530014, 0.7425813, 0.8215687, 0.8890287, 0.94219947, 0.97890407, 0.9976399,
+        0.9976399, 0.97890407, 0.94219947, 0.8890287, 0.8215687, 0.7425813, 0.65530014, 0.56329864,
+        0.47034323, 0.38023958, 0.29667655, 0.22307521, 0.16244882, 0.11727941, 0.08941623, 0.08,
+    ];
+
+    const BLACKMAN: &'static [f32] = &[
+        0., 0.0037516544, 0.015638448, 0.0374027, 0.071464606, 0.120286465, 0.18564673, 0.26795498,
+        0.36573502, 0.47537854, 0.5912286, 0.7060008, 0.8114933, 0.8994904, 0.9627307, 0.99579704,
+        0.99579704, 0.9627307, 0.8994904, 0.8114933, 0.7060008, 0.5912286, 0.47537854, 0.36573502,
+        0.26795498, 0.18564673, 0.120286465, 0.071464606, 0.0374027, 0.015638448, 0.0037516544, 0.,
+    ];
+}
+
+impl Tabulated for U64 {
+    const HANN: &'static [f32] = &[
+        0., 0.0024846124, 0.0099137565, 0.022213597, 0.039261892, 0.060889214, 0.08688061,
+        0.11697778, 0.15088159, 0.1882551, 0.22872686, 0.27189466, 0.3173295, 0.36457977, 0.4131759,
+        0.46263495, 0.51246536, 0.5621719, 0.6112605, 0.65924335, 0.70564353, 0.75, 0.79187185,
+        0.8308429, 0.86652595, 0.89856625, 0.92664546, 0.95048445, 0.9698463, 0.9845386, 0.9944154,
+        0.99937844, 0.99937844, 0.9944154, 0.9845386, 0.9698463, 0.95048445, 0.92664546, 0.89856625,
+        0.86652595, 0.8308429, 0.79187185, 0.75, 0.70564353, 0.65924335, 0.6112605, 0.5621719,
+        0.51246536, 0.46263495, 0.4131759, 0.36457977, 0.3173295, 0.27189466, 0.22872686, 0.1882551,
+        0.15088159, 0.11697778, 0.08688061, 0.060889214, 0.039261892, 0.022213597, 0.0099137565,
+        0.0024846124, 0.,
+    ];
+
+    const HAMMING: &'static [f32] = &[
+        0.08, 0.082285844, 0.089120656, 0.10043651, 0.11612094, 0.13601808, 0.15993017, 0.18761955,
+        0.21881106, 0.2531947, 0.29042873, 0.3301431, 0.37194312, 0.41541338, 0.46012184, 0.5056242,
+        0.55146813, 0.5971981, 0.6423596, 0.6865039, 0.7291921, 0.77, 0.8085221, 0.8443755,
+        0.8772039, 0.90668094, 0.93251383, 0.95444566, 0.9722586, 0.98577553, 0.9948622, 0.9994282,
+        0.9994282, 0.9948622, 0.98577553, 0.9722586, 0.95444566, 0.93251383, 0.90668094, 0.8772039,
+        0.8443755, 0.8085221, 0.77, 0.7291921, 0.6865039, 0.6423596, 0.5971981, 0.55146813,
+        0.5056242, 0.46012184, 0.41541338, 0.37194312, 0.3301431, 0.29042873, 0.2531947, 0.21881106,
+        0.18761955, 0.15993017, 0.13601808, 0.11612094, 0.10043651, 0.089120656, 0.082285844, 0.08,
+    ];
+
+    const BLACKMAN: &'static [f32] = &[
+        0., 0.0008984113, 0.003631853, 0.008312699, 0.01512084, 0.024292914, 0.036107894,
+        0.050869633, 0.06888714, 0.09045342, 0.1158239, 0.14519517, 0.17868534, 0.21631649,
+        0.2580005, 0.3035285, 0.35256478, 0.40464568, 0.45918295, 0.5154727, 0.57270867, 0.63,
+        0.6863929, 0.74089545, 0.7925044, 0.8402335, 0.8831423, 0.9203636, 0.95112985, 0.97479635,
+        0.99086124, 0.99898094, 0.99898094, 0.99086124, 0.97479635, 0.95112985, 0.9203636,
+        0.8831423, 0.8402335, 0.7925044, 0.74089545, 0.6863929, 0.63, 0.57270867, 0.5154727,
+        0.45918295, 0.40464568, 0.35256478, 0.3035285, 0.2580005, 0.21631649, 0.17868534,
+        0.14519517, 0.1158239, 0.09045342, 0.06888714, 0.050869633, 0.036107894, 0.024292914,
+        0.01512084, 0.008312699, 0.003631853, 0.0008984113, 0.,
+    ];
+}